@@ -0,0 +1,37 @@
+-- Create mobiles table (stores mobile definitions/templates from .are files)
+CREATE TABLE IF NOT EXISTS mobiles (
+    id SERIAL PRIMARY KEY,
+    vnum INTEGER NOT NULL UNIQUE,
+    area_name TEXT NOT NULL,
+    keywords TEXT NOT NULL,
+    short_description TEXT NOT NULL,
+    long_description TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    race TEXT NOT NULL DEFAULT 'human',
+    act_flags TEXT NOT NULL DEFAULT '',
+    affect_flags TEXT NOT NULL DEFAULT '',
+    alignment INTEGER NOT NULL DEFAULT 0,
+    level INTEGER NOT NULL DEFAULT 1,
+    hitroll INTEGER NOT NULL DEFAULT 0,
+    hit_dice TEXT NOT NULL DEFAULT '1d1+0',  -- e.g. '2d6+10'
+    mana_dice TEXT NOT NULL DEFAULT '1d1+0',
+    damage_dice TEXT NOT NULL DEFAULT '1d4+0',
+    damage_type TEXT NOT NULL DEFAULT 'hit',
+    ac_pierce INTEGER NOT NULL DEFAULT 0,
+    ac_bash INTEGER NOT NULL DEFAULT 0,
+    ac_slash INTEGER NOT NULL DEFAULT 0,
+    ac_exotic INTEGER NOT NULL DEFAULT 0,
+    start_position TEXT NOT NULL DEFAULT 'stand',
+    default_position TEXT NOT NULL DEFAULT 'stand',
+    gender TEXT NOT NULL DEFAULT 'either',
+    wealth INTEGER NOT NULL DEFAULT 0,
+    size TEXT NOT NULL DEFAULT 'medium',
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+
+-- Create index on vnum for fast lookups
+CREATE INDEX IF NOT EXISTS idx_mobiles_vnum ON mobiles(vnum);
+
+-- Create index on area_name for area-based queries
+CREATE INDEX IF NOT EXISTS idx_mobiles_area_name ON mobiles(area_name);
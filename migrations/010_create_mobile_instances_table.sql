@@ -0,0 +1,19 @@
+-- Create mobile_instances table (stores actual spawned mobiles in the game)
+CREATE TABLE IF NOT EXISTS mobile_instances (
+    id SERIAL PRIMARY KEY,
+    mobile_vnum INTEGER NOT NULL,
+    room_id TEXT NOT NULL, -- room channel_id the mobile is currently in
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL,
+    FOREIGN KEY (mobile_vnum) REFERENCES mobiles(vnum) ON DELETE CASCADE
+);
+
+-- Create index on mobile_vnum for limit checks
+CREATE INDEX IF NOT EXISTS idx_mobile_instances_vnum ON mobile_instances(mobile_vnum);
+
+-- Create index on room_id for finding mobiles in a room
+CREATE INDEX IF NOT EXISTS idx_mobile_instances_room ON mobile_instances(room_id);
+
+-- Objects carried or worn by mobiles live in object_instances with:
+-- location_type = 'mobile'          (carried, location_id = mobile instance id)
+-- location_type = 'mobile_equipped' (worn, location_id = mobile instance id, equipped_slot set)
@@ -0,0 +1,16 @@
+-- Create area_resets table (stores the #RESETS section of imported areas so they can be re-applied)
+CREATE TABLE IF NOT EXISTS area_resets (
+    id SERIAL PRIMARY KEY,
+    area_name TEXT NOT NULL,
+    seq INTEGER NOT NULL, -- position of the reset within the area file
+    command TEXT NOT NULL, -- 'M', 'O', 'G', 'E', 'P', 'D', 'R'
+    if_flag INTEGER NOT NULL DEFAULT 0,
+    arg1 INTEGER NOT NULL DEFAULT 0,
+    arg2 INTEGER NOT NULL DEFAULT 0,
+    arg3 INTEGER NOT NULL DEFAULT 0,
+    arg4 INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (area_name) REFERENCES areas(name) ON DELETE CASCADE
+);
+
+-- Create index on area_name for loading an area's resets in order
+CREATE INDEX IF NOT EXISTS idx_area_resets_area ON area_resets(area_name, seq);
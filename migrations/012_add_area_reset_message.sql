@@ -0,0 +1,3 @@
+-- Add a configurable message announced to players when their area resets
+ALTER TABLE areas
+ADD COLUMN IF NOT EXISTS reset_message TEXT NOT NULL DEFAULT 'You hear the patter of little feet.';
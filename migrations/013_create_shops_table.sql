@@ -0,0 +1,21 @@
+-- Create shops table (stores the #SHOPS section of imported areas)
+CREATE TABLE IF NOT EXISTS shops (
+    id SERIAL PRIMARY KEY,
+    keeper_vnum INTEGER NOT NULL UNIQUE, -- mobile vnum of the shopkeeper
+    area_name TEXT NOT NULL,
+    buy_types TEXT[] NOT NULL DEFAULT '{}', -- item types the keeper will buy, e.g. {'weapon','armor'}
+    profit_buy INTEGER NOT NULL DEFAULT 100, -- % of cost charged when players buy
+    profit_sell INTEGER NOT NULL DEFAULT 100, -- % of cost paid when players sell
+    open_hour INTEGER NOT NULL DEFAULT 0,
+    close_hour INTEGER NOT NULL DEFAULT 23,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL,
+    FOREIGN KEY (keeper_vnum) REFERENCES mobiles(vnum) ON DELETE CASCADE
+);
+
+-- Create index on area_name for area-based queries
+CREATE INDEX IF NOT EXISTS idx_shops_area_name ON shops(area_name);
+
+-- Shopkeepers' permanent stock (from G resets) lives in object_instances with
+-- location_type = 'shop' and location_id = mobile instance id. It never runs out:
+-- buying one creates a new instance for the player.
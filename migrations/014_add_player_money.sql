@@ -0,0 +1,6 @@
+-- Add a coin purse to players (100 silver = 1 gold)
+ALTER TABLE players
+ADD COLUMN IF NOT EXISTS gold INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE players
+ADD COLUMN IF NOT EXISTS silver INTEGER NOT NULL DEFAULT 0;
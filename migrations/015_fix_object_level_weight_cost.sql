@@ -0,0 +1,7 @@
+-- Objects were imported reading the "level weight cost condition" line as
+-- "weight cost level condition". Rotate the values of already-imported objects
+-- back into the right columns (right-hand sides see the old row values).
+UPDATE objects
+SET level = weight,
+    weight = cost,
+    cost = level;
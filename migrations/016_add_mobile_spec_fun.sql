@@ -0,0 +1,4 @@
+-- Add special procedure (from the #SPECIALS section) to mobile definitions
+-- e.g. 'spec_janitor', 'spec_fido', 'spec_cast_adept'
+ALTER TABLE mobiles
+ADD COLUMN IF NOT EXISTS spec_fun TEXT;
@@ -0,0 +1,4 @@
+-- Coins carried by individual mobile instances (e.g. what a thief has stolen)
+ALTER TABLE mobile_instances
+ADD COLUMN IF NOT EXISTS gold INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS silver INTEGER NOT NULL DEFAULT 0;
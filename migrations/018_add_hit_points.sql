@@ -0,0 +1,20 @@
+-- Add hit points to players (ROM characters start with 20)
+ALTER TABLE players
+ADD COLUMN IF NOT EXISTS hit INTEGER NOT NULL DEFAULT 20,
+ADD COLUMN IF NOT EXISTS max_hit INTEGER NOT NULL DEFAULT 20;
+
+-- Add hit points to mobile instances (rolled from the template's hit dice at spawn)
+ALTER TABLE mobile_instances
+ADD COLUMN IF NOT EXISTS hit INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS max_hit INTEGER NOT NULL DEFAULT 0;
+
+-- Give mobiles spawned before hit points existed the average of their hit dice
+UPDATE mobile_instances mi
+SET max_hit = GREATEST(1,
+        substring(m.hit_dice from '^(\d+)d')::INTEGER
+        * (substring(m.hit_dice from 'd(\d+)')::INTEGER + 1) / 2
+        + COALESCE(substring(m.hit_dice from '([+-]\d+)$')::INTEGER, 0))
+FROM mobiles m
+WHERE m.vnum = mi.mobile_vnum AND mi.max_hit = 0;
+
+UPDATE mobile_instances SET hit = max_hit WHERE hit = 0;
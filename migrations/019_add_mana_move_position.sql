@@ -0,0 +1,38 @@
+-- Add mana, movement and position to players (ROM characters start with 100 mana and move)
+ALTER TABLE players
+ADD COLUMN IF NOT EXISTS mana INTEGER NOT NULL DEFAULT 100,
+ADD COLUMN IF NOT EXISTS max_mana INTEGER NOT NULL DEFAULT 100,
+ADD COLUMN IF NOT EXISTS movement INTEGER NOT NULL DEFAULT 100,
+ADD COLUMN IF NOT EXISTS max_movement INTEGER NOT NULL DEFAULT 100,
+ADD COLUMN IF NOT EXISTS position TEXT NOT NULL DEFAULT 'standing'; -- 'standing', 'sitting', 'resting', 'sleeping'
+
+-- Add mana, movement and position to mobile instances
+ALTER TABLE mobile_instances
+ADD COLUMN IF NOT EXISTS mana INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS max_mana INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS movement INTEGER NOT NULL DEFAULT 100,
+ADD COLUMN IF NOT EXISTS max_movement INTEGER NOT NULL DEFAULT 100,
+ADD COLUMN IF NOT EXISTS position TEXT NOT NULL DEFAULT 'standing';
+
+-- Give mobiles spawned before mana existed the average of their mana dice,
+-- and put them in their template's starting position
+UPDATE mobile_instances mi
+SET max_mana = GREATEST(0,
+        substring(m.mana_dice from '^(\d+)d')::INTEGER
+        * (substring(m.mana_dice from 'd(\d+)')::INTEGER + 1) / 2
+        + COALESCE(substring(m.mana_dice from '([+-]\d+)$')::INTEGER, 0)),
+    position = CASE m.start_position
+        WHEN 'sleep' THEN 'sleeping'
+        WHEN 'rest' THEN 'resting'
+        WHEN 'sit' THEN 'sitting'
+        ELSE 'standing'
+    END
+FROM mobiles m
+WHERE m.vnum = mi.mobile_vnum AND mi.max_mana = 0;
+
+UPDATE mobile_instances SET mana = max_mana WHERE mana = 0;
+
+-- Room regeneration rates in percent (ROM's H and M room lines)
+ALTER TABLE rooms
+ADD COLUMN IF NOT EXISTS heal_rate INTEGER NOT NULL DEFAULT 100,
+ADD COLUMN IF NOT EXISTS mana_rate INTEGER NOT NULL DEFAULT 100;
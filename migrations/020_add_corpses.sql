@@ -0,0 +1,19 @@
+-- Per-instance descriptions, for objects like corpses whose text depends on
+-- who they were ("the corpse of the cityguard"); NULL means use the template's
+ALTER TABLE object_instances
+ADD COLUMN IF NOT EXISTS short_description TEXT,
+ADD COLUMN IF NOT EXISTS long_description TEXT;
+
+-- Corpse templates (ROM's OBJ_VNUM_CORPSE_NPC and OBJ_VNUM_CORPSE_PC from limbo.are)
+INSERT INTO objects (
+    vnum, area_name, keywords, short_description, long_description,
+    material, item_type, extra_flags, wear_flags, weight,
+    created_at, updated_at
+) VALUES
+    (10, 'limbo', 'corpse', 'the corpse of %s', 'The corpse of %s is lying here.',
+     'flesh', 'npc_corpse', '', 'A', 100,
+     EXTRACT(EPOCH FROM NOW())::BIGINT, EXTRACT(EPOCH FROM NOW())::BIGINT),
+    (11, 'limbo', 'corpse', 'the corpse of %s', 'The corpse of %s is lying here.',
+     'flesh', 'pc_corpse', '', '', 100,
+     EXTRACT(EPOCH FROM NOW())::BIGINT, EXTRACT(EPOCH FROM NOW())::BIGINT)
+ON CONFLICT (vnum) DO NOTHING;
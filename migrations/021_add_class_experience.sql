@@ -0,0 +1,21 @@
+-- Per-class level advancement (ROM's class_table): experience needed per level,
+-- hit points gained per level, and whether the class's mana grows at full speed
+ALTER TABLE classes
+ADD COLUMN IF NOT EXISTS exp_per_level INTEGER NOT NULL DEFAULT 1000,
+ADD COLUMN IF NOT EXISTS hp_min INTEGER NOT NULL DEFAULT 8,
+ADD COLUMN IF NOT EXISTS hp_max INTEGER NOT NULL DEFAULT 12,
+ADD COLUMN IF NOT EXISTS gains_mana BOOLEAN NOT NULL DEFAULT TRUE;
+
+-- Sturdier fighters take longer to level but gain more hit points
+UPDATE classes SET exp_per_level = 1200, hp_min = 11, hp_max = 15, gains_mana = FALSE WHERE name = 'Warrior';
+UPDATE classes SET exp_per_level = 1000, hp_min = 6, hp_max = 8, gains_mana = TRUE WHERE name = 'Mage';
+UPDATE classes SET exp_per_level = 1000, hp_min = 8, hp_max = 13, gains_mana = FALSE WHERE name = 'Rogue';
+UPDATE classes SET exp_per_level = 1100, hp_min = 7, hp_max = 10, gains_mana = TRUE WHERE name = 'Cleric';
+
+-- Rooms each player has been to, so exploring somewhere new is only rewarded once
+CREATE TABLE IF NOT EXISTS player_visited_rooms (
+    slack_user_id TEXT NOT NULL REFERENCES players(slack_user_id) ON DELETE CASCADE,
+    room_id TEXT NOT NULL,
+    visited_at BIGINT NOT NULL DEFAULT EXTRACT(EPOCH FROM NOW())::BIGINT,
+    PRIMARY KEY (slack_user_id, room_id)
+);
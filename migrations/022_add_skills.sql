@@ -0,0 +1,11 @@
+-- Practice sessions available to spend at a guildmaster (ROM characters start with 5)
+ALTER TABLE players
+ADD COLUMN IF NOT EXISTS practices INTEGER NOT NULL DEFAULT 5;
+
+-- How well each player knows each skill or spell, in percent
+CREATE TABLE IF NOT EXISTS player_skills (
+    slack_user_id TEXT NOT NULL REFERENCES players(slack_user_id) ON DELETE CASCADE,
+    skill TEXT NOT NULL,
+    learned INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (slack_user_id, skill)
+);
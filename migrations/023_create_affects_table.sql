@@ -0,0 +1,16 @@
+-- Spell effects on players and mobile instances (ROM's AFFECT_DATA)
+CREATE TABLE IF NOT EXISTS affects (
+    id SERIAL PRIMARY KEY,
+    target_type TEXT NOT NULL, -- 'player' or 'mobile'
+    target_id TEXT NOT NULL, -- player slack_user_id or mobile instance id
+    skill TEXT NOT NULL, -- the spell that caused it, e.g. 'armor'
+    level INTEGER NOT NULL DEFAULT 0,
+    duration INTEGER NOT NULL, -- ticks remaining; -1 is permanent
+    location TEXT NOT NULL DEFAULT 'none', -- what it modifies: 'ac', 'hitroll', 'saving_spell', ...
+    modifier INTEGER NOT NULL DEFAULT 0,
+    bitvector TEXT NOT NULL DEFAULT '', -- ROM affect flag letters it grants, e.g. 'D' for detect invis
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_affects_target ON affects(target_type, target_id);
@@ -0,0 +1,3 @@
+-- Stat changes and affect flags objects give their wearer (A and F lines in area files)
+ALTER TABLE objects
+ADD COLUMN IF NOT EXISTS applies JSONB NOT NULL DEFAULT '[]';
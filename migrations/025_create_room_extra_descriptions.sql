@@ -0,0 +1,11 @@
+-- Extra descriptions for things in a room ("look fountain", "look sign")
+CREATE TABLE IF NOT EXISTS room_extra_descriptions (
+    id SERIAL PRIMARY KEY,
+    room_id TEXT NOT NULL REFERENCES rooms(channel_id) ON DELETE CASCADE,
+    keywords TEXT NOT NULL, -- space-separated, any of which brings up the description
+    description TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_room_extra_descriptions_room ON room_extra_descriptions(room_id);
@@ -0,0 +1,4 @@
+-- Room flags as area file letters (ROM's room_flags, e.g. "CDS") and sector type code
+ALTER TABLE rooms
+ADD COLUMN IF NOT EXISTS room_flags TEXT NOT NULL DEFAULT '',
+ADD COLUMN IF NOT EXISTS sector_type INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,6 @@
+-- Doors on exits (ROM's exit keyword, description, exit_info and key)
+ALTER TABLE exits
+ADD COLUMN IF NOT EXISTS keyword TEXT NOT NULL DEFAULT '', -- door name(s), e.g. "gate"
+ADD COLUMN IF NOT EXISTS description TEXT NOT NULL DEFAULT '',
+ADD COLUMN IF NOT EXISTS exit_flags TEXT NOT NULL DEFAULT '', -- ROM exit flag letters, e.g. "ABC" for a locked door
+ADD COLUMN IF NOT EXISTS key_vnum INTEGER NOT NULL DEFAULT 0; -- object that locks it; 0 or less for none
@@ -0,0 +1,3 @@
+-- Hours of light left in a light source once it has started burning (ROM's value[2])
+ALTER TABLE object_instances
+ADD COLUMN IF NOT EXISTS light_hours INTEGER;
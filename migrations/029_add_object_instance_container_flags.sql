@@ -0,0 +1,4 @@
+-- A container's own open, closed and locked state once someone has changed it (ROM's value[1]);
+-- NULL means the template's flags
+ALTER TABLE object_instances
+ADD COLUMN IF NOT EXISTS container_flags TEXT;
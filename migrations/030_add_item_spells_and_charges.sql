@@ -0,0 +1,12 @@
+-- Spells potions, pills, scrolls, wands and staves cast (ROM's value slots holding spell names)
+ALTER TABLE objects
+ADD COLUMN IF NOT EXISTS spells JSONB NOT NULL DEFAULT '[]';
+
+-- Charges left in a wand or staff, or drinks left in a drink container, once some are used
+-- (ROM's value[2] and value[1]); NULL means the template's
+ALTER TABLE object_instances
+ADD COLUMN IF NOT EXISTS charges INTEGER;
+
+-- What a drink container holds once it has been filled or emptied (ROM's value[2]); NULL means the template's
+ALTER TABLE object_instances
+ADD COLUMN IF NOT EXISTS liquid TEXT;
@@ -0,0 +1,7 @@
+-- Hunger, thirst, fullness and drunkenness (ROM's pcdata->condition), from 0 to 48;
+-- players start fed and watered
+ALTER TABLE players
+ADD COLUMN IF NOT EXISTS drunk INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS fullness INTEGER NOT NULL DEFAULT 48,
+ADD COLUMN IF NOT EXISTS thirst INTEGER NOT NULL DEFAULT 48,
+ADD COLUMN IF NOT EXISTS hunger INTEGER NOT NULL DEFAULT 48;
@@ -0,0 +1,22 @@
+-- Hit points, mana and movement a player's worn gear adds to their maximums, so the
+-- maximums can be brought back in line with what they wear whenever it changes
+ALTER TABLE players
+ADD COLUMN IF NOT EXISTS gear_hit INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS gear_mana INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS gear_movement INTEGER NOT NULL DEFAULT 0;
+
+-- Whatever players have on now was added to their maximums when they put it on
+UPDATE players p
+SET gear_hit = g.hit, gear_mana = g.mana, gear_movement = g.movement
+FROM (
+    SELECT oi.location_id,
+        COALESCE(SUM((a->>'modifier')::INTEGER) FILTER (WHERE a->>'location' = 'hit'), 0) AS hit,
+        COALESCE(SUM((a->>'modifier')::INTEGER) FILTER (WHERE a->>'location' = 'mana'), 0) AS mana,
+        COALESCE(SUM((a->>'modifier')::INTEGER) FILTER (WHERE a->>'location' = 'move'), 0) AS movement
+    FROM object_instances oi
+    JOIN objects o ON o.vnum = oi.object_vnum
+    CROSS JOIN LATERAL jsonb_array_elements(o.applies) a
+    WHERE oi.location_type = 'equipped'
+    GROUP BY oi.location_id
+) g
+WHERE p.slack_user_id = g.location_id;
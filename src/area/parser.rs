@@ -14,6 +14,10 @@ pub fn parse_area_file(content: &str) -> Result<AreaFile, ParseError> {
                 lines.next(); // Consume #AREA
                 area.header = parse_area_header(&mut lines)?;
             }
+            "#MOBILES" => {
+                lines.next(); // Consume #MOBILES
+                area.mobiles = parse_mobiles(&mut lines)?;
+            }
             "#ROOMS" => {
                 lines.next(); // Consume #ROOMS
                 area.rooms = parse_rooms(&mut lines)?;
@@ -26,6 +30,14 @@ pub fn parse_area_file(content: &str) -> Result<AreaFile, ParseError> {
                 lines.next(); // Consume #RESETS
                 area.resets = parse_resets(&mut lines)?;
             }
+            "#SHOPS" => {
+                lines.next(); // Consume #SHOPS
+                area.shops = parse_shops(&mut lines)?;
+            }
+            "#SPECIALS" => {
+                lines.next(); // Consume #SPECIALS
+                area.specials = parse_specials(&mut lines)?;
+            }
             "#$" => break, // End of file
             _ => {
                 lines.next(); // Skip unknown sections
@@ -117,6 +129,8 @@ where
     // Parse exits and extra descriptions until 'S'
     let mut exits = Vec::new();
     let mut extra_descs = Vec::new();
+    let mut heal_rate = 100;
+    let mut mana_rate = 100;
 
     while let Some(&line) = lines.peek() {
         let trimmed = line.trim();
@@ -128,6 +142,17 @@ where
             exits.push(parse_exit(lines)?);
         } else if trimmed == "E" {
             extra_descs.push(parse_extra_desc(lines)?);
+        } else if trimmed.starts_with("H ") || trimmed.starts_with("M ") {
+            // Regeneration rates, either on separate lines or together as "H 110 M 110"
+            let parts: Vec<&str> = trimmed.split_whitespace().collect();
+            for pair in parts.chunks(2) {
+                match pair {
+                    ["H", rate] => heal_rate = rate.parse()?,
+                    ["M", rate] => mana_rate = rate.parse()?,
+                    _ => {}
+                }
+            }
+            lines.next();
         } else {
             lines.next(); // Skip unknown lines
         }
@@ -142,6 +167,8 @@ where
         sector_type,
         exits,
         extra_descs,
+        heal_rate,
+        mana_rate,
     })
 }
 
@@ -280,6 +307,140 @@ fn parse_vnum(line: &str) -> Result<i32, ParseError> {
         .map_err(|_| ParseError::InvalidVnum)
 }
 
+fn parse_mobiles<'a, I>(lines: &mut Peekable<I>) -> Result<Vec<AreaMobile>, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut mobiles = Vec::new();
+
+    while let Some(&line) = lines.peek() {
+        let trimmed = line.trim();
+
+        // Check for section end
+        if trimmed.starts_with("#RESETS")
+            || trimmed.starts_with("#OBJECTS")
+            || trimmed.starts_with("#ROOMS")
+            || trimmed.starts_with("#SHOPS")
+            || trimmed.starts_with("#SPECIALS")
+            || trimmed.starts_with("#$")
+            || trimmed == "#0"
+        {
+            break;
+        }
+
+        if trimmed.starts_with('#') && trimmed.len() > 1 {
+            // This is a vnum marker - parse the mobile
+            mobiles.push(parse_single_mobile(lines)?);
+        } else {
+            lines.next(); // Skip non-vnum lines
+        }
+    }
+
+    Ok(mobiles)
+}
+
+fn parse_single_mobile<'a, I>(lines: &mut Peekable<I>) -> Result<AreaMobile, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    // Parse #vnum
+    let vnum_line = lines.next().ok_or(ParseError::UnexpectedEof)?;
+    let vnum = parse_vnum(vnum_line)?;
+
+    // Tilde-terminated text fields
+    let keywords = read_until_tilde(lines)?;
+    let short_description = read_until_tilde(lines)?;
+    let long_description = read_until_tilde(lines)?;
+    let description = read_until_tilde(lines)?;
+    let race = read_until_tilde(lines)?;
+
+    // "act affect alignment group"
+    let parts = next_fields(lines, 3)?;
+    let act_flags = parts[0].to_string();
+    let affect_flags = parts[1].to_string();
+    let alignment = parts[2].parse::<i32>()?;
+
+    // "level hitroll hit_dice mana_dice damage_dice damage_type"
+    let parts = next_fields(lines, 6)?;
+    let level = parts[0].parse::<i32>()?;
+    let hitroll = parts[1].parse::<i32>()?;
+    let hit_dice = Dice::parse(parts[2])?;
+    let mana_dice = Dice::parse(parts[3])?;
+    let damage_dice = Dice::parse(parts[4])?;
+    let damage_type = parts[5].to_string();
+
+    // "ac_pierce ac_bash ac_slash ac_exotic"
+    let parts = next_fields(lines, 4)?;
+    let ac_pierce = parts[0].parse::<i32>()?;
+    let ac_bash = parts[1].parse::<i32>()?;
+    let ac_slash = parts[2].parse::<i32>()?;
+    let ac_exotic = parts[3].parse::<i32>()?;
+
+    // "offense immune resist vulnerable" - not used yet
+    next_fields(lines, 4)?;
+
+    // "start_position default_position gender wealth"
+    let parts = next_fields(lines, 4)?;
+    let start_position = parts[0].to_string();
+    let default_position = parts[1].to_string();
+    let gender = parts[2].to_string();
+    let wealth = parts[3].parse::<i32>()?;
+
+    // "form parts size material"
+    let parts = next_fields(lines, 3)?;
+    let size = parts[2].to_string();
+
+    // Skip optional flag modifier lines (F ...) until the next mobile
+    while let Some(&line) = lines.peek() {
+        if line.trim().starts_with('#') {
+            break;
+        }
+        lines.next();
+    }
+
+    Ok(AreaMobile {
+        vnum,
+        keywords,
+        short_description,
+        long_description,
+        description,
+        race,
+        act_flags,
+        affect_flags,
+        alignment,
+        level,
+        hitroll,
+        hit_dice,
+        mana_dice,
+        damage_dice,
+        damage_type,
+        ac_pierce,
+        ac_bash,
+        ac_slash,
+        ac_exotic,
+        start_position,
+        default_position,
+        gender,
+        wealth,
+        size,
+    })
+}
+
+/// Read the next line and split it into at least `min` whitespace-separated fields
+fn next_fields<'a, I>(lines: &mut Peekable<I>, min: usize) -> Result<Vec<&'a str>, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let line = lines.next().ok_or(ParseError::UnexpectedEof)?;
+    let parts: Vec<&str> = line.split_whitespace().collect();
+
+    if parts.len() < min {
+        return Err(ParseError::InvalidMobileData);
+    }
+
+    Ok(parts)
+}
+
 fn parse_objects<'a, I>(lines: &mut Peekable<I>) -> Result<Vec<AreaObject>, ParseError>
 where
     I: Iterator<Item = &'a str>,
@@ -339,17 +500,23 @@ where
     // Parse values line (format varies by item type)
     let values_line = lines.next().ok_or(ParseError::UnexpectedEof)?;
     let (value0, value1, value2, value3, value4) = parse_object_values_line(values_line)?;
+    let spells = parse_object_spells(&item_type, values_line);
 
-    // Parse weight/cost/level/condition line
+    // Parse level/weight/cost/condition line
     let weight_line = lines.next().ok_or(ParseError::UnexpectedEof)?;
-    let (weight, cost, level, condition) = parse_object_weight_line(weight_line)?;
+    let (level, weight, cost, condition) = parse_object_weight_line(weight_line)?;
 
-    // Parse optional extra descriptions
+    // Parse optional applies, affects and extra descriptions
     let mut extra_descriptions = Vec::new();
+    let mut applies = Vec::new();
     while let Some(&line) = lines.peek() {
         let trimmed = line.trim();
 
-        if trimmed == "E" {
+        if trimmed == "A" || trimmed == "F" {
+            lines.next();
+            let apply_line = lines.next().ok_or(ParseError::UnexpectedEof)?;
+            applies.push(parse_object_apply_line(apply_line, trimmed == "F")?);
+        } else if trimmed == "E" {
             extra_descriptions.push(parse_extra_desc(lines)?);
         } else if trimmed.starts_with('#') {
             // Next object
@@ -378,6 +545,25 @@ where
         level,
         condition,
         extra_descriptions,
+        applies,
+        spells,
+    })
+}
+
+/// Parse the line after an A ("location modifier") or F ("where location modifier bitvector")
+fn parse_object_apply_line(line: &str, affect: bool) -> Result<ObjectApply, ParseError> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    // F lines start with where the affect goes (A for affects, I/R/V for immunities and the like)
+    let parts = if affect { parts.get(1..).unwrap_or_default() } else { &parts[..] };
+
+    if parts.len() < 2 {
+        return Err(ParseError::InvalidObjectApply);
+    }
+
+    Ok(ObjectApply {
+        location: apply_location(parts[0].parse()?).to_string(),
+        modifier: parts[1].parse()?,
+        bitvector: if affect { parts.get(2).unwrap_or(&"").to_string() } else { String::new() },
     })
 }
 
@@ -395,16 +581,50 @@ fn parse_object_type_line(line: &str) -> Result<(String, String, String), ParseE
     Ok((item_type, extra_flags, wear_flags))
 }
 
+/// Split a line into words, keeping quoted words like 'cure light' whole (ROM's fread_word)
+fn split_words(line: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut rest = line.trim_start();
+    while !rest.is_empty() {
+        let (word, after) = match rest.chars().next() {
+            Some(quote @ ('\'' | '"')) => match rest[1..].find(quote) {
+                Some(end) => (&rest[..end + 2], &rest[end + 2..]),
+                None => (rest, ""),
+            },
+            _ => rest.split_at(rest.find(char::is_whitespace).unwrap_or(rest.len())),
+        };
+        words.push(word);
+        rest = after.trim_start();
+    }
+    words
+}
+
+/// Names of the spells a magic item casts: value1-4 of potions, pills and scrolls, value3 of wands and staves
+fn parse_object_spells(item_type: &str, line: &str) -> Vec<String> {
+    let parts = split_words(line);
+    let slots = match item_type {
+        "potion" | "pill" | "scroll" => parts.get(1..5).unwrap_or_default(),
+        "wand" | "staff" => parts.get(3..4).unwrap_or_default(),
+        _ => &[],
+    };
+    slots
+        .iter()
+        .map(|word| word.trim_matches(|c| c == '\'' || c == '"'))
+        .filter(|name| !name.is_empty() && name.parse::<i32>().is_err())
+        .map(str::to_string)
+        .collect()
+}
+
 fn parse_object_values_line(line: &str) -> Result<(i32, i32, String, i32, i32), ParseError> {
-    let parts: Vec<&str> = line.split_whitespace().collect();
+    let parts = split_words(line);
 
     // Values vary by item type, but we'll parse them generically
     // value0 and value1 are usually integers
     // value2 might be a string (e.g., liquid type) or integer
     // value3 and value4 are integers
 
-    let value0 = parts.get(0).unwrap_or(&"0").parse::<i32>().unwrap_or(0);
-    let value1 = parts.get(1).unwrap_or(&"0").parse::<i32>().unwrap_or(0);
+    let value0 = parse_object_value(parts.get(0).unwrap_or(&"0"));
+    let value1 = parse_object_value(parts.get(1).unwrap_or(&"0"));
 
     // value2 might be a quoted string or a number
     let value2_raw = parts.get(2).unwrap_or(&"0");
@@ -415,12 +635,23 @@ fn parse_object_values_line(line: &str) -> Result<(i32, i32, String, i32, i32),
         value2_raw.to_string()
     };
 
-    let value3 = parts.get(3).unwrap_or(&"0").parse::<i32>().unwrap_or(0);
-    let value4 = parts.get(4).unwrap_or(&"0").parse::<i32>().unwrap_or(0);
+    let value3 = parse_object_value(parts.get(3).unwrap_or(&"0"));
+    let value4 = parse_object_value(parts.get(4).unwrap_or(&"0"));
 
     Ok((value0, value1, value2, value3, value4))
 }
 
+/// A number, or flag letters like a container's "ABCD" as their bits (ROM's fread_flag)
+fn parse_object_value(value: &str) -> i32 {
+    if let Ok(number) = value.parse::<i32>() {
+        return number;
+    }
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_uppercase()) {
+        return letters_to_bits(value) as i32;
+    }
+    0
+}
+
 fn parse_object_weight_line(line: &str) -> Result<(i32, i32, i32, String), ParseError> {
     let parts: Vec<&str> = line.split_whitespace().collect();
 
@@ -428,12 +659,102 @@ fn parse_object_weight_line(line: &str) -> Result<(i32, i32, i32, String), Parse
         return Err(ParseError::InvalidObjectWeightCost);
     }
 
-    let weight = parts[0].parse::<i32>()?;
-    let cost = parts[1].parse::<i32>()?;
-    let level = parts[2].parse::<i32>()?;
+    // ROM order: level weight cost condition
+    let level = parts[0].parse::<i32>()?;
+    let weight = parts[1].parse::<i32>()?;
+    let cost = parts[2].parse::<i32>()?;
     let condition = parts[3].to_string();
 
-    Ok((weight, cost, level, condition))
+    Ok((level, weight, cost, condition))
+}
+
+fn parse_shops<'a, I>(lines: &mut Peekable<I>) -> Result<Vec<AreaShop>, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut shops = Vec::new();
+
+    while let Some(&line) = lines.peek() {
+        let trimmed = line.trim();
+
+        // Check for section end
+        if trimmed == "0" {
+            lines.next(); // Consume terminator
+            break;
+        }
+        if trimmed.starts_with('#') {
+            break;
+        }
+
+        // Skip comments and blank lines
+        if trimmed.starts_with('*') || trimmed.is_empty() {
+            lines.next();
+            continue;
+        }
+
+        shops.push(parse_single_shop(trimmed)?);
+        lines.next();
+    }
+
+    Ok(shops)
+}
+
+fn parse_specials<'a, I>(lines: &mut Peekable<I>) -> Result<Vec<AreaSpecial>, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut specials = Vec::new();
+
+    while let Some(&line) = lines.peek() {
+        let trimmed = line.trim();
+
+        // Check for section end
+        if trimmed == "S" {
+            lines.next(); // Consume terminator
+            break;
+        }
+        if trimmed.starts_with('#') {
+            break;
+        }
+
+        // Only "M <vnum> <spec_fun>" lines carry data; skip comments and blanks
+        let parts: Vec<&str> = trimmed.split_whitespace().collect();
+        if parts.len() >= 3 && parts[0] == "M" {
+            specials.push(AreaSpecial {
+                mob_vnum: parts[1].parse()?,
+                spec_fun: parts[2].to_lowercase(),
+            });
+        }
+        lines.next();
+    }
+
+    Ok(specials)
+}
+
+fn parse_single_shop(line: &str) -> Result<AreaShop, ParseError> {
+    // "keeper type0 type1 type2 type3 type4 profit_buy profit_sell open close * comment"
+    let line = match line.find('*') {
+        Some(idx) => &line[..idx],
+        None => line,
+    };
+
+    let parts = line
+        .split_whitespace()
+        .map(|p| p.parse::<i32>())
+        .collect::<Result<Vec<i32>, _>>()?;
+
+    if parts.len() < 10 {
+        return Err(ParseError::InvalidShopData);
+    }
+
+    Ok(AreaShop {
+        keeper_vnum: parts[0],
+        buy_types: parts[1..6].iter().copied().filter(|&t| t != 0).collect(),
+        profit_buy: parts[6],
+        profit_sell: parts[7],
+        open_hour: parts[8],
+        close_hour: parts[9],
+    })
 }
 
 fn parse_resets<'a, I>(lines: &mut Peekable<I>) -> Result<Vec<Reset>, ParseError>
@@ -607,9 +928,165 @@ mod tests {
     fn test_parse_room_attributes() {
         let (area, flags, sector) = parse_room_attributes("0 CDS 0").unwrap();
         assert_eq!(area, 0);
-        assert!(flags.contains(RoomFlags::NO_RECALL));
-        assert!(flags.contains(RoomFlags::DARK));
-        assert!(flags.contains(RoomFlags::SAFE));
+        assert_eq!(flags, RoomFlags::NO_MOB | RoomFlags::INDOORS | RoomFlags::LAW);
+        assert_eq!(flags.to_letters(), "CDS");
         assert_eq!(sector, SectorType::Inside);
+
+        // Numeric flags are the same bits
+        let (_, flags, _) = parse_room_attributes("0 1024 1").unwrap();
+        assert_eq!(flags, RoomFlags::SAFE);
+    }
+
+    #[test]
+    fn test_parse_object_weight_line() {
+        // ROM order is level, weight, cost, condition
+        let (level, weight, cost, condition) = parse_object_weight_line("5 160 75 P").unwrap();
+        assert_eq!(level, 5);
+        assert_eq!(weight, 160);
+        assert_eq!(cost, 75);
+        assert_eq!(condition, "P");
+    }
+
+    #[test]
+    fn test_parse_object_values_line() {
+        // A locked, pick-proof chest: flags in letters and a key vnum
+        let (capacity, flags, key, max_item, multiplier) = parse_object_values_line("34 ABCD 3122 20 100").unwrap();
+        assert_eq!((capacity, key.as_str(), max_item, multiplier), (34, "3122", 20, 100));
+        let locked = ContainerFlags::CLOSEABLE | ContainerFlags::PICKPROOF | ContainerFlags::CLOSED | ContainerFlags::LOCKED;
+        assert_eq!(ContainerFlags::from_bits_truncate(flags as u32), locked);
+        assert_eq!(parse_object_values_line("sword 2 6 'slash' 0").unwrap().0, 0);
+        // Quoted liquid names can have spaces in them
+        assert_eq!(parse_object_values_line("12 12 'local specialty' 0 0").unwrap().2, "local specialty");
+    }
+
+    #[test]
+    fn test_parse_object_spells() {
+        let spells = parse_object_spells("potion", "35 'sanctuary' 'stone skin' 'armor' ''");
+        assert_eq!(spells, vec!["sanctuary", "stone skin", "armor"]);
+        assert_eq!(parse_object_spells("wand", "4 10 10 'magic missile' 0"), vec!["magic missile"]);
+        assert!(parse_object_spells("drink", "16 16 'beer' 0 0").is_empty());
+    }
+
+    #[test]
+    fn test_parse_object_apply_line() {
+        let apply = parse_object_apply_line("13 20", false).unwrap();
+        assert_eq!((apply.location.as_str(), apply.modifier), ("hit", 20));
+
+        let affect = parse_object_apply_line("A 0 0 D", true).unwrap();
+        assert_eq!((affect.location.as_str(), affect.bitvector.as_str()), ("none", "D"));
+
+        assert!(parse_object_apply_line("17", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_shops() {
+        let content = "#SHOPS\n\
+3002  1  8 13 15 19 \t 150  40 \t 0 23 \t* the grocer\n\
+3006 22  0  0  0  0 \t 120  90 \t 6 22 \t* the captain\n\
+0\n\
+\n\
+#$\n";
+        let area = parse_area_file(content).unwrap();
+        assert_eq!(area.shops.len(), 2);
+
+        let grocer = &area.shops[0];
+        assert_eq!(grocer.keeper_vnum, 3002);
+        assert_eq!(grocer.buy_types, vec![1, 8, 13, 15, 19]);
+        assert_eq!(grocer.profit_buy, 150);
+        assert_eq!(grocer.profit_sell, 40);
+
+        let captain = &area.shops[1];
+        assert_eq!(captain.buy_types, vec![22]);
+        assert_eq!(captain.open_hour, 6);
+        assert_eq!(captain.close_hour, 22);
+    }
+
+    #[test]
+    fn test_parse_specials() {
+        let content = "#SPECIALS\n\
+M 3061 spec_janitor        \t* the janitor\n\
+* a comment line\n\
+M 3062 spec_fido           \t* the beastly fido\n\
+S\n\
+\n\
+#$\n";
+        let area = parse_area_file(content).unwrap();
+        assert_eq!(area.specials.len(), 2);
+        assert_eq!(area.specials[0].mob_vnum, 3061);
+        assert_eq!(area.specials[0].spec_fun, "spec_janitor");
+        assert_eq!(area.specials[1].spec_fun, "spec_fido");
+    }
+
+    #[test]
+    fn test_parse_dice() {
+        assert_eq!(Dice::parse("1d8+32").unwrap(), Dice { number: 1, size: 8, bonus: 32 });
+        assert_eq!(Dice::parse("2d4").unwrap(), Dice { number: 2, size: 4, bonus: 0 });
+        assert_eq!(Dice::parse("1d6-1").unwrap(), Dice { number: 1, size: 6, bonus: -1 });
+        assert_eq!(Dice::parse("3d5+0").unwrap().to_string(), "3d5+0");
+        assert!(Dice::parse("d8").is_err());
+        assert!(Dice::parse("beating").is_err());
+    }
+
+    #[test]
+    fn test_parse_mobiles() {
+        let content = "#MOBILES\n\
+#3001\n\
+baker~\n\
+the baker~\n\
+The baker looks at you calmly.\n\
+~\n\
+A fat, nice looking baker.\n\
+~\n\
+human~\n\
+ABV 0 900 0\n\
+23 0 1d1+999 1d1+99 1d8+20 beating\n\
+-15 -15 -15 -15\n\
+CDF ABCD 0 0\n\
+stand stand male 500\n\
+0 0 medium 0\n\
+F aff D\n\
+#0\n\
+#$\n";
+        let area = parse_area_file(content).unwrap();
+        assert_eq!(area.mobiles.len(), 1);
+
+        let mob = &area.mobiles[0];
+        assert_eq!(mob.vnum, 3001);
+        assert_eq!(mob.keywords, "baker");
+        assert_eq!(mob.short_description, "the baker");
+        assert_eq!(mob.long_description, "The baker looks at you calmly.");
+        assert_eq!(mob.race, "human");
+        assert_eq!(mob.act_flags, "ABV");
+        assert_eq!(mob.alignment, 900);
+        assert_eq!(mob.level, 23);
+        assert_eq!(mob.hit_dice, Dice { number: 1, size: 1, bonus: 999 });
+        assert_eq!(mob.damage_type, "beating");
+        assert_eq!(mob.ac_exotic, -15);
+        assert_eq!(mob.default_position, "stand");
+        assert_eq!(mob.gender, "male");
+        assert_eq!(mob.wealth, 500);
+        assert_eq!(mob.size, "medium");
+    }
+
+    #[test]
+    fn test_parse_midgaard_mobiles() {
+        let area = parse_area_file(include_str!("../../data/areas/midgaard.are")).unwrap();
+        assert!(area.mobiles.iter().any(|m| m.vnum == 3000 && m.keywords == "wizard"));
+        assert!(area.mobiles.iter().any(|m| m.short_description == "the cityguard"));
+        assert!(!area.rooms.is_empty());
+        assert!(!area.objects.is_empty());
+        assert!(area.shops.iter().any(|s| s.keeper_vnum == 3001));
+        assert!(area.specials.iter().any(|s| s.mob_vnum == 3012 && s.spec_fun == "spec_cast_adept"));
+        let club = area.objects.iter().find(|o| o.vnum == 3023).unwrap();
+        assert_eq!(club.applies, vec![ObjectApply { location: "hitroll".to_string(), modifier: -1, bitvector: String::new() }]);
+        let scimitar = area.objects.iter().find(|o| o.vnum == 3005).unwrap();
+        assert!(scimitar.extra_descriptions.iter().any(|ed| ed.keywords.contains(&"blade".to_string())));
+        let temple = area.rooms.iter().find(|r| r.vnum == 3054).unwrap();
+        assert_eq!((temple.heal_rate, temple.mana_rate), (110, 110));
+        assert!(area.rooms.iter().any(|r| r.heal_rate == 100));
+        let west_gate = area.rooms.iter().find(|r| r.vnum == 3052).unwrap();
+        let gate = west_gate.exits.iter().find(|e| e.direction == Direction::East).unwrap();
+        assert_eq!((gate.keyword.as_deref(), gate.key_vnum, gate.to_room), (Some("gate"), 3133, 3040));
+        assert_eq!(ExitFlags::from_lock_code(gate.door_flags), ExitFlags::IS_DOOR);
     }
 }
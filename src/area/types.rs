@@ -1,11 +1,15 @@
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Default)]
 pub struct AreaFile {
     pub header: AreaHeader,
     pub rooms: Vec<AreaRoom>,
+    pub mobiles: Vec<AreaMobile>,
     pub objects: Vec<AreaObject>,
     pub resets: Vec<Reset>,
+    pub shops: Vec<AreaShop>,
+    pub specials: Vec<AreaSpecial>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -27,6 +31,10 @@ pub struct AreaRoom {
     pub sector_type: SectorType,
     pub exits: Vec<AreaExit>,
     pub extra_descs: Vec<ExtraDescription>,
+    /// Hit point and movement regeneration rate in percent (H line, default 100)
+    pub heal_rate: i32,
+    /// Mana regeneration rate in percent (M line, default 100)
+    pub mana_rate: i32,
 }
 
 #[derive(Debug, Clone)]
@@ -39,12 +47,87 @@ pub struct AreaExit {
     pub to_room: i32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtraDescription {
     pub keywords: Vec<String>,
     pub description: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct AreaMobile {
+    pub vnum: i32,
+    pub keywords: String,
+    pub short_description: String,
+    pub long_description: String,
+    pub description: String,
+    pub race: String,
+    pub act_flags: String,
+    pub affect_flags: String,
+    pub alignment: i32,
+    pub level: i32,
+    pub hitroll: i32,
+    pub hit_dice: Dice,
+    pub mana_dice: Dice,
+    pub damage_dice: Dice,
+    pub damage_type: String,
+    pub ac_pierce: i32,
+    pub ac_bash: i32,
+    pub ac_slash: i32,
+    pub ac_exotic: i32,
+    pub start_position: String,
+    pub default_position: String,
+    pub gender: String,
+    pub wealth: i32,
+    pub size: String,
+}
+
+/// Dice expression from area files, e.g. "2d6+10"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dice {
+    pub number: i32,
+    pub size: i32,
+    pub bonus: i32,
+}
+
+impl Dice {
+    /// Roll the dice and add the bonus
+    pub fn roll(&self) -> i32 {
+        let mut rng = rand::thread_rng();
+        let total: i32 = (0..self.number).map(|_| rng.gen_range(1..=self.size.max(1))).sum();
+        total + self.bonus
+    }
+
+    /// Parse a dice expression like "1d8+32", "2d4" or "1d6-1"
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let s = s.trim();
+        let (number, rest) = s.split_once(['d', 'D']).ok_or(ParseError::InvalidDice)?;
+
+        let (size, bonus) = if let Some(idx) = rest.find(['+', '-']) {
+            let (size, bonus) = rest.split_at(idx);
+            let bonus = bonus.trim_start_matches('+');
+            (size, bonus.parse::<i32>().map_err(|_| ParseError::InvalidDice)?)
+        } else {
+            (rest, 0)
+        };
+
+        Ok(Dice {
+            number: number.parse::<i32>().map_err(|_| ParseError::InvalidDice)?,
+            size: size.parse::<i32>().map_err(|_| ParseError::InvalidDice)?,
+            bonus,
+        })
+    }
+}
+
+impl std::fmt::Display for Dice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.bonus < 0 {
+            write!(f, "{}d{}{}", self.number, self.size, self.bonus)
+        } else {
+            write!(f, "{}d{}+{}", self.number, self.size, self.bonus)
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AreaObject {
     pub vnum: i32,
@@ -65,6 +148,121 @@ pub struct AreaObject {
     pub level: i32,
     pub condition: String,
     pub extra_descriptions: Vec<ExtraDescription>,
+    /// Stat changes and affect flags given to whoever wears it (A and F lines)
+    pub applies: Vec<ObjectApply>,
+    /// Spells a potion, pill, scroll, wand or staff casts, by name
+    pub spells: Vec<String>,
+}
+
+/// A stat change an object gives its wearer, from an A or F line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectApply {
+    /// What it modifies: "hitroll", "ac", "hit", ... or "none" for flag-only affects
+    pub location: String,
+    pub modifier: i32,
+    /// ROM affect flag letters it grants (F lines only)
+    #[serde(default)]
+    pub bitvector: String,
+}
+
+/// Name of a ROM APPLY_* location number
+///
+/// ROM keeps a single saving throw, so every saving location is stored as `saving_spell`.
+pub fn apply_location(location: i32) -> &'static str {
+    match location {
+        1 => "str",
+        2 => "dex",
+        3 => "int",
+        4 => "wis",
+        5 => "con",
+        6 => "sex",
+        7 => "class",
+        8 => "level",
+        9 => "age",
+        10 => "height",
+        11 => "weight",
+        12 => "mana",
+        13 => "hit",
+        14 => "move",
+        15 => "gold",
+        16 => "exp",
+        17 => "ac",
+        18 => "hitroll",
+        19 => "damroll",
+        20..=24 => "saving_spell",
+        _ => "none",
+    }
+}
+
+/// Shop definition from the #SHOPS section
+#[derive(Debug, Clone)]
+pub struct AreaShop {
+    pub keeper_vnum: i32,
+    pub buy_types: Vec<i32>, // Numeric item types the keeper will buy (0 = none)
+    pub profit_buy: i32,     // Percentage of cost charged when selling to players
+    pub profit_sell: i32,    // Percentage of cost paid when buying from players
+    pub open_hour: i32,
+    pub close_hour: i32,
+}
+
+/// Special procedure assignment from the #SPECIALS section: M <mob_vnum> <spec_fun>
+#[derive(Debug, Clone)]
+pub struct AreaSpecial {
+    pub mob_vnum: i32,
+    pub spec_fun: String,
+}
+
+/// Check a ROM flag field for a flag letter
+///
+/// Area files write flags either as letters ("AN") or as a bit number ("8193");
+/// letters A-Z are bits 0-25 and a-f are bits 26-31.
+pub fn has_flag(flags: &str, flag: char) -> bool {
+    let flags = flags.trim();
+    if let Ok(bits) = flags.parse::<i64>() {
+        let bit = match flag {
+            'A'..='Z' => flag as u32 - 'A' as u32,
+            'a'..='f' => flag as u32 - 'a' as u32 + 26,
+            _ => return false,
+        };
+        return bits & (1 << bit) != 0;
+    }
+    flags.contains(flag)
+}
+
+/// Map a ROM numeric item type (as used in #SHOPS) to the item type name used in #OBJECTS
+pub fn item_type_name(code: i32) -> Option<&'static str> {
+    match code {
+        1 => Some("light"),
+        2 => Some("scroll"),
+        3 => Some("wand"),
+        4 => Some("staff"),
+        5 => Some("weapon"),
+        8 => Some("treasure"),
+        9 => Some("armor"),
+        10 => Some("potion"),
+        11 => Some("clothing"),
+        12 => Some("furniture"),
+        13 => Some("trash"),
+        15 => Some("container"),
+        17 => Some("drink"),
+        18 => Some("key"),
+        19 => Some("food"),
+        20 => Some("money"),
+        22 => Some("boat"),
+        23 => Some("npc_corpse"),
+        24 => Some("pc_corpse"),
+        25 => Some("fountain"),
+        26 => Some("pill"),
+        27 => Some("protect"),
+        28 => Some("map"),
+        29 => Some("portal"),
+        30 => Some("warp_stone"),
+        31 => Some("room_key"),
+        32 => Some("gem"),
+        33 => Some("jewelry"),
+        34 => Some("jukebox"),
+        _ => None,
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -200,58 +398,143 @@ impl SectorType {
             SectorType::Desert => "desert",
         }
     }
+
+    /// Movement points it takes to cross (ROM's movement_loss table)
+    pub fn movement_loss(&self) -> i32 {
+        match self {
+            SectorType::Inside => 1,
+            SectorType::City => 2,
+            SectorType::Field => 2,
+            SectorType::Forest => 3,
+            SectorType::Hills => 4,
+            SectorType::Mountain => 6,
+            SectorType::WaterSwim => 4,
+            SectorType::WaterNoSwim => 1,
+            SectorType::Underwater => 6,
+            SectorType::Air => 10,
+            SectorType::Desert => 6,
+        }
+    }
 }
 
 bitflags::bitflags! {
+    /// ROM room flags; each bit is the letter 'A' plus its position in area files
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct RoomFlags: u32 {
-        const DARK        = 1 << 0;  // D
-        const NO_MOB      = 1 << 2;  // K
-        const INDOORS     = 1 << 3;  // I
+        const DARK        = 1 << 0;  // A
+        const NO_MOB      = 1 << 2;  // C
+        const INDOORS     = 1 << 3;  // D
         const PRIVATE     = 1 << 9;  // J
-        const SAFE        = 1 << 10; // S
-        const SOLITARY    = 1 << 11; // O
-        const PET_SHOP    = 1 << 12; // P
-        const NO_RECALL   = 1 << 13; // C
-        const IMP_ONLY    = 1 << 14;
-        const GODS_ONLY   = 1 << 15; // G
-        const HEROES_ONLY = 1 << 16; // H
-        const NEWBIES_ONLY= 1 << 17; // N
-        const LAW         = 1 << 18; // L
-        const NOWHERE     = 1 << 19;
-        const BANK        = 1 << 20; // B
-        const ARENA       = 1 << 21; // A
+        const SAFE        = 1 << 10; // K
+        const SOLITARY    = 1 << 11; // L
+        const PET_SHOP    = 1 << 12; // M
+        const NO_RECALL   = 1 << 13; // N
+        const IMP_ONLY    = 1 << 14; // O
+        const GODS_ONLY   = 1 << 15; // P
+        const HEROES_ONLY = 1 << 16; // Q
+        const NEWBIES_ONLY= 1 << 17; // R
+        const LAW         = 1 << 18; // S
+        const NOWHERE     = 1 << 19; // T
+        const BANK        = 1 << 20; // U
+        const ARENA       = 1 << 21; // V
     }
 }
 
 impl RoomFlags {
+    /// Parse flags as written in an area file: letters ("CDS") or a plain number
     pub fn from_str(flags_str: &str) -> Self {
-        let mut flags = RoomFlags::empty();
-
-        for ch in flags_str.chars() {
-            match ch {
-                'D' => flags |= RoomFlags::DARK,
-                'C' => flags |= RoomFlags::NO_RECALL,
-                'S' => flags |= RoomFlags::SAFE,
-                'B' => flags |= RoomFlags::BANK,
-                'J' => flags |= RoomFlags::PRIVATE,
-                'K' => flags |= RoomFlags::NO_MOB,
-                'L' => flags |= RoomFlags::LAW,
-                'A' => flags |= RoomFlags::ARENA,
-                'G' => flags |= RoomFlags::GODS_ONLY,
-                'H' => flags |= RoomFlags::HEROES_ONLY,
-                'N' => flags |= RoomFlags::NEWBIES_ONLY,
-                'O' => flags |= RoomFlags::SOLITARY,
-                'P' => flags |= RoomFlags::PET_SHOP,
-                'I' => flags |= RoomFlags::INDOORS,
-                _ => {} // Ignore unknown flags
-            }
+        RoomFlags::from_bits_truncate(letters_to_bits(flags_str))
+    }
+
+    /// The flags as area file letters, the way rooms store them
+    pub fn to_letters(self) -> String {
+        bits_to_letters(self.bits())
+    }
+
+    /// Lowercase names of the flags that are set, for showing to wizards
+    pub fn names(self) -> Vec<String> {
+        self.iter_names().map(|(name, _)| name.to_lowercase()).collect()
+    }
+}
+
+bitflags::bitflags! {
+    /// ROM exit flags (exit_info); each bit is the letter 'A' plus its position
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ExitFlags: u32 {
+        const IS_DOOR   = 1 << 0; // A
+        const CLOSED    = 1 << 1; // B
+        const LOCKED    = 1 << 2; // C
+        const PICKPROOF = 1 << 5; // F
+        const NO_PASS   = 1 << 6; // G
+    }
+}
+
+impl ExitFlags {
+    /// Parse flags stored as letters ("ABC")
+    pub fn from_str(flags_str: &str) -> Self {
+        ExitFlags::from_bits_truncate(letters_to_bits(flags_str))
+    }
+
+    /// The flags as letters, the way exits store them
+    pub fn to_letters(self) -> String {
+        bits_to_letters(self.bits())
+    }
+
+    /// The door an exit's lock code in an area file describes (ROM's load_rooms)
+    pub fn from_lock_code(code: i32) -> Self {
+        match code {
+            1 => ExitFlags::IS_DOOR,
+            2 => ExitFlags::IS_DOOR | ExitFlags::PICKPROOF,
+            3 => ExitFlags::IS_DOOR | ExitFlags::NO_PASS,
+            4 => ExitFlags::IS_DOOR | ExitFlags::NO_PASS | ExitFlags::PICKPROOF,
+            _ => ExitFlags::empty(),
         }
+    }
+}
+
+bitflags::bitflags! {
+    /// ROM container flags (a container's value1); each bit is the letter 'A' plus its position
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ContainerFlags: u32 {
+        const CLOSEABLE = 1 << 0; // A
+        const PICKPROOF = 1 << 1; // B
+        const CLOSED    = 1 << 2; // C
+        const LOCKED    = 1 << 3; // D
+        const PUT_ON    = 1 << 4; // E
+    }
+}
+
+impl ContainerFlags {
+    /// Parse flags stored as letters ("AC") or as a number (a template's value1)
+    pub fn from_str(flags_str: &str) -> Self {
+        ContainerFlags::from_bits_truncate(letters_to_bits(flags_str))
+    }
 
-        flags
+    /// The flags as letters, the way object instances store them
+    pub fn to_letters(self) -> String {
+        bits_to_letters(self.bits())
     }
 }
 
+/// Bits for a set of flag letters, or for a plain number
+pub(super) fn letters_to_bits(flags: &str) -> u32 {
+    let mut bits = 0;
+    for (bit, letter) in ('A'..='Z').enumerate() {
+        if has_flag(flags, letter) {
+            bits |= 1 << bit;
+        }
+    }
+    bits
+}
+
+fn bits_to_letters(bits: u32) -> String {
+    ('A'..='Z')
+        .enumerate()
+        .filter(|(bit, _)| bits & (1 << bit) != 0)
+        .map(|(_, letter)| letter)
+        .collect()
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
     #[error("Unexpected end of file")]
@@ -272,6 +555,15 @@ pub enum ParseError {
     #[error("Invalid exit data")]
     InvalidExitData,
 
+    #[error("Invalid mobile data")]
+    InvalidMobileData,
+
+    #[error("Invalid dice expression")]
+    InvalidDice,
+
+    #[error("Invalid shop data")]
+    InvalidShopData,
+
     #[error("Invalid object type line")]
     InvalidObjectType,
 
@@ -281,6 +573,9 @@ pub enum ParseError {
     #[error("Invalid object weight/cost line")]
     InvalidObjectWeightCost,
 
+    #[error("Invalid object apply line")]
+    InvalidObjectApply,
+
     #[error("Invalid reset command")]
     InvalidResetCommand,
 
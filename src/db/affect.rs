@@ -0,0 +1,74 @@
+use crate::models::Affect;
+use sqlx::{PgPool, Row};
+
+pub struct AffectRepository {
+    pool: PgPool,
+}
+
+impl AffectRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Put an affect on a player or mobile
+    pub async fn create(&self, affect: &Affect) -> Result<i32, sqlx::Error> {
+        let row = sqlx::query(
+            "INSERT INTO affects (
+                target_type, target_id, skill, level, duration, location, modifier, bitvector,
+                created_at, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id"
+        )
+        .bind(&affect.target_type)
+        .bind(&affect.target_id)
+        .bind(&affect.skill)
+        .bind(affect.level)
+        .bind(affect.duration)
+        .bind(&affect.location)
+        .bind(affect.modifier)
+        .bind(&affect.bitvector)
+        .bind(affect.created_at)
+        .bind(affect.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.get("id"))
+    }
+
+    /// Get every affect on a player or mobile
+    pub async fn get_for(&self, target_type: &str, target_id: &str) -> Result<Vec<Affect>, sqlx::Error> {
+        sqlx::query_as::<_, Affect>(
+            "SELECT * FROM affects WHERE target_type = $1 AND target_id = $2 ORDER BY id"
+        )
+        .bind(target_type)
+        .bind(target_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Count down every timed affect by one tick, removing and returning the ones that wore off
+    ///
+    /// As in ROM, an affect lasts through the tick its duration reaches zero and wears off on the next.
+    pub async fn decrement_durations(&self) -> Result<Vec<Affect>, sqlx::Error> {
+        let expired = sqlx::query_as::<_, Affect>("DELETE FROM affects WHERE duration = 0 RETURNING *")
+            .fetch_all(&self.pool)
+            .await?;
+
+        sqlx::query("UPDATE affects SET duration = duration - 1, updated_at = $1 WHERE duration > 0")
+            .bind(chrono::Utc::now().timestamp())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(expired)
+    }
+
+    /// Remove every affect from a player or mobile (e.g. when a mobile leaves the world)
+    pub async fn delete_for(&self, target_type: &str, target_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM affects WHERE target_type = $1 AND target_id = $2")
+            .bind(target_type)
+            .bind(target_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
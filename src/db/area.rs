@@ -1,4 +1,5 @@
-use crate::models::Area;
+use crate::area::types::Reset;
+use crate::models::{Area, AreaReset};
 use sqlx::PgPool;
 
 pub struct AreaRepository {
@@ -17,10 +18,17 @@ impl AreaRepository {
             .await
     }
 
+    /// Get all imported areas
+    pub async fn get_all(&self) -> Result<Vec<Area>, sqlx::Error> {
+        sqlx::query_as::<_, Area>("SELECT * FROM areas ORDER BY min_vnum")
+            .fetch_all(&self.pool)
+            .await
+    }
+
     pub async fn create(&self, area: &Area) -> Result<Area, sqlx::Error> {
         sqlx::query_as::<_, Area>(
-            "INSERT INTO areas (name, filename, min_vnum, max_vnum, rooms_count, exits_count, imported_at, updated_at)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "INSERT INTO areas (name, filename, min_vnum, max_vnum, rooms_count, exits_count, reset_message, imported_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
              RETURNING *"
         )
         .bind(&area.name)
@@ -29,6 +37,7 @@ impl AreaRepository {
         .bind(area.max_vnum)
         .bind(area.rooms_count)
         .bind(area.exits_count)
+        .bind(&area.reset_message)
         .bind(area.imported_at)
         .bind(area.updated_at)
         .fetch_one(&self.pool)
@@ -40,7 +49,7 @@ impl AreaRepository {
         sqlx::query(
             "UPDATE areas
              SET filename = $2, min_vnum = $3, max_vnum = $4,
-                 rooms_count = $5, exits_count = $6, updated_at = $7
+                 rooms_count = $5, exits_count = $6, reset_message = $7, updated_at = $8
              WHERE name = $1"
         )
         .bind(&area.name)
@@ -49,6 +58,7 @@ impl AreaRepository {
         .bind(area.max_vnum)
         .bind(area.rooms_count)
         .bind(area.exits_count)
+        .bind(&area.reset_message)
         .bind(now)
         .execute(&self.pool)
         .await?;
@@ -83,3 +93,52 @@ impl AreaRepository {
         Ok(result.map(|(exists,)| exists).unwrap_or(false))
     }
 }
+
+pub struct AreaResetRepository {
+    pool: PgPool,
+}
+
+impl AreaResetRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Replace the stored resets for an area with the given list (kept in file order)
+    pub async fn replace_for_area(&self, area_name: &str, resets: &[Reset]) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM area_resets WHERE area_name = $1")
+            .bind(area_name)
+            .execute(&self.pool)
+            .await?;
+
+        for (seq, reset) in resets.iter().enumerate() {
+            let row = AreaReset::from_reset(area_name.to_string(), seq as i32, reset);
+            sqlx::query(
+                "INSERT INTO area_resets (area_name, seq, command, if_flag, arg1, arg2, arg3, arg4)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
+            )
+            .bind(&row.area_name)
+            .bind(row.seq)
+            .bind(&row.command)
+            .bind(row.if_flag)
+            .bind(row.arg1)
+            .bind(row.arg2)
+            .bind(row.arg3)
+            .bind(row.arg4)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Get the stored resets for an area in file order
+    pub async fn get_by_area(&self, area_name: &str) -> Result<Vec<Reset>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, AreaReset>(
+            "SELECT * FROM area_resets WHERE area_name = $1 ORDER BY seq"
+        )
+        .bind(area_name)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().filter_map(AreaReset::to_reset).collect())
+    }
+}
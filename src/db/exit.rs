@@ -1,4 +1,6 @@
+use crate::area::types::ExitFlags;
 use crate::models::Exit;
+use crate::models::exit::reverse_direction;
 use sqlx::PgPool;
 
 pub struct ExitRepository {
@@ -12,15 +14,24 @@ impl ExitRepository {
 
     pub async fn create(&self, exit: &Exit) -> Result<(), sqlx::Error> {
         sqlx::query(
-            "INSERT INTO exits (from_room_id, direction, to_room_id, created_at, created_by)
-             VALUES ($1, $2, $3, $4, $5)
-             ON CONFLICT (from_room_id, direction) DO NOTHING"
+            "INSERT INTO exits (from_room_id, direction, to_room_id, created_at, created_by, keyword, description, exit_flags, key_vnum)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             ON CONFLICT (from_room_id, direction) DO UPDATE SET
+             to_room_id = EXCLUDED.to_room_id,
+             keyword = EXCLUDED.keyword,
+             description = EXCLUDED.description,
+             exit_flags = EXCLUDED.exit_flags,
+             key_vnum = EXCLUDED.key_vnum"
         )
         .bind(&exit.from_room_id)
         .bind(&exit.direction)
         .bind(&exit.to_room_id)
         .bind(exit.created_at)
         .bind(&exit.created_by)
+        .bind(&exit.keyword)
+        .bind(&exit.description)
+        .bind(&exit.exit_flags)
+        .bind(exit.key_vnum)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -45,6 +56,30 @@ impl ExitRepository {
         .await
     }
 
+    /// The exit on the far side of a door that leads back through it, if there is one
+    pub async fn get_reverse(&self, exit: &Exit) -> Result<Option<Exit>, sqlx::Error> {
+        let Some(back) = reverse_direction(&exit.direction) else { return Ok(None) };
+        Ok(self.get_exit_in_direction(&exit.to_room_id, back).await?
+            .filter(|reverse| reverse.to_room_id == exit.from_room_id))
+    }
+
+    /// Open, close, lock or unlock a door, on both sides if it has another side
+    pub async fn set_door_state(&self, exit: &Exit, closed: bool, locked: bool) -> Result<(), sqlx::Error> {
+        let mut sides = vec![exit.clone()];
+        sides.extend(self.get_reverse(exit).await?);
+        for side in sides {
+            let mut flags = side.flags();
+            flags.set(ExitFlags::CLOSED, closed);
+            flags.set(ExitFlags::LOCKED, locked);
+            sqlx::query("UPDATE exits SET exit_flags = $1 WHERE id = $2")
+                .bind(flags.to_letters())
+                .bind(side.id)
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
     pub async fn delete_exit(&self, room_id: &str, direction: &str) -> Result<(), sqlx::Error> {
         sqlx::query("DELETE FROM exits WHERE from_room_id = $1 AND direction = $2")
             .bind(room_id)
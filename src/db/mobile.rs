@@ -0,0 +1,346 @@
+use crate::models::{Mobile, MobileInstance, Position};
+use sqlx::{PgPool, Row};
+
+pub struct MobileRepository {
+    pool: PgPool,
+}
+
+impl MobileRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a new mobile definition
+    pub async fn create(&self, mobile: &Mobile) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO mobiles (
+                vnum, area_name, keywords, short_description, long_description, description,
+                race, act_flags, affect_flags, alignment, level, hitroll,
+                hit_dice, mana_dice, damage_dice, damage_type,
+                ac_pierce, ac_bash, ac_slash, ac_exotic,
+                start_position, default_position, gender, wealth, size, spec_fun,
+                created_at, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28)
+            ON CONFLICT (vnum) DO UPDATE SET
+                area_name = EXCLUDED.area_name,
+                keywords = EXCLUDED.keywords,
+                short_description = EXCLUDED.short_description,
+                long_description = EXCLUDED.long_description,
+                description = EXCLUDED.description,
+                race = EXCLUDED.race,
+                act_flags = EXCLUDED.act_flags,
+                affect_flags = EXCLUDED.affect_flags,
+                alignment = EXCLUDED.alignment,
+                level = EXCLUDED.level,
+                hitroll = EXCLUDED.hitroll,
+                hit_dice = EXCLUDED.hit_dice,
+                mana_dice = EXCLUDED.mana_dice,
+                damage_dice = EXCLUDED.damage_dice,
+                damage_type = EXCLUDED.damage_type,
+                ac_pierce = EXCLUDED.ac_pierce,
+                ac_bash = EXCLUDED.ac_bash,
+                ac_slash = EXCLUDED.ac_slash,
+                ac_exotic = EXCLUDED.ac_exotic,
+                start_position = EXCLUDED.start_position,
+                default_position = EXCLUDED.default_position,
+                gender = EXCLUDED.gender,
+                wealth = EXCLUDED.wealth,
+                size = EXCLUDED.size,
+                spec_fun = EXCLUDED.spec_fun,
+                updated_at = EXCLUDED.updated_at"
+        )
+        .bind(mobile.vnum)
+        .bind(&mobile.area_name)
+        .bind(&mobile.keywords)
+        .bind(&mobile.short_description)
+        .bind(&mobile.long_description)
+        .bind(&mobile.description)
+        .bind(&mobile.race)
+        .bind(&mobile.act_flags)
+        .bind(&mobile.affect_flags)
+        .bind(mobile.alignment)
+        .bind(mobile.level)
+        .bind(mobile.hitroll)
+        .bind(&mobile.hit_dice)
+        .bind(&mobile.mana_dice)
+        .bind(&mobile.damage_dice)
+        .bind(&mobile.damage_type)
+        .bind(mobile.ac_pierce)
+        .bind(mobile.ac_bash)
+        .bind(mobile.ac_slash)
+        .bind(mobile.ac_exotic)
+        .bind(&mobile.start_position)
+        .bind(&mobile.default_position)
+        .bind(&mobile.gender)
+        .bind(mobile.wealth)
+        .bind(&mobile.size)
+        .bind(&mobile.spec_fun)
+        .bind(mobile.created_at)
+        .bind(mobile.updated_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Get a mobile definition by vnum
+    pub async fn get_by_vnum(&self, vnum: i32) -> Result<Option<Mobile>, sqlx::Error> {
+        sqlx::query_as::<_, Mobile>("SELECT * FROM mobiles WHERE vnum = $1")
+            .bind(vnum)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Delete all mobiles for an area
+    pub async fn delete_by_area(&self, area_name: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM mobiles WHERE area_name = $1")
+            .bind(area_name)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct MobileInstanceRepository {
+    pool: PgPool,
+}
+
+impl MobileInstanceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a new mobile instance
+    pub async fn create(&self, instance: &MobileInstance) -> Result<i32, sqlx::Error> {
+        let row = sqlx::query(
+            "INSERT INTO mobile_instances (mobile_vnum, room_id, hit, max_hit, mana, max_mana, movement, max_movement, position, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             RETURNING id"
+        )
+        .bind(instance.mobile_vnum)
+        .bind(&instance.room_id)
+        .bind(instance.hit)
+        .bind(instance.max_hit)
+        .bind(instance.mana)
+        .bind(instance.max_mana)
+        .bind(instance.movement)
+        .bind(instance.max_movement)
+        .bind(&instance.position)
+        .bind(instance.created_at)
+        .bind(instance.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.get("id"))
+    }
+
+    /// Get all mobile instances in a room
+    pub async fn get_in_room(&self, room_channel_id: &str) -> Result<Vec<MobileInstance>, sqlx::Error> {
+        sqlx::query_as::<_, MobileInstance>(
+            "SELECT * FROM mobile_instances WHERE room_id = $1 ORDER BY id"
+        )
+        .bind(room_channel_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get every mobile instance whose template has a special procedure
+    pub async fn get_with_spec_fun(&self) -> Result<Vec<MobileInstance>, sqlx::Error> {
+        sqlx::query_as::<_, MobileInstance>(
+            "SELECT mi.* FROM mobile_instances mi
+             JOIN mobiles m ON m.vnum = mi.mobile_vnum
+             WHERE m.spec_fun IS NOT NULL
+             ORDER BY mi.id"
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Move a mobile instance to another room
+    pub async fn update_room(&self, instance_id: i32, room_channel_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE mobile_instances SET room_id = $1, updated_at = $2 WHERE id = $3")
+            .bind(room_channel_id)
+            .bind(chrono::Utc::now().timestamp())
+            .bind(instance_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Add coins to a mobile instance's purse
+    pub async fn add_money(&self, instance_id: i32, gold: i32, silver: i32) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE mobile_instances SET gold = gold + $1, silver = silver + $2, updated_at = $3 WHERE id = $4"
+        )
+        .bind(gold)
+        .bind(silver)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(instance_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Get a mobile instance by id
+    pub async fn get_by_id(&self, instance_id: i32) -> Result<Option<MobileInstance>, sqlx::Error> {
+        sqlx::query_as::<_, MobileInstance>("SELECT * FROM mobile_instances WHERE id = $1")
+            .bind(instance_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Subtract damage from a mobile instance's hit points, returning what it had before and after,
+    /// or nothing if the instance is gone
+    pub async fn apply_damage(&self, instance_id: i32, damage: i32) -> Result<Option<(i32, i32)>, sqlx::Error> {
+        sqlx::query_as(
+            "UPDATE mobile_instances SET hit = hit - $1, updated_at = $2 WHERE id = $3 RETURNING hit + $1, hit"
+        )
+        .bind(damage)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(instance_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Restore hit points, up to the instance's maximum, returning what it has now
+    pub async fn heal(&self, instance_id: i32, amount: i32) -> Result<i32, sqlx::Error> {
+        let row = sqlx::query(
+            "UPDATE mobile_instances SET hit = LEAST(max_hit, hit + $1), updated_at = $2 WHERE id = $3 RETURNING hit"
+        )
+        .bind(amount)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(instance_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.get("hit"))
+    }
+
+    /// Get every mobile instance short of full hit points, mana or movement
+    pub async fn get_wounded(&self) -> Result<Vec<MobileInstance>, sqlx::Error> {
+        sqlx::query_as::<_, MobileInstance>(
+            "SELECT * FROM mobile_instances
+             WHERE hit < max_hit OR mana < max_mana OR movement < max_movement
+             ORDER BY id"
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Add regained hit points, mana and movement, without going past the mobile's maximums
+    /// or lowering anything already over them
+    pub async fn regenerate(&self, instance_id: i32, hit: i32, mana: i32, movement: i32) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE mobile_instances SET
+                hit = GREATEST(hit, LEAST(max_hit, hit + $1)),
+                mana = GREATEST(mana, LEAST(max_mana, mana + $2)),
+                movement = GREATEST(movement, LEAST(max_movement, movement + $3)),
+                updated_at = $4
+             WHERE id = $5"
+        )
+        .bind(hit)
+        .bind(mana)
+        .bind(movement)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(instance_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Change what a mobile instance is doing (standing, sleeping, ...)
+    pub async fn update_position(&self, instance_id: i32, position: Position) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE mobile_instances SET position = $1, updated_at = $2 WHERE id = $3")
+            .bind(position.as_str())
+            .bind(chrono::Utc::now().timestamp())
+            .bind(instance_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Remove a mobile instance from the world along with everything it carries
+    pub async fn delete(&self, instance_id: i32) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "WITH RECURSIVE carried AS (
+                 SELECT id FROM object_instances
+                 WHERE location_type IN ('mobile', 'mobile_equipped', 'shop') AND location_id = $1
+                 UNION ALL
+                 SELECT o.id FROM object_instances o
+                 JOIN carried c ON o.location_type = 'container' AND o.location_id = c.id::TEXT
+             )
+             DELETE FROM object_instances WHERE id IN (SELECT id FROM carried)"
+        )
+        .bind(instance_id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("DELETE FROM affects WHERE target_type = 'mobile' AND target_id = $1")
+            .bind(instance_id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("DELETE FROM mobile_instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Count all instances of a mobile in the world
+    pub async fn count_by_vnum(&self, mobile_vnum: i32) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM mobile_instances WHERE mobile_vnum = $1")
+            .bind(mobile_vnum)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.get("count"))
+    }
+
+    /// Count instances of a mobile in a specific room
+    pub async fn count_in_room(&self, mobile_vnum: i32, room_channel_id: &str) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) AS count FROM mobile_instances WHERE mobile_vnum = $1 AND room_id = $2"
+        )
+        .bind(mobile_vnum)
+        .bind(room_channel_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.get("count"))
+    }
+
+    /// Delete all mobile instances (and the objects they carry) for an area
+    pub async fn delete_by_area(&self, area_name: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "DELETE FROM object_instances
+             WHERE location_type IN ('mobile', 'mobile_equipped', 'shop')
+             AND location_id IN (
+                 SELECT mi.id::TEXT FROM mobile_instances mi
+                 JOIN mobiles m ON m.vnum = mi.mobile_vnum
+                 WHERE m.area_name = $1
+             )"
+        )
+        .bind(area_name)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "DELETE FROM affects
+             WHERE target_type = 'mobile'
+             AND target_id IN (
+                 SELECT mi.id::TEXT FROM mobile_instances mi
+                 JOIN mobiles m ON m.vnum = mi.mobile_vnum
+                 WHERE m.area_name = $1
+             )"
+        )
+        .bind(area_name)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "DELETE FROM mobile_instances WHERE mobile_vnum IN
+             (SELECT vnum FROM mobiles WHERE area_name = $1)"
+        )
+        .bind(area_name)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
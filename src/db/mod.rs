@@ -5,6 +5,10 @@ pub mod room;
 pub mod exit;
 pub mod area;
 pub mod object;
+pub mod mobile;
+pub mod shop;
+pub mod skill;
+pub mod affect;
 
 use sqlx::postgres::{PgPool, PgPoolOptions};
 use std::time::Duration;
@@ -21,9 +21,9 @@ impl ObjectRepository {
                 vnum, area_name, keywords, short_description, long_description,
                 material, item_type, extra_flags, wear_flags,
                 value0, value1, value2, value3, value4,
-                weight, cost, level, condition, extra_descriptions,
+                weight, cost, level, condition, extra_descriptions, applies, spells,
                 created_at, updated_at
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21)
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
             ON CONFLICT (vnum) DO UPDATE SET
                 area_name = EXCLUDED.area_name,
                 keywords = EXCLUDED.keywords,
@@ -43,6 +43,8 @@ impl ObjectRepository {
                 level = EXCLUDED.level,
                 condition = EXCLUDED.condition,
                 extra_descriptions = EXCLUDED.extra_descriptions,
+                applies = EXCLUDED.applies,
+                spells = EXCLUDED.spells,
                 updated_at = EXCLUDED.updated_at"
         )
         .bind(object.vnum)
@@ -64,6 +66,8 @@ impl ObjectRepository {
         .bind(object.level)
         .bind(&object.condition)
         .bind(&object.extra_descriptions)
+        .bind(&object.applies)
+        .bind(&object.spells)
         .bind(object.created_at)
         .bind(object.updated_at)
         .execute(&self.pool)
@@ -79,6 +83,11 @@ impl ObjectRepository {
             .await
     }
 
+    /// Get the definition of an object instance, with any per-instance descriptions applied
+    pub async fn get_for_instance(&self, instance: &ObjectInstance) -> Result<Option<Object>, sqlx::Error> {
+        Ok(self.get_by_vnum(instance.object_vnum).await?.map(|object| instance.restring(object)))
+    }
+
     /// Get all objects for an area
     pub async fn get_by_area(&self, area_name: &str) -> Result<Vec<Object>, sqlx::Error> {
         sqlx::query_as::<_, Object>("SELECT * FROM objects WHERE area_name = $1 ORDER BY vnum")
@@ -110,17 +119,25 @@ impl ObjectInstanceRepository {
     pub async fn create(&self, instance: &ObjectInstance) -> Result<i32, sqlx::Error> {
         let row = sqlx::query(
             "INSERT INTO object_instances (
-                object_vnum, location_type, location_id, wear_location,
-                current_condition, timer, created_at, updated_at
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                object_vnum, location_type, location_id, wear_location, equipped_slot,
+                current_condition, timer, short_description, long_description, light_hours, container_flags,
+                charges, liquid, created_at, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             RETURNING id"
         )
         .bind(instance.object_vnum)
         .bind(&instance.location_type)
         .bind(&instance.location_id)
         .bind(&instance.wear_location)
+        .bind(&instance.equipped_slot)
         .bind(instance.current_condition)
         .bind(instance.timer)
+        .bind(&instance.short_description)
+        .bind(&instance.long_description)
+        .bind(instance.light_hours)
+        .bind(&instance.container_flags)
+        .bind(instance.charges)
+        .bind(&instance.liquid)
         .bind(instance.created_at)
         .bind(instance.updated_at)
         .fetch_one(&self.pool)
@@ -139,6 +156,15 @@ impl ObjectInstanceRepository {
         .await
     }
 
+    /// Count all instances of an object in the world
+    pub async fn count_by_vnum(&self, object_vnum: i32) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM object_instances WHERE object_vnum = $1")
+            .bind(object_vnum)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.get("count"))
+    }
+
     /// Get all object instances in a player's inventory
     pub async fn get_in_player_inventory(&self, player_slack_id: &str) -> Result<Vec<ObjectInstance>, sqlx::Error> {
         sqlx::query_as::<_, ObjectInstance>(
@@ -149,6 +175,91 @@ impl ObjectInstanceRepository {
         .await
     }
 
+    /// Get all objects carried (not worn) by a mobile instance
+    pub async fn get_carried_by_mobile(&self, mobile_instance_id: i32) -> Result<Vec<ObjectInstance>, sqlx::Error> {
+        sqlx::query_as::<_, ObjectInstance>(
+            "SELECT * FROM object_instances WHERE location_type = 'mobile' AND location_id = $1 ORDER BY id"
+        )
+        .bind(mobile_instance_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get all objects worn or wielded by a mobile instance
+    pub async fn get_worn_by_mobile(&self, mobile_instance_id: i32) -> Result<Vec<ObjectInstance>, sqlx::Error> {
+        sqlx::query_as::<_, ObjectInstance>(
+            "SELECT * FROM object_instances WHERE location_type = 'mobile_equipped' AND location_id = $1 ORDER BY id"
+        )
+        .bind(mobile_instance_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get the contents of a container (or corpse) object instance
+    pub async fn get_in_container(&self, container_instance_id: i32) -> Result<Vec<ObjectInstance>, sqlx::Error> {
+        sqlx::query_as::<_, ObjectInstance>(
+            "SELECT * FROM object_instances WHERE location_type = 'container' AND location_id = $1 ORDER BY id"
+        )
+        .bind(container_instance_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Total weight of everything inside a container, however deeply nested, as each
+    /// container's weight multiplier (value4) lightens what it holds
+    pub async fn get_contents_weight(&self, container_instance_id: i32) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query(&format!(
+            "WITH RECURSIVE contents AS (
+                 SELECT o.id, o.object_vnum, {} / 100.0 AS scale
+                 FROM object_instances o
+                 JOIN object_instances c ON c.id::TEXT = o.location_id
+                 JOIN objects holder ON holder.vnum = c.object_vnum
+                 WHERE o.location_type = 'container' AND o.location_id = $1
+                 UNION ALL
+                 SELECT o.id, o.object_vnum, c.scale * {} / 100.0 FROM object_instances o
+                 JOIN contents c ON o.location_type = 'container' AND o.location_id = c.id::TEXT
+                 JOIN objects holder ON holder.vnum = c.object_vnum
+             )
+             SELECT COALESCE(SUM(objects.weight * contents.scale), 0)::BIGINT AS weight
+             FROM contents JOIN objects ON objects.vnum = contents.object_vnum",
+            WEIGHT_MULT, WEIGHT_MULT
+        ))
+        .bind(container_instance_id.to_string())
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.get("weight"))
+    }
+
+    /// How many items a player has about them and what they weigh, counting everything in their containers
+    ///
+    /// As in ROM's get_obj_number, containers, gems and jewelry don't count as items.
+    pub async fn get_carried_load(&self, player_slack_id: &str) -> Result<(i64, i64), sqlx::Error> {
+        let row = sqlx::query(&load_query("location_type IN ('player', 'equipped') AND location_id = $1"))
+            .bind(player_slack_id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok((row.get("items"), row.get("weight")))
+    }
+
+    /// How many items an object counts as and what it weighs, along with everything inside it
+    pub async fn get_load(&self, instance_id: i32) -> Result<(i64, i64), sqlx::Error> {
+        let row = sqlx::query(&load_query("id = $1"))
+            .bind(instance_id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok((row.get("items"), row.get("weight")))
+    }
+
+    /// Get a shopkeeper's permanent stock
+    pub async fn get_shop_stock(&self, keeper_instance_id: i32) -> Result<Vec<ObjectInstance>, sqlx::Error> {
+        sqlx::query_as::<_, ObjectInstance>(
+            "SELECT * FROM object_instances WHERE location_type = 'shop' AND location_id = $1 ORDER BY id"
+        )
+        .bind(keeper_instance_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+    }
+
     /// Get all equipped objects for a player
     pub async fn get_equipped(&self, player_slack_id: &str) -> Result<Vec<ObjectInstance>, sqlx::Error> {
         sqlx::query_as::<_, ObjectInstance>(
@@ -189,6 +300,159 @@ impl ObjectInstanceRepository {
         Ok(())
     }
 
+    /// Move an object instance if it's still where it was seen, returning false if it has moved or gone
+    pub async fn move_if_at(
+        &self,
+        instance_id: i32,
+        from_type: &str,
+        from_id: &str,
+        location_type: &str,
+        location_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE object_instances SET location_type = $1, location_id = $2, updated_at = $3
+             WHERE id = $4 AND location_type = $5 AND location_id = $6"
+        )
+        .bind(location_type)
+        .bind(location_id)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(instance_id)
+        .bind(from_type)
+        .bind(from_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Move everything a player or mobile holds into a container (or corpse), unequipping it
+    pub async fn transfer_all_to_container(
+        &self,
+        location_types: &[&str],
+        owner_id: &str,
+        container_instance_id: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE object_instances
+             SET location_type = 'container', location_id = $1, equipped_slot = NULL, wear_location = NULL, updated_at = $2
+             WHERE location_type = ANY($3) AND location_id = $4"
+        )
+        .bind(container_instance_id.to_string())
+        .bind(chrono::Utc::now().timestamp())
+        .bind(location_types)
+        .bind(owner_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Count down every object timer by one tick, returning the objects that just expired
+    ///
+    /// Shop stock keeps fresh until it's bought.
+    pub async fn decrement_timers(&self) -> Result<Vec<ObjectInstance>, sqlx::Error> {
+        let ticked = sqlx::query_as::<_, ObjectInstance>(
+            "UPDATE object_instances SET timer = timer - 1
+             WHERE timer IS NOT NULL AND timer > 0 AND location_type <> 'shop'
+             RETURNING *"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(ticked.into_iter().filter(|instance| instance.timer == Some(0)).collect())
+    }
+
+    /// Set how many ticks an object has left before it decays; None means it never does
+    pub async fn update_timer(&self, instance_id: i32, timer: Option<i32>) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query("UPDATE object_instances SET timer = $1, updated_at = $2 WHERE id = $3")
+            .bind(timer)
+            .bind(now)
+            .bind(instance_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Record how worn an object is, out of 100
+    pub async fn update_condition(&self, instance_id: i32, condition: i32) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query("UPDATE object_instances SET current_condition = $1, updated_at = $2 WHERE id = $3")
+            .bind(condition)
+            .bind(now)
+            .bind(instance_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Get every light source players or mobiles are holding lit
+    pub async fn get_lights_held(&self) -> Result<Vec<ObjectInstance>, sqlx::Error> {
+        sqlx::query_as::<_, ObjectInstance>(
+            "SELECT * FROM object_instances
+             WHERE location_type IN ('equipped', 'mobile_equipped') AND equipped_slot = 'light'
+             ORDER BY id"
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Record how many hours a light source has left
+    pub async fn update_light_hours(&self, instance_id: i32, hours: i32) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query("UPDATE object_instances SET light_hours = $1, updated_at = $2 WHERE id = $3")
+            .bind(hours)
+            .bind(now)
+            .bind(instance_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Record a container's flags after it's been opened, closed, locked or picked; None puts back the template's
+    pub async fn update_container_flags(&self, instance_id: i32, flags: Option<&str>) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query("UPDATE object_instances SET container_flags = $1, updated_at = $2 WHERE id = $3")
+            .bind(flags)
+            .bind(now)
+            .bind(instance_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Record how many charges a wand or staff has left
+    pub async fn update_charges(&self, instance_id: i32, charges: i32) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query("UPDATE object_instances SET charges = $1, updated_at = $2 WHERE id = $3")
+            .bind(charges)
+            .bind(now)
+            .bind(instance_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Record how much a drink container holds and of what
+    pub async fn update_drink(&self, instance_id: i32, drinks: i32, liquid: &str) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query("UPDATE object_instances SET charges = $1, liquid = $2, updated_at = $3 WHERE id = $4")
+            .bind(drinks)
+            .bind(liquid)
+            .bind(now)
+            .bind(instance_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Get every instance of an object lying on the floor of a room, oldest first
+    pub async fn get_lying_by_vnum(&self, object_vnum: i32) -> Result<Vec<ObjectInstance>, sqlx::Error> {
+        sqlx::query_as::<_, ObjectInstance>(
+            "SELECT * FROM object_instances WHERE object_vnum = $1 AND location_type = 'room' ORDER BY id"
+        )
+        .bind(object_vnum)
+        .fetch_all(&self.pool)
+        .await
+    }
+
     /// Delete an object instance
     pub async fn delete(&self, instance_id: i32) -> Result<(), sqlx::Error> {
         sqlx::query("DELETE FROM object_instances WHERE id = $1")
@@ -198,6 +462,36 @@ impl ObjectInstanceRepository {
         Ok(())
     }
 
+    /// Delete an object instance if it's still where it was seen, returning false if it has moved or gone
+    pub async fn delete_if_at(&self, instance_id: i32, location_type: &str, location_id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM object_instances WHERE id = $1 AND location_type = $2 AND location_id = $3"
+        )
+        .bind(instance_id)
+        .bind(location_type)
+        .bind(location_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete an object instance along with everything inside it, however deeply nested
+    pub async fn delete_with_contents(&self, instance_id: i32) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "WITH RECURSIVE contents AS (
+                 SELECT id FROM object_instances WHERE id = $1
+                 UNION ALL
+                 SELECT o.id FROM object_instances o
+                 JOIN contents c ON o.location_type = 'container' AND o.location_id = c.id::TEXT
+             )
+             DELETE FROM object_instances WHERE id IN (SELECT id FROM contents)"
+        )
+        .bind(instance_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Delete all object instances in a room
     pub async fn delete_in_room(&self, room_channel_id: &str) -> Result<(), sqlx::Error> {
         sqlx::query("DELETE FROM object_instances WHERE location_type = 'room' AND location_id = $1")
@@ -249,6 +543,22 @@ impl ObjectInstanceRepository {
         Ok(())
     }
 
+    /// Take an item a mobile has equipped back into its inventory
+    pub async fn unequip_from_mobile(&self, instance_id: i32, mobile_instance_id: i32) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query(
+            "UPDATE object_instances
+             SET location_type = 'mobile', location_id = $1, equipped_slot = NULL, updated_at = $2
+             WHERE id = $3"
+        )
+        .bind(mobile_instance_id.to_string())
+        .bind(now)
+        .bind(instance_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Get item in a specific equipment slot for a player
     pub async fn get_item_in_slot(
         &self,
@@ -285,3 +595,26 @@ impl ObjectInstanceRepository {
         Ok(())
     }
 }
+
+/// Percentage of its contents' weight a `holder` object passes on (ROM's WEIGHT_MULT):
+/// a container's value4, or all of it for anything else that holds things
+const WEIGHT_MULT: &str = "CASE WHEN holder.item_type = 'container' THEN holder.value4 ELSE 100 END";
+
+/// Count and weigh the objects matching `roots` and everything nested inside them,
+/// with each container's weight multiplier applied to what it holds
+fn load_query(roots: &str) -> String {
+    format!(
+        "WITH RECURSIVE held AS (
+             SELECT id, object_vnum, 1.0 AS scale FROM object_instances WHERE {}
+             UNION ALL
+             SELECT o.id, o.object_vnum, h.scale * {} / 100.0 FROM object_instances o
+             JOIN held h ON o.location_type = 'container' AND o.location_id = h.id::TEXT
+             JOIN objects holder ON holder.vnum = h.object_vnum
+         )
+         SELECT
+             COUNT(*) FILTER (WHERE objects.item_type NOT IN ('container', 'gem', 'jewelry'))::BIGINT AS items,
+             COALESCE(SUM(objects.weight * held.scale), 0)::BIGINT AS weight
+         FROM held JOIN objects ON objects.vnum = held.object_vnum",
+        roots, WEIGHT_MULT
+    )
+}
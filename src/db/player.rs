@@ -1,4 +1,4 @@
-use crate::models::Player;
+use crate::models::{Player, Position};
 use sqlx::PgPool;
 
 pub struct PlayerRepository {
@@ -74,6 +74,274 @@ impl PlayerRepository {
         Ok(())
     }
 
+    /// Add (or with negative amounts, remove) coins from a player's purse
+    pub async fn add_money(&self, slack_user_id: &str, gold: i32, silver: i32) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE players SET gold = gold + $1, silver = silver + $2, updated_at = $3 WHERE slack_user_id = $4"
+        )
+        .bind(gold)
+        .bind(silver)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Take coins from a player's purse if they have that many, returning false if they don't
+    pub async fn spend_money(&self, slack_user_id: &str, gold: i32, silver: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE players SET gold = gold - $1, silver = silver - $2, updated_at = $3
+             WHERE slack_user_id = $4 AND gold >= $1 AND silver >= $2"
+        )
+        .bind(gold)
+        .bind(silver)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Subtract damage from a player's hit points, returning what they had before and after,
+    /// or nothing if there's no such player
+    pub async fn apply_damage(&self, slack_user_id: &str, damage: i32) -> Result<Option<(i32, i32)>, sqlx::Error> {
+        sqlx::query_as(
+            "UPDATE players SET hit = hit - $1, updated_at = $2 WHERE slack_user_id = $3 RETURNING hit + $1, hit"
+        )
+        .bind(damage)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Restore hit points, up to the player's maximum, returning what they have now
+    pub async fn heal(&self, slack_user_id: &str, amount: i32) -> Result<i32, sqlx::Error> {
+        let (hit,): (i32,) = sqlx::query_as(
+            "UPDATE players SET hit = LEAST(max_hit, hit + $1), updated_at = $2 WHERE slack_user_id = $3 RETURNING hit"
+        )
+        .bind(amount)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(hit)
+    }
+
+    /// Set a player's current hit points
+    pub async fn update_hit(&self, slack_user_id: &str, hit: i32) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE players SET hit = $1, updated_at = $2 WHERE slack_user_id = $3")
+            .bind(hit)
+            .bind(chrono::Utc::now().timestamp())
+            .bind(slack_user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Take mana from a player if they have that much, returning false if they don't
+    pub async fn spend_mana(&self, slack_user_id: &str, mana: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE players SET mana = mana - $1, updated_at = $2 WHERE slack_user_id = $3 AND mana >= $1"
+        )
+        .bind(mana)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Take half of a player's remaining movement
+    pub async fn halve_movement(&self, slack_user_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE players SET movement = movement / 2, updated_at = $1 WHERE slack_user_id = $2")
+            .bind(chrono::Utc::now().timestamp())
+            .bind(slack_user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Add regained hit points, mana and movement, without going past the player's maximums
+    /// or lowering anything already over them
+    pub async fn regenerate(&self, slack_user_id: &str, hit: i32, mana: i32, movement: i32) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE players SET
+                hit = GREATEST(hit, LEAST(max_hit, hit + $1)),
+                mana = GREATEST(mana, LEAST(max_mana, mana + $2)),
+                movement = GREATEST(movement, LEAST(max_movement, movement + $3)),
+                updated_at = $4
+             WHERE slack_user_id = $5"
+        )
+        .bind(hit)
+        .bind(mana)
+        .bind(movement)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Take movement from a player if they have that much, returning false if they don't
+    pub async fn spend_movement(&self, slack_user_id: &str, movement: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE players SET movement = movement - $1, updated_at = $2 WHERE slack_user_id = $3 AND movement >= $1"
+        )
+        .bind(movement)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Replace what worn gear adds to a player's maximum hit points, mana and movement,
+    /// keeping current values within the new maximums
+    pub async fn set_gear_points(&self, slack_user_id: &str, hit: i32, mana: i32, movement: i32) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE players SET
+                max_hit = max_hit - gear_hit + $1, hit = LEAST(hit, max_hit - gear_hit + $1), gear_hit = $1,
+                max_mana = max_mana - gear_mana + $2, mana = LEAST(mana, max_mana - gear_mana + $2), gear_mana = $2,
+                max_movement = max_movement - gear_movement + $3,
+                movement = LEAST(movement, max_movement - gear_movement + $3), gear_movement = $3,
+                updated_at = $4
+             WHERE slack_user_id = $5"
+        )
+        .bind(hit)
+        .bind(mana)
+        .bind(movement)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Add to a player's drunkenness, fullness, thirst and hunger, keeping each between 0 and 48,
+    /// and return the new values in that order
+    pub async fn gain_conditions(
+        &self,
+        slack_user_id: &str,
+        drunk: i32,
+        full: i32,
+        thirst: i32,
+        hunger: i32,
+    ) -> Result<(i32, i32, i32, i32), sqlx::Error> {
+        sqlx::query_as(
+            "UPDATE players SET
+                drunk = LEAST(GREATEST(drunk + $1, 0), 48),
+                fullness = LEAST(GREATEST(fullness + $2, 0), 48),
+                thirst = LEAST(GREATEST(thirst + $3, 0), 48),
+                hunger = LEAST(GREATEST(hunger + $4, 0), 48),
+                updated_at = $5
+             WHERE slack_user_id = $6
+             RETURNING drunk, fullness, thirst, hunger"
+        )
+        .bind(drunk)
+        .bind(full)
+        .bind(thirst)
+        .bind(hunger)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Add (or with a negative amount, take away) experience, never dropping below what the
+    /// player's current level needs at `exp_per_level`
+    ///
+    /// Returns how much it actually changed, the new total and the player's level.
+    pub async fn gain_experience(
+        &self,
+        slack_user_id: &str,
+        amount: i32,
+        exp_per_level: i32,
+    ) -> Result<Option<(i32, i32, i32)>, sqlx::Error> {
+        sqlx::query_as(
+            "WITH old AS (SELECT experience_points FROM players WHERE slack_user_id = $3 FOR UPDATE)
+             UPDATE players
+             SET experience_points = GREATEST(players.experience_points + $1, (level - 1) * $2), updated_at = $4
+             FROM old
+             WHERE slack_user_id = $3
+             RETURNING players.experience_points - old.experience_points, players.experience_points, level"
+        )
+        .bind(amount)
+        .bind(exp_per_level)
+        .bind(slack_user_id)
+        .bind(chrono::Utc::now().timestamp())
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Raise a player from `level` to the next, adding to their hit points, mana, movement
+    /// and practices, and return the player as they are now
+    ///
+    /// Returns nothing if the player is no longer at `level`.
+    pub async fn advance_level(
+        &self,
+        slack_user_id: &str,
+        level: i32,
+        hit: i32,
+        mana: i32,
+        movement: i32,
+        practices: i32,
+    ) -> Result<Option<Player>, sqlx::Error> {
+        sqlx::query_as::<_, Player>(
+            "UPDATE players
+             SET level = level + 1, max_hit = max_hit + $1, hit = hit + $1, max_mana = max_mana + $2,
+                 mana = mana + $2, max_movement = max_movement + $3, movement = movement + $3,
+                 practices = practices + $4, updated_at = $5
+             WHERE slack_user_id = $6 AND level = $7
+             RETURNING *"
+        )
+        .bind(hit)
+        .bind(mana)
+        .bind(movement)
+        .bind(practices)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .bind(level)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Use up one of a player's practice sessions, returning false if they have none left
+    pub async fn spend_practice(&self, slack_user_id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE players SET practices = practices - 1, updated_at = $1 WHERE slack_user_id = $2 AND practices > 0"
+        )
+        .bind(chrono::Utc::now().timestamp())
+        .bind(slack_user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record that a player has been to a room, returning true if it's their first visit
+    pub async fn mark_visited(&self, slack_user_id: &str, room_id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "INSERT INTO player_visited_rooms (slack_user_id, room_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+        )
+        .bind(slack_user_id)
+        .bind(room_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Change what a player is doing (standing, sleeping, ...)
+    pub async fn update_position(&self, slack_user_id: &str, position: Position) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE players SET position = $1, updated_at = $2 WHERE slack_user_id = $3")
+            .bind(position.as_str())
+            .bind(chrono::Utc::now().timestamp())
+            .bind(slack_user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     pub async fn get_or_create(&self, slack_user_id: String, name: String) -> Result<Player, sqlx::Error> {
         if let Some(player) = self.get_by_slack_id(&slack_user_id).await? {
             Ok(player)
@@ -93,6 +361,20 @@ impl PlayerRepository {
         .await
     }
 
+    /// Get all players standing in a virtual room within a vnum range (i.e. inside an area)
+    pub async fn get_players_in_vnum_range(&self, min_vnum: i32, max_vnum: i32) -> Result<Vec<Player>, sqlx::Error> {
+        sqlx::query_as::<_, Player>(
+            "SELECT * FROM players
+             WHERE current_channel_id LIKE 'vnum_%'
+             AND CAST(SUBSTRING(current_channel_id FROM 6) AS INTEGER) BETWEEN $1 AND $2
+             ORDER BY name"
+        )
+        .bind(min_vnum)
+        .bind(max_vnum)
+        .fetch_all(&self.pool)
+        .await
+    }
+
     pub async fn get_all_players(&self) -> Result<Vec<Player>, sqlx::Error> {
         sqlx::query_as::<_, Player>(
             "SELECT * FROM players ORDER BY name"
@@ -1,4 +1,4 @@
-use crate::models::Room;
+use crate::models::{Room, RoomExtraDescription};
 use sqlx::PgPool;
 
 pub struct RoomRepository {
@@ -19,18 +19,26 @@ impl RoomRepository {
 
     pub async fn create(&self, room: &Room) -> Result<(), sqlx::Error> {
         sqlx::query(
-            "INSERT INTO rooms (channel_id, channel_name, description, attached_channel_id, created_at, updated_at)
-             VALUES ($1, $2, $3, $4, $5, $6)
+            "INSERT INTO rooms (channel_id, channel_name, description, attached_channel_id, heal_rate, mana_rate, room_flags, sector_type, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
              ON CONFLICT (channel_id) DO UPDATE SET
              channel_name = EXCLUDED.channel_name,
              description = EXCLUDED.description,
              attached_channel_id = EXCLUDED.attached_channel_id,
+             heal_rate = EXCLUDED.heal_rate,
+             mana_rate = EXCLUDED.mana_rate,
+             room_flags = EXCLUDED.room_flags,
+             sector_type = EXCLUDED.sector_type,
              updated_at = EXCLUDED.updated_at"
         )
         .bind(&room.channel_id)
         .bind(&room.channel_name)
         .bind(&room.description)
         .bind(&room.attached_channel_id)
+        .bind(room.heal_rate)
+        .bind(room.mana_rate)
+        .bind(&room.room_flags)
+        .bind(room.sector_type)
         .bind(room.created_at)
         .bind(room.updated_at)
         .execute(&self.pool)
@@ -82,3 +90,64 @@ impl RoomRepository {
         Ok(())
     }
 }
+
+pub struct RoomExtraDescriptionRepository {
+    pool: PgPool,
+}
+
+impl RoomExtraDescriptionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Add an extra description to a room
+    pub async fn create(&self, extra: &RoomExtraDescription) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO room_extra_descriptions (room_id, keywords, description, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5)"
+        )
+        .bind(&extra.room_id)
+        .bind(&extra.keywords)
+        .bind(&extra.description)
+        .bind(extra.created_at)
+        .bind(extra.updated_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Get every extra description in a room
+    pub async fn get_for_room(&self, room_id: &str) -> Result<Vec<RoomExtraDescription>, sqlx::Error> {
+        sqlx::query_as::<_, RoomExtraDescription>(
+            "SELECT * FROM room_extra_descriptions WHERE room_id = $1 ORDER BY id"
+        )
+        .bind(room_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Find the extra description in a room matching a keyword
+    pub async fn find(&self, room_id: &str, keyword: &str) -> Result<Option<RoomExtraDescription>, sqlx::Error> {
+        Ok(self.get_for_room(room_id).await?
+            .into_iter()
+            .find(|extra| extra.matches_keyword(keyword)))
+    }
+
+    /// Delete one extra description
+    pub async fn delete(&self, id: i32) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM room_extra_descriptions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Delete every extra description in a room (before re-importing it)
+    pub async fn delete_for_room(&self, room_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM room_extra_descriptions WHERE room_id = $1")
+            .bind(room_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
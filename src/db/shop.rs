@@ -0,0 +1,59 @@
+use crate::models::Shop;
+use sqlx::PgPool;
+
+pub struct ShopRepository {
+    pool: PgPool,
+}
+
+impl ShopRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a new shop definition
+    pub async fn create(&self, shop: &Shop) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO shops (
+                keeper_vnum, area_name, buy_types, profit_buy, profit_sell,
+                open_hour, close_hour, created_at, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (keeper_vnum) DO UPDATE SET
+                area_name = EXCLUDED.area_name,
+                buy_types = EXCLUDED.buy_types,
+                profit_buy = EXCLUDED.profit_buy,
+                profit_sell = EXCLUDED.profit_sell,
+                open_hour = EXCLUDED.open_hour,
+                close_hour = EXCLUDED.close_hour,
+                updated_at = EXCLUDED.updated_at"
+        )
+        .bind(shop.keeper_vnum)
+        .bind(&shop.area_name)
+        .bind(&shop.buy_types)
+        .bind(shop.profit_buy)
+        .bind(shop.profit_sell)
+        .bind(shop.open_hour)
+        .bind(shop.close_hour)
+        .bind(shop.created_at)
+        .bind(shop.updated_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Get the shop run by a mobile, if it is a shopkeeper
+    pub async fn get_by_keeper(&self, keeper_vnum: i32) -> Result<Option<Shop>, sqlx::Error> {
+        sqlx::query_as::<_, Shop>("SELECT * FROM shops WHERE keeper_vnum = $1")
+            .bind(keeper_vnum)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Delete all shops for an area
+    pub async fn delete_by_area(&self, area_name: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM shops WHERE area_name = $1")
+            .bind(area_name)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+pub struct SkillRepository {
+    pool: PgPool,
+}
+
+impl SkillRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// How well a player knows each skill they've practiced, in percent
+    pub async fn get_learned(&self, slack_user_id: &str) -> Result<HashMap<String, i32>, sqlx::Error> {
+        let rows: Vec<(String, i32)> = sqlx::query_as(
+            "SELECT skill, learned FROM player_skills WHERE slack_user_id = $1"
+        )
+        .bind(slack_user_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Save how well a player knows a skill
+    pub async fn set_learned(&self, slack_user_id: &str, skill: &str, learned: i32) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO player_skills (slack_user_id, skill, learned) VALUES ($1, $2, $3)
+             ON CONFLICT (slack_user_id, skill) DO UPDATE SET learned = EXCLUDED.learned"
+        )
+        .bind(slack_user_id)
+        .bind(skill)
+        .bind(learned)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
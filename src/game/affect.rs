@@ -0,0 +1,193 @@
+use crate::AppState;
+use crate::area::types::has_flag;
+use crate::db::affect::AffectRepository;
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::db::player::PlayerRepository;
+use crate::game::combat::Combatant;
+use crate::game::skills;
+use crate::models::{Affect, Mobile, Object, Player};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Wizards see everything
+const WIZARD_LEVEL: i32 = 50;
+/// ROM's AFF_INVISIBLE, among a mobile's affect flags
+const AFF_INVISIBLE: char = 'B';
+/// ROM's AFF_DETECT_INVIS, granted by the spell or by gear
+const AFF_DETECT_INVIS: char = 'D';
+/// ROM's ITEM_INVIS, among an object's extra flags
+const ITEM_INVIS: char = 'F';
+
+/// What a set of affects and worn objects adds up to, by ROM apply location
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Modifiers {
+    /// Added to armor class (negative is better)
+    pub ac: i32,
+    pub hitroll: i32,
+    pub damroll: i32,
+    /// Added to the saving throw against spells (negative is better)
+    pub saving_spell: i32,
+    pub strength: i32,
+    pub dexterity: i32,
+}
+
+impl Modifiers {
+    /// Total up every affect's modifier
+    pub fn total(affects: &[Affect]) -> Self {
+        let mut modifiers = Self::default();
+        for affect in affects {
+            modifiers.add(&affect.location, affect.modifier);
+        }
+        modifiers
+    }
+
+    /// Add what a worn object gives its wearer
+    pub fn add_object(&mut self, object: &Object) {
+        for apply in object.applies() {
+            self.add(&apply.location, apply.modifier);
+        }
+    }
+
+    fn add(&mut self, location: &str, modifier: i32) {
+        match location {
+            "ac" => self.ac += modifier,
+            "hitroll" => self.hitroll += modifier,
+            "damroll" => self.damroll += modifier,
+            "saving_spell" => self.saving_spell += modifier,
+            "str" => self.strength += modifier,
+            "dex" => self.dexterity += modifier,
+            _ => {}
+        }
+    }
+}
+
+/// Every affect on a player or mobile
+pub async fn affects_on(state: &Arc<AppState>, who: &Combatant) -> anyhow::Result<Vec<Affect>> {
+    let (target_type, target_id) = who.affect_target();
+    Ok(AffectRepository::new(state.db_pool.clone()).get_for(target_type, &target_id).await?)
+}
+
+/// Bring a player's maximum hit points, mana and movement in line with what they're wearing
+/// (ROM's equip_char and unequip_char)
+///
+/// The other applies are totted up from worn gear whenever they're needed, so only the
+/// stored maximums change here. Call it after anything is put on, taken off or destroyed
+/// while worn; it totals everything worn, so a missed call is made up by the next one.
+pub async fn refresh_gear_points(state: &Arc<AppState>, slack_user_id: &str) -> anyhow::Result<()> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let (mut hit, mut mana, mut movement) = (0, 0, 0);
+    for instance in object_instance_repo.get_equipped(slack_user_id).await? {
+        let Some(object) = object_repo.get_for_instance(&instance).await? else { continue };
+        for apply in object.applies() {
+            match apply.location.as_str() {
+                "hit" => hit += apply.modifier,
+                "mana" => mana += apply.modifier,
+                "move" => movement += apply.modifier,
+                _ => {}
+            }
+        }
+    }
+    PlayerRepository::new(state.db_pool.clone()).set_gear_points(slack_user_id, hit, mana, movement).await?;
+    Ok(())
+}
+
+/// Whether a player can see invisible mobiles and objects (ROM's can_see and can_see_obj)
+///
+/// They need detect invis from a spell or from something they're wearing, unless they're a wizard.
+pub async fn detects_invisible(state: &Arc<AppState>, player: &Player) -> anyhow::Result<bool> {
+    if player.level >= WIZARD_LEVEL {
+        return Ok(true);
+    }
+
+    let who = Combatant::Player(player.slack_user_id.clone());
+    if affects_on(state, &who).await?.iter().any(|affect| has_flag(&affect.bitvector, AFF_DETECT_INVIS)) {
+        return Ok(true);
+    }
+
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    for instance in ObjectInstanceRepository::new(state.db_pool.clone()).get_equipped(&player.slack_user_id).await? {
+        let Some(object) = object_repo.get_for_instance(&instance).await? else { continue };
+        if object.applies().iter().any(|apply| has_flag(&apply.bitvector, AFF_DETECT_INVIS)) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Whether a mobile is invisible to anyone without detect invis
+pub fn is_invisible_mobile(mobile: &Mobile) -> bool {
+    has_flag(&mobile.affect_flags, AFF_INVISIBLE)
+}
+
+/// Whether an object is invisible to anyone without detect invis
+pub fn is_invisible_object(object: &Object) -> bool {
+    has_flag(&object.extra_flags, ITEM_INVIS)
+}
+
+/// One line describing an affect, as ROM's score and affects commands show it
+pub fn describe(affect: &Affect) -> String {
+    let mut line = format!("Spell: '{}'", affect.skill);
+    if affect.location != "none" {
+        line.push_str(&format!(" modifies {} by {}", affect.location.replace('_', " "), affect.modifier));
+    }
+    if affect.duration < 0 {
+        line.push_str(" permanently");
+    } else {
+        line.push_str(&format!(" for {} hours", affect.duration));
+    }
+    line
+}
+
+/// Count down affects and take off the ones that have worn off (on each `Tick::Point`)
+pub async fn affect_update(state: &Arc<AppState>) -> anyhow::Result<()> {
+    let affect_repo = AffectRepository::new(state.db_pool.clone());
+
+    // A spell can leave several affects (bless modifies two things) but wears off with one message
+    let mut told = HashSet::new();
+    for affect in affect_repo.decrement_durations().await? {
+        if affect.target_type != "player" || !told.insert((affect.target_id.clone(), affect.skill.clone())) {
+            continue;
+        }
+        let Some(message) = skills::lookup(&affect.skill).and_then(|skill| skill.wear_off) else { continue };
+        if let Err(e) = state.slack_client.send_dm(&affect.target_id, message).await {
+            tracing::warn!("Failed to tell {} that {} wore off: {}", affect.target_id, affect.skill, e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modifiers_total_and_describe() {
+        let affects = vec![
+            Affect::new("player", "U1".to_string(), "armor", 10, 24).modifying("ac", -20),
+            Affect::new("player", "U1".to_string(), "bless", 16, 22).modifying("hitroll", 2),
+            Affect::new("player", "U1".to_string(), "bless", 16, 22).modifying("saving_spell", -2),
+            Affect::new("player", "U1".to_string(), "detect invis", 10, -1).granting("D"),
+        ];
+
+        let modifiers = Modifiers::total(&affects);
+        assert_eq!(modifiers, Modifiers { ac: -20, hitroll: 2, damroll: 0, saving_spell: -2, strength: 0, dexterity: 0 });
+
+        assert_eq!(describe(&affects[2]), "Spell: 'bless' modifies saving spell by -2 for 22 hours");
+        assert_eq!(describe(&affects[3]), "Spell: 'detect invis' permanently");
+    }
+
+    #[test]
+    fn test_is_invisible_object() {
+        let object = |extra_flags: &str| Object::new(
+            3010, "midgaard".to_string(), "ring".to_string(),
+            "a ring".to_string(), "A ring is here.".to_string(),
+            "gold".to_string(), "jewelry".to_string(), extra_flags.to_string(), "AB".to_string(),
+            0, 0, String::new(), 0, 0, 1, 10, 0, "P".to_string(),
+        );
+        assert!(is_invisible_object(&object("FG")));
+        assert!(!is_invisible_object(&object("G")));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::AppState;
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::game::affect::{self, Modifiers};
+use crate::game::combat::Combatant;
+use crate::models::{Object, Player};
+use std::sync::Arc;
+
+/// Wizards carry as much as they like
+const WIZARD_LEVEL: i32 = 50;
+/// Players have no rolled stats, so everyone starts from ROM's average 13 before affects and gear
+const BASE_STAT: i32 = 13;
+/// ROM's MAX_WEAR: a slot's worth of items for everything that can be worn
+const MAX_WEAR: i32 = 19;
+
+/// How many items someone has about them and what they weigh, in tenths of a pound
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Load {
+    pub items: i64,
+    pub weight: i64,
+}
+
+impl Load {
+    pub fn plus(self, other: Load) -> Load {
+        Load { items: self.items + other.items, weight: self.weight + other.weight }
+    }
+}
+
+/// Why someone can't take on more
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overload {
+    Items,
+    Weight,
+}
+
+/// Strength's carrying capacity in pounds (ROM's str_app carry column)
+fn strength_carry(strength: i32) -> i64 {
+    const CARRY: [i64; 26] = [
+        0, 3, 3, 10, 25, 55, 80, 90, 100, 100, 115, 115, 130,
+        130, 140, 150, 165, 180, 200, 225, 250, 300, 350, 400, 450, 500,
+    ];
+    CARRY[strength.clamp(0, 25) as usize]
+}
+
+/// Most a player can carry for their level, strength and dexterity (ROM's can_carry_n and can_carry_w)
+pub fn limits(level: i32, strength: i32, dexterity: i32) -> Load {
+    if level >= WIZARD_LEVEL {
+        return Load { items: 1000, weight: 10_000_000 };
+    }
+    Load {
+        items: i64::from(MAX_WEAR + 2 * dexterity.clamp(3, 25) + level),
+        weight: strength_carry(strength) * 10 + i64::from(level) * 25,
+    }
+}
+
+/// Whether picking up `adding` on top of `carried` would go over the limits
+pub fn overload(carried: Load, adding: Load, limits: Load) -> Option<Overload> {
+    let total = carried.plus(adding);
+    if total.items > limits.items {
+        Some(Overload::Items)
+    } else if total.weight > limits.weight {
+        Some(Overload::Weight)
+    } else {
+        None
+    }
+}
+
+/// What a player is told when something they'd pick up is too much for them (ROM's do_get)
+pub fn too_much(overload: Overload, what: &str) -> String {
+    match overload {
+        Overload::Items => format!("{}: you can't carry that many items.", what),
+        Overload::Weight => format!("{}: you can't carry that much weight.", what),
+    }
+}
+
+/// A player's limits, with whatever their affects and worn gear do to strength and dexterity
+pub async fn player_limits(state: &Arc<AppState>, player: &Player) -> anyhow::Result<Load> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let who = Combatant::Player(player.slack_user_id.clone());
+    let mut modifiers = Modifiers::total(&affect::affects_on(state, &who).await?);
+    for instance in object_instance_repo.get_equipped(&player.slack_user_id).await? {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
+            modifiers.add_object(&object);
+        }
+    }
+
+    Ok(limits(player.level, BASE_STAT + modifiers.strength, BASE_STAT + modifiers.dexterity))
+}
+
+/// Everything a player has about them, worn or carried, contents and all
+pub async fn carried(state: &Arc<AppState>, slack_user_id: &str) -> anyhow::Result<Load> {
+    let (items, weight) = ObjectInstanceRepository::new(state.db_pool.clone()).get_carried_load(slack_user_id).await?;
+    Ok(Load { items, weight })
+}
+
+/// What one object adds to someone's load, contents and all
+pub async fn load_of(state: &Arc<AppState>, instance_id: i32) -> anyhow::Result<Load> {
+    let (items, weight) = ObjectInstanceRepository::new(state.db_pool.clone()).get_load(instance_id).await?;
+    Ok(Load { items, weight })
+}
+
+/// What a fresh, empty copy of an object would add to someone's load
+///
+/// Containers, gems and jewelry don't count as items, just as in `load_of`.
+pub fn new_load(object: &Object) -> Load {
+    let items = if matches!(object.item_type.as_str(), "container" | "gem" | "jewelry") { 0 } else { 1 };
+    Load { items, weight: i64::from(object.weight) }
+}
+
+/// Whether a player can take on an object, or why not
+pub async fn refusal(state: &Arc<AppState>, player: &Player, instance_id: i32) -> anyhow::Result<Option<Overload>> {
+    let limits = player_limits(state, player).await?;
+    let carried = carried(state, &player.slack_user_id).await?;
+    let adding = load_of(state, instance_id).await?;
+    Ok(overload(carried, adding, limits))
+}
+
+/// "Items: 12/30  Weight: 45/200" for the inventory, in pounds
+pub fn summary(carried: Load, limits: Load) -> String {
+    format!(
+        "Items: {}/{}  Weight: {}/{}",
+        carried.items,
+        limits.items,
+        carried.weight / 10,
+        limits.weight / 10
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(limits(1, 13, 13), Load { items: 46, weight: 1325 });
+        assert_eq!(limits(10, 18, 3), Load { items: 35, weight: 2250 });
+        assert_eq!(limits(50, 0, 0).items, 1000);
+    }
+
+    #[test]
+    fn test_overload() {
+        let limits = Load { items: 30, weight: 2000 };
+        let carried = Load { items: 29, weight: 1900 };
+        assert_eq!(overload(carried, Load { items: 1, weight: 100 }, limits), None);
+        assert_eq!(overload(carried, Load { items: 2, weight: 10 }, limits), Some(Overload::Items));
+        assert_eq!(overload(carried, Load { items: 1, weight: 150 }, limits), Some(Overload::Weight));
+
+        // A bag adds no items, only its weight
+        let full = Load { items: 30, weight: 100 };
+        assert_eq!(overload(full, Load { items: 0, weight: 50 }, limits), None);
+        assert_eq!(too_much(Overload::Weight, "A barrel"), "A barrel: you can't carry that much weight.");
+        assert_eq!(summary(Load { items: 12, weight: 455 }, limits), "Items: 12/30  Weight: 45/200");
+    }
+
+    #[test]
+    fn test_new_load() {
+        let object = |item_type: &str| Object::new(
+            3010, "midgaard".to_string(), "thing".to_string(),
+            "a thing".to_string(), "A thing is here.".to_string(),
+            "wood".to_string(), item_type.to_string(), String::new(), "A".to_string(),
+            0, 0, String::new(), 0, 0, 1, 10, 0, "P".to_string(),
+        );
+        assert_eq!(new_load(&object("weapon")), Load { items: 1, weight: 1 });
+        assert_eq!(new_load(&object("container")), Load { items: 0, weight: 1 });
+    }
+}
@@ -0,0 +1,662 @@
+use crate::AppState;
+use crate::area::Dice;
+use crate::db::affect::AffectRepository;
+use crate::db::class::ClassRepository;
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::db::player::PlayerRepository;
+use crate::db::room::RoomRepository;
+use crate::area::types::RoomFlags;
+use crate::game::affect::{self, Modifiers};
+use crate::game::experience;
+use crate::models::{EquipmentSlot, Object, ObjectInstance, Position};
+use rand::Rng;
+use std::sync::Arc;
+
+/// One side of a fight
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Combatant {
+    /// A player, by Slack user ID
+    Player(String),
+    /// A mobile instance, by instance ID
+    Mobile(i32),
+}
+
+impl Combatant {
+    pub fn slack_user_id(&self) -> Option<&str> {
+        match self {
+            Combatant::Player(id) => Some(id),
+            Combatant::Mobile(_) => None,
+        }
+    }
+
+    /// How affects refer to this combatant: its target type and ID
+    pub fn affect_target(&self) -> (&'static str, String) {
+        match self {
+            Combatant::Player(id) => ("player", id.clone()),
+            Combatant::Mobile(id) => ("mobile", id.to_string()),
+        }
+    }
+}
+
+/// Which armor class protects against an attack (ROM's AC_PIERCE .. AC_EXOTIC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageClass {
+    Pierce,
+    Bash,
+    Slash,
+    Exotic,
+}
+
+impl DamageClass {
+    /// Class of a ROM attack noun, e.g. "stab" is pierce and "crush" is bash
+    pub fn of_attack(attack: &str) -> Self {
+        match attack {
+            "stab" | "pierce" | "bite" | "peck" | "sting" | "thrust" | "chomp" | "scratch" => DamageClass::Pierce,
+            "slice" | "slash" | "whip" | "claw" | "grep" | "cleave" | "chop" => DamageClass::Slash,
+            "hit" | "pound" | "crush" | "blast" | "suction" | "beating" | "charge" | "slap" | "punch"
+            | "smash" | "thwack" => DamageClass::Bash,
+            _ => DamageClass::Exotic,
+        }
+    }
+}
+
+/// Everything a combat round needs to know about one side
+#[derive(Debug, Clone)]
+pub struct Fighter {
+    pub who: Combatant,
+    /// Name for the start of a sentence ("The cityguard")
+    pub name: String,
+    /// Name for the middle of a sentence ("the cityguard")
+    pub short: String,
+    pub room_id: String,
+    pub level: i32,
+    pub max_hit: i32,
+    /// Stored position; someone caught sitting or asleep takes extra damage
+    position: Position,
+    /// Saving throw against spells (negative is better)
+    pub saving_throw: i32,
+    hitroll: i32,
+    damroll: i32,
+    /// Class THAC0 at level 32 (ROM's thac0_32; everyone starts from 20 at level 0)
+    thac0_32: i32,
+    /// Damage dice of the wielded weapon or the mobile's natural attack; None for bare hands
+    damage: Option<Dice>,
+    /// Attack noun used in messages ("slash", "bite", "punch")
+    attack: String,
+    /// Armor class against pierce, bash, slash and exotic attacks (ROM scale: 100 is unarmored)
+    armor: [i32; 4],
+}
+
+impl Fighter {
+    fn armor_against(&self, class: DamageClass) -> i32 {
+        self.armor[class as usize]
+    }
+
+    /// Load a combatant's current fighting stats, or None if they're gone from the world
+    pub async fn load(state: &Arc<AppState>, who: &Combatant) -> anyhow::Result<Option<Fighter>> {
+        let object_repo = ObjectRepository::new(state.db_pool.clone());
+        let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+        match who {
+            Combatant::Player(slack_user_id) => {
+                let player_repo = PlayerRepository::new(state.db_pool.clone());
+                let class_repo = ClassRepository::new(state.db_pool.clone());
+
+                let Some(player) = player_repo.get_by_slack_id(slack_user_id).await? else { return Ok(None) };
+                let Some(room_id) = player.current_channel_id.clone() else { return Ok(None) };
+
+                let class_name = match player.class_id {
+                    Some(id) => class_repo.get_by_id(id).await?.map(|c| c.name),
+                    None => None,
+                };
+
+                let worn = load_objects(&object_repo, object_instance_repo.get_equipped(slack_user_id).await?).await?;
+                let weapon = wielded(&worn);
+                let mut modifiers = Modifiers::total(&affect::affects_on(state, who).await?);
+                worn.iter().for_each(|(_, object)| modifiers.add_object(object));
+
+                // Worn gear subtracts its armor class from the unarmored 100
+                let ac = 100 - worn.iter().map(|(_, object)| object.get_armor_class()).sum::<i32>() + modifiers.ac;
+
+                Ok(Some(Fighter {
+                    who: who.clone(),
+                    name: player.name.clone(),
+                    short: player.name.clone(),
+                    room_id,
+                    level: player.level,
+                    max_hit: player.max_hit,
+                    position: player.position(),
+                    saving_throw: modifiers.saving_spell,
+                    hitroll: modifiers.hitroll,
+                    damroll: modifiers.damroll,
+                    thac0_32: class_thac0_32(class_name.as_deref()),
+                    damage: weapon.and_then(weapon_dice),
+                    attack: weapon.and_then(|w| w.get_damage_type()).unwrap_or_else(|| "punch".to_string()),
+                    armor: [ac; 4],
+                }))
+            }
+            Combatant::Mobile(instance_id) => {
+                let mobile_repo = MobileRepository::new(state.db_pool.clone());
+                let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+
+                let Some(instance) = mobile_instance_repo.get_by_id(*instance_id).await? else { return Ok(None) };
+                let Some(mobile) = mobile_repo.get_by_vnum(instance.mobile_vnum).await? else { return Ok(None) };
+
+                let worn = load_objects(&object_repo, object_instance_repo.get_worn_by_mobile(instance.id).await?).await?;
+                let weapon = wielded(&worn);
+                let natural = Dice::parse(&mobile.damage_dice).ok();
+                let mut modifiers = Modifiers::total(&affect::affects_on(state, who).await?);
+                worn.iter().for_each(|(_, object)| modifiers.add_object(object));
+
+                let attack = match weapon.and_then(|w| w.get_damage_type()) {
+                    Some(attack) => attack,
+                    None if mobile.damage_type.is_empty() || mobile.damage_type == "none" => "hit".to_string(),
+                    None => mobile.damage_type.clone(),
+                };
+
+                Ok(Some(Fighter {
+                    who: who.clone(),
+                    name: mobile.display_name(),
+                    short: mobile.short_description.clone(),
+                    level: mobile.level,
+                    max_hit: instance.max_hit,
+                    position: instance.position(),
+                    room_id: instance.room_id,
+                    saving_throw: modifiers.saving_spell,
+                    hitroll: mobile.hitroll + modifiers.hitroll,
+                    // ROM uses the bonus of the damage dice as the mobile's damroll
+                    damroll: natural.map_or(0, |dice| dice.bonus) + modifiers.damroll,
+                    thac0_32: -4,
+                    damage: weapon.and_then(weapon_dice)
+                        .or(natural.map(|dice| Dice { bonus: 0, ..dice })),
+                    attack,
+                    // Area files give armor in tens
+                    armor: [mobile.ac_pierce, mobile.ac_bash, mobile.ac_slash, mobile.ac_exotic]
+                        .map(|ac| ac * 10 + modifiers.ac),
+                }))
+            }
+        }
+    }
+}
+
+async fn load_objects(
+    object_repo: &ObjectRepository,
+    instances: Vec<ObjectInstance>,
+) -> anyhow::Result<Vec<(ObjectInstance, Object)>> {
+    let mut objects = Vec::new();
+    for instance in instances {
+        if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+            objects.push((instance, object));
+        }
+    }
+    Ok(objects)
+}
+
+fn wielded(worn: &[(ObjectInstance, Object)]) -> Option<&Object> {
+    let wield = EquipmentSlot::Wield.to_db_string();
+    worn.iter()
+        .find(|(instance, _)| instance.equipped_slot.as_deref() == Some(wield))
+        .map(|(_, object)| object)
+}
+
+fn weapon_dice(weapon: &Object) -> Option<Dice> {
+    Dice::parse(&weapon.get_weapon_damage()?).ok()
+}
+
+/// ROM's class THAC0 at level 32 (lower is better)
+fn class_thac0_32(class_name: Option<&str>) -> i32 {
+    match class_name.map(|name| name.to_lowercase()).as_deref() {
+        Some("warrior") => -10,
+        Some("rogue") | Some("thief") => -4,
+        Some("cleric") => 2,
+        Some("mage") => 6,
+        _ => 0,
+    }
+}
+
+/// Number to beat on a d20 to hit, before the victim's armor is taken into account
+pub fn thac0(level: i32, thac0_32: i32, hitroll: i32) -> i32 {
+    // Interpolate from 20 at level 0 to the class value at level 32
+    let mut thac0 = 20 + (thac0_32 - 20) * level / 32;
+    if thac0 < 0 {
+        thac0 /= 2;
+    }
+    if thac0 < -5 {
+        thac0 = -5 + (thac0 + 5) / 2;
+    }
+    thac0 - hitroll
+}
+
+/// Whether a d20 roll (0-19) hits: 0 always misses, 19 always hits
+pub fn roll_hits(diceroll: i32, thac0: i32, victim_ac: i32) -> bool {
+    let mut victim_ac = victim_ac / 10;
+    if victim_ac < -15 {
+        victim_ac = (victim_ac + 15) / 5 - 15;
+    }
+    !(diceroll == 0 || (diceroll != 19 && diceroll < thac0 - victim_ac))
+}
+
+/// ROM's diminishing returns on big hits
+fn reduce_damage(mut damage: i32) -> i32 {
+    if damage > 35 {
+        damage = (damage - 35) / 2 + 35;
+    }
+    if damage > 80 {
+        damage = (damage - 80) / 2 + 80;
+    }
+    damage
+}
+
+/// What the attacker, the victim and everyone else see for one blow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlowMessages {
+    pub to_attacker: String,
+    pub to_victim: String,
+    pub to_room: String,
+}
+
+/// ROM's dam_message: the verb depends on the blow's share of the victim's maximum hit points
+pub fn damage_messages(attacker: &Fighter, victim: &Fighter, damage: i32) -> BlowMessages {
+    let percent = 100 * damage / victim.max_hit.max(1);
+    let verb = match percent {
+        _ if damage == 0 => "misses",
+        ..=5 => "scratches",
+        6..=10 => "grazes",
+        11..=15 => "hits",
+        16..=20 => "injures",
+        21..=25 => "wounds",
+        26..=30 => "mauls",
+        31..=35 => "decimates",
+        36..=40 => "devastates",
+        41..=45 => "maims",
+        46..=50 => "MUTILATES",
+        51..=55 => "DISEMBOWELS",
+        56..=60 => "DISMEMBERS",
+        61..=65 => "MASSACRES",
+        66..=70 => "MANGLES",
+        71..=75 => "*** DEMOLISHES ***",
+        76..=80 => "*** DEVASTATES ***",
+        81..=85 => "=== OBLITERATES ===",
+        86..=90 => ">>> ANNIHILATES <<<",
+        91..=95 => "<<< ERADICATES >>>",
+        _ => "does UNSPEAKABLE things to",
+    };
+    let punct = if damage <= 24 { '.' } else { '!' };
+
+    BlowMessages {
+        to_attacker: format!("Your {} {} {}{}", attacker.attack, verb, victim.short, punct),
+        to_victim: format!("{}'s {} {} you{}", attacker.name, attacker.attack, verb, punct),
+        to_room: format!("{}'s {} {} {}{}", attacker.name, attacker.attack, verb, victim.short, punct),
+    }
+}
+
+/// Who a combatant is fighting, if anyone
+pub fn fighting(state: &AppState, who: &Combatant) -> Option<Combatant> {
+    state.fights.lock().unwrap().get(who).cloned()
+}
+
+/// Start `attacker` fighting `victim` unless it's already fighting someone
+pub fn set_fighting(state: &AppState, attacker: &Combatant, victim: &Combatant) {
+    state.fights.lock().unwrap().entry(attacker.clone()).or_insert_with(|| victim.clone());
+}
+
+/// How hurt someone looks, from their share of hit points left (ROM's look condition)
+pub fn condition(hit: i32, max_hit: i32) -> &'static str {
+    let percent = if max_hit > 0 { 100 * hit / max_hit } else { -1 };
+    match percent {
+        100.. => "is in excellent condition.",
+        90..=99 => "has a few scratches.",
+        75..=89 => "has some small wounds and bruises.",
+        50..=74 => "has quite a few wounds.",
+        30..=49 => "has some big nasty wounds and scratches.",
+        15..=29 => "looks pretty hurt.",
+        0..=14 => "is in awful condition.",
+        _ => "is bleeding to death.",
+    }
+}
+
+/// What a combatant is doing: fighting while in a fight, otherwise their stored position
+pub fn effective_position(state: &AppState, who: &Combatant, stored: Position) -> Position {
+    if stored == Position::Standing && fighting(state, who).is_some() {
+        Position::Fighting
+    } else {
+        stored
+    }
+}
+
+/// Store a combatant's position
+pub async fn set_position(state: &Arc<AppState>, who: &Combatant, position: Position) -> anyhow::Result<()> {
+    match who {
+        Combatant::Player(id) => PlayerRepository::new(state.db_pool.clone()).update_position(id, position).await?,
+        Combatant::Mobile(id) => MobileInstanceRepository::new(state.db_pool.clone()).update_position(*id, position).await?,
+    }
+    Ok(())
+}
+
+/// Stop a combatant fighting, and with `both` set, everyone fighting it too
+pub fn stop_fighting(state: &AppState, who: &Combatant, both: bool) {
+    let mut fights = state.fights.lock().unwrap();
+    fights.remove(who);
+    if both {
+        fights.retain(|_, victim| victim != who);
+    }
+}
+
+/// Whether fighting is forbidden in a room (ROM's ROOM_SAFE)
+pub async fn is_safe_room(state: &Arc<AppState>, room_id: &str) -> anyhow::Result<bool> {
+    let room = RoomRepository::new(state.db_pool.clone()).get_by_channel_id(room_id).await?;
+    Ok(room.is_some_and(|room| room.flags().contains(RoomFlags::SAFE)))
+}
+
+/// Attack someone: both sides start fighting and the attacker gets an immediate blow
+pub async fn attack(state: &Arc<AppState>, attacker: &Fighter, victim: &Fighter) -> anyhow::Result<()> {
+    set_fighting(state, &attacker.who, &victim.who);
+    one_hit(state, attacker, victim).await?;
+    Ok(())
+}
+
+/// Swing once at the victim, returning true if it died
+pub async fn one_hit(state: &Arc<AppState>, attacker: &Fighter, victim: &Fighter) -> anyhow::Result<bool> {
+    let (hit, rolled) = {
+        let mut rng = rand::thread_rng();
+        let thac0 = thac0(attacker.level, attacker.thac0_32, attacker.hitroll);
+        let victim_ac = victim.armor_against(DamageClass::of_attack(&attacker.attack));
+        let hit = roll_hits(rng.gen_range(0..20), thac0, victim_ac);
+
+        let rolled = match attacker.damage {
+            Some(dice) => dice.roll(),
+            // Bare hands, as ROM rolls for an unarmed player with full skill
+            None => {
+                let high = 2 * attacker.level / 3;
+                if high > 5 { rng.gen_range(5..=high) } else { 5 }
+            }
+        };
+        (hit, rolled)
+    };
+
+    if hit {
+        crate::game::object::wear_from_blow(state, attacker, victim).await?;
+    }
+
+    let mut damage = if hit { (rolled + attacker.damroll).max(1) } else { 0 };
+    if victim.position <= Position::Sleeping {
+        damage *= 2;
+    } else if victim.position < Position::Fighting {
+        damage = damage * 3 / 2;
+    }
+    inflict(state, attacker, victim, damage).await
+}
+
+/// Hurt the victim with a spell, named by `noun` in the damage messages; returns true if it died
+pub async fn spell_damage(
+    state: &Arc<AppState>,
+    caster: &Fighter,
+    victim: &Fighter,
+    damage: i32,
+    noun: &str,
+) -> anyhow::Result<bool> {
+    let caster = Fighter { attack: noun.to_string(), ..caster.clone() };
+    set_fighting(state, &caster.who, &victim.who);
+    inflict(state, &caster, victim, damage).await
+}
+
+/// Apply a blow's damage, tell everyone about it and handle the victim dying
+async fn inflict(state: &Arc<AppState>, attacker: &Fighter, victim: &Fighter, damage: i32) -> anyhow::Result<bool> {
+    let damage = reduce_damage(damage);
+
+    // Being hit makes the victim fight back, waking them if need be
+    set_fighting(state, &victim.who, &attacker.who);
+    if victim.position != Position::Standing {
+        set_position(state, &victim.who, Position::Standing).await?;
+    }
+
+    let hit = match &victim.who {
+        Combatant::Player(id) => PlayerRepository::new(state.db_pool.clone()).apply_damage(id, damage).await?,
+        Combatant::Mobile(id) => MobileInstanceRepository::new(state.db_pool.clone()).apply_damage(*id, damage).await?,
+    };
+    // Another blow has already killed the victim and it's gone
+    let Some((before, after)) = hit else { return Ok(false) };
+
+    let messages = damage_messages(attacker, victim, damage);
+    let to_attacker = format!("_{}_", messages.to_attacker);
+    let to_victim = format!("_{}_", messages.to_victim);
+    let mut personal = Vec::new();
+    if let Some(id) = attacker.who.slack_user_id() {
+        personal.push((id, to_attacker.as_str()));
+    }
+    if let Some(id) = victim.who.slack_user_id() {
+        personal.push((id, to_victim.as_str()));
+    }
+    crate::handlers::broadcast_room_message(state, &victim.room_id, &format!("_{}_", messages.to_room), &personal).await?;
+
+    // Only the blow that takes the victim from alive to dead kills it, so two blows
+    // landing together (a spell during a round, say) can't kill it twice
+    if before <= 0 || after > 0 {
+        return Ok(false);
+    }
+
+    raw_kill(state, attacker, victim).await?;
+    Ok(true)
+}
+
+/// Where players wake up after dying (ROM's ROOM_VNUM_ALTAR, in the Midgaard temple)
+pub const DEATH_ROOM: &str = "vnum_3054";
+/// Corpse templates, ROM's OBJ_VNUM_CORPSE_NPC and OBJ_VNUM_CORPSE_PC
+const CORPSE_NPC_VNUM: i32 = 10;
+const CORPSE_PC_VNUM: i32 = 11;
+
+/// Take a dead combatant out of the fight, leaving a corpse behind
+///
+/// Mobiles leave the world; players wake up at the temple altar with a sliver of health.
+async fn raw_kill(state: &Arc<AppState>, killer: &Fighter, victim: &Fighter) -> anyhow::Result<()> {
+    stop_fighting(state, &victim.who, true);
+
+    let death = format!("_{} is DEAD!!_", victim.name);
+    let personal: Vec<(&str, &str)> = victim.who.slack_user_id()
+        .map(|id| (id, "_You have been KILLED!!_"))
+        .into_iter()
+        .collect();
+    crate::handlers::broadcast_room_message(state, &victim.room_id, &death, &personal).await?;
+
+    award_exp(state, killer, victim).await?;
+    make_corpse(state, killer, victim).await?;
+
+    match &victim.who {
+        Combatant::Player(id) => {
+            let player_repo = PlayerRepository::new(state.db_pool.clone());
+            player_repo.update_current_channel(id, DEATH_ROOM).await?;
+            player_repo.update_hit(id, 1).await?;
+            AffectRepository::new(state.db_pool.clone()).delete_for("player", id).await?;
+            set_position(state, &victim.who, Position::Resting).await?;
+            crate::handlers::broadcast_room_message(
+                state,
+                DEATH_ROOM,
+                &format!("_{} appears in a shimmer of light, looking shaken._", victim.name),
+                &[(id.as_str(), "_You awaken at the altar of the temple, naked and shaken._")],
+            ).await?;
+        }
+        Combatant::Mobile(id) => {
+            MobileInstanceRepository::new(state.db_pool.clone()).delete(*id).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Experience for the kill, or the cost of dying (ROM's group_gain and death penalty)
+async fn award_exp(state: &Arc<AppState>, killer: &Fighter, victim: &Fighter) -> anyhow::Result<()> {
+    match (&killer.who, &victim.who) {
+        (Combatant::Player(id), Combatant::Mobile(_)) => {
+            let xp = experience::kill_exp(killer.level, victim.level);
+            if xp > 0 {
+                state.slack_client.send_dm(id, &format!("You receive {} experience points.", xp)).await?;
+                experience::gain_exp(state, id, xp).await?;
+            }
+        }
+        (_, Combatant::Player(id)) => {
+            let Some(player) = PlayerRepository::new(state.db_pool.clone()).get_by_slack_id(id).await? else { return Ok(()) };
+            let exp_per_level = experience::player_class(state, &player).await?
+                .map_or(experience::DEFAULT_EXP_PER_LEVEL, |class| class.exp_per_level);
+            let loss = experience::death_exp_loss(player.experience_points, player.level, exp_per_level);
+            if loss > 0 {
+                experience::gain_exp(state, id, -loss).await?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Leave a corpse in the victim's room holding everything they carried and wore
+///
+/// A mobile's coins go straight to the player who killed it.
+async fn make_corpse(state: &Arc<AppState>, killer: &Fighter, victim: &Fighter) -> anyhow::Result<()> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    // Like ROM, NPC corpses rot within minutes while player corpses last a good half hour
+    let (vnum, timer, held, owner_id) = match &victim.who {
+        Combatant::Mobile(id) => {
+            (CORPSE_NPC_VNUM, rand::thread_rng().gen_range(3..=6), ["mobile", "mobile_equipped"], id.to_string())
+        }
+        Combatant::Player(id) => {
+            (CORPSE_PC_VNUM, rand::thread_rng().gen_range(25..=40), ["player", "equipped"], id.clone())
+        }
+    };
+
+    let Some(template) = object_repo.get_by_vnum(vnum).await? else {
+        tracing::warn!("Corpse object {} is missing, so {} left no corpse", vnum, victim.name);
+        return Ok(());
+    };
+    let corpse = ObjectInstance::new_corpse(&template, &victim.short, victim.room_id.clone(), timer);
+    let corpse_id = object_instance_repo.create(&corpse).await?;
+    object_instance_repo.transfer_all_to_container(&held, &owner_id, corpse_id).await?;
+    if let Combatant::Player(id) = &victim.who {
+        // Everything worn came off into the corpse
+        affect::refresh_gear_points(state, id).await?;
+    }
+    crate::game::object::rot_contents(state, corpse_id).await?;
+
+    if let (Combatant::Mobile(id), Some(killer_id)) = (&victim.who, killer.who.slack_user_id()) {
+        let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+        if let Some(instance) = mobile_instance_repo.get_by_id(*id).await? {
+            if instance.gold > 0 || instance.silver > 0 {
+                PlayerRepository::new(state.db_pool.clone()).add_money(killer_id, instance.gold, instance.silver).await?;
+                state.slack_client.send_dm(
+                    killer_id,
+                    &format!(
+                        "_You get {} gold and {} silver coins from {}._",
+                        instance.gold,
+                        instance.silver,
+                        corpse.short_description.as_deref().unwrap_or("the corpse"),
+                    ),
+                ).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run one combat round for every fight in progress (on each `Tick::Violence`)
+pub async fn violence_update(state: &Arc<AppState>) -> anyhow::Result<()> {
+    let fights: Vec<(Combatant, Combatant)> = state.fights.lock().unwrap()
+        .iter()
+        .map(|(attacker, victim)| (attacker.clone(), victim.clone()))
+        .collect();
+
+    for (attacker_id, victim_id) in fights {
+        // An earlier blow this round may already have ended this fight
+        if fighting(state, &attacker_id).as_ref() != Some(&victim_id) {
+            continue;
+        }
+
+        let attacker = Fighter::load(state, &attacker_id).await?;
+        let victim = Fighter::load(state, &victim_id).await?;
+        match (attacker, victim) {
+            (Some(attacker), Some(victim)) if attacker.room_id == victim.room_id => {
+                one_hit(state, &attacker, &victim).await?;
+            }
+            _ => stop_fighting(state, &attacker_id, false),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fighter(name: &str, max_hit: i32, attack: &str) -> Fighter {
+        Fighter {
+            who: Combatant::Mobile(1),
+            name: name.to_string(),
+            short: name.to_lowercase(),
+            room_id: "vnum_3001".to_string(),
+            level: 1,
+            max_hit,
+            position: Position::Standing,
+            saving_throw: 0,
+            hitroll: 0,
+            damroll: 0,
+            thac0_32: 0,
+            damage: None,
+            attack: attack.to_string(),
+            armor: [100; 4],
+        }
+    }
+
+    #[test]
+    fn test_thac0_interpolates_by_level() {
+        assert_eq!(thac0(0, -10, 0), 20);
+        assert_eq!(thac0(16, -10, 0), 5);
+        // Negative values are halved, then softened again below -5
+        assert_eq!(thac0(32, -10, 0), -5);
+        assert_eq!(thac0(10, 0, 3), 11);
+    }
+
+    #[test]
+    fn test_roll_hits() {
+        // 0 always misses and 19 always hits
+        assert!(!roll_hits(0, -20, 100));
+        assert!(roll_hits(19, 40, -200));
+        // A THAC0 of 19 against an unarmored target (AC 100) needs 9 or better
+        assert!(!roll_hits(8, 19, 100));
+        assert!(roll_hits(9, 19, 100));
+    }
+
+    #[test]
+    fn test_damage_messages() {
+        let attacker = fighter("Bob", 20, "slash");
+        let victim = fighter("The cityguard", 100, "punch");
+
+        let miss = damage_messages(&attacker, &victim, 0);
+        assert_eq!(miss.to_attacker, "Your slash misses the cityguard.");
+        assert_eq!(miss.to_victim, "Bob's slash misses you.");
+
+        let maul = damage_messages(&attacker, &victim, 28);
+        assert_eq!(maul.to_room, "Bob's slash mauls the cityguard!");
+
+        let huge = damage_messages(&attacker, &victim, 500);
+        assert_eq!(huge.to_attacker, "Your slash does UNSPEAKABLE things to the cityguard!");
+    }
+
+    #[test]
+    fn test_damage_class_of_attack() {
+        assert_eq!(DamageClass::of_attack("stab"), DamageClass::Pierce);
+        assert_eq!(DamageClass::of_attack("crush"), DamageClass::Bash);
+        assert_eq!(DamageClass::of_attack("slash"), DamageClass::Slash);
+        assert_eq!(DamageClass::of_attack("flame"), DamageClass::Exotic);
+    }
+
+    #[test]
+    fn test_condition_follows_hit_ratio() {
+        assert_eq!(condition(20, 20), "is in excellent condition.");
+        assert_eq!(condition(19, 20), "has a few scratches.");
+        assert_eq!(condition(17, 20), "has some small wounds and bruises.");
+        assert_eq!(condition(1, 20), "is in awful condition.");
+        assert_eq!(condition(-3, 20), "is bleeding to death.");
+    }
+}
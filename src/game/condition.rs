@@ -0,0 +1,161 @@
+use crate::AppState;
+use crate::db::player::PlayerRepository;
+use crate::models::Player;
+use std::sync::Arc;
+
+/// Wizards never hunger, thirst or get drunk
+const WIZARD_LEVEL: i32 = 50;
+/// Highest any condition goes
+pub const MAX_CONDITION: i32 = 48;
+/// What an hour does to drunkenness, fullness, thirst and hunger
+const HOURLY: (i32, i32, i32, i32) = (-1, -2, -1, -1);
+
+/// A player's hunger, thirst, fullness and drunkenness (ROM's COND_* values)
+///
+/// Hunger and thirst count down from 48 (well fed, well watered) to 0 (starving,
+/// parched); fullness caps how much more can be eaten or drunk; drunkenness
+/// counts down to sober at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conditions {
+    pub drunk: i32,
+    pub full: i32,
+    pub thirst: i32,
+    pub hunger: i32,
+}
+
+impl Conditions {
+    pub fn of(player: &Player) -> Self {
+        Self { drunk: player.drunk, full: player.fullness, thirst: player.thirst, hunger: player.hunger }
+    }
+
+    /// Add to each condition, keeping them between 0 and 48 (ROM's gain_condition)
+    pub fn gain(self, drunk: i32, full: i32, thirst: i32, hunger: i32) -> Self {
+        let gain = |current: i32, amount: i32| (current + amount).clamp(0, MAX_CONDITION);
+        Self {
+            drunk: gain(self.drunk, drunk),
+            full: gain(self.full, full),
+            thirst: gain(self.thirst, thirst),
+            hunger: gain(self.hunger, hunger),
+        }
+    }
+
+    /// One hour's worth of sobering up, digesting and getting hungrier and thirstier
+    pub fn tick(self) -> Self {
+        let (drunk, full, thirst, hunger) = HOURLY;
+        self.gain(drunk, full, thirst, hunger)
+    }
+
+    pub fn is_starving(&self) -> bool {
+        self.hunger == 0
+    }
+
+    pub fn is_parched(&self) -> bool {
+        self.thirst == 0
+    }
+
+    pub fn is_drunk(&self) -> bool {
+        self.drunk > 10
+    }
+}
+
+/// Whether a player's conditions never change
+pub fn is_immune(level: i32) -> bool {
+    level >= WIZARD_LEVEL
+}
+
+/// What to tell a player whose conditions went from `before` to `after`
+///
+/// Only running out is worth a message, so players who are away aren't told
+/// they're hungry every hour.
+pub fn ran_out(before: Conditions, after: Conditions) -> Vec<&'static str> {
+    let mut messages = Vec::new();
+    if after.is_starving() && !before.is_starving() {
+        messages.push("You are hungry.");
+    }
+    if after.is_parched() && !before.is_parched() {
+        messages.push("You are thirsty.");
+    }
+    if after.drunk == 0 && before.drunk > 0 {
+        messages.push("You are sober.");
+    }
+    messages
+}
+
+/// Cut a regeneration gain in half for starving and again for parched players (ROM's hit_gain)
+pub fn regen_penalty(gain: i32, conditions: Conditions) -> i32 {
+    let mut gain = gain;
+    if conditions.is_starving() {
+        gain /= 2;
+    }
+    if conditions.is_parched() {
+        gain /= 2;
+    }
+    gain
+}
+
+/// Add to a player's stored conditions as `Conditions::gain` does, returning where they end up
+///
+/// The change is made in the database rather than saving values worked out from a
+/// copy of the player, so eating or drinking during the hourly tick isn't lost.
+pub async fn gain(
+    state: &Arc<AppState>,
+    slack_user_id: &str,
+    drunk: i32,
+    full: i32,
+    thirst: i32,
+    hunger: i32,
+) -> anyhow::Result<Conditions> {
+    let (drunk, full, thirst, hunger) = PlayerRepository::new(state.db_pool.clone())
+        .gain_conditions(slack_user_id, drunk, full, thirst, hunger)
+        .await?;
+    Ok(Conditions { drunk, full, thirst, hunger })
+}
+
+/// Make every player in the world hungrier and thirstier (on each `Tick::Point`)
+pub async fn condition_update(state: &Arc<AppState>) -> anyhow::Result<()> {
+    for player in PlayerRepository::new(state.db_pool.clone()).get_all_players().await? {
+        if player.current_channel_id.is_none() || is_immune(player.level) {
+            continue;
+        }
+
+        let before = Conditions::of(&player);
+        if before.tick() == before {
+            continue;
+        }
+        let (drunk, full, thirst, hunger) = HOURLY;
+        let after = gain(state, &player.slack_user_id, drunk, full, thirst, hunger).await?;
+        for message in ran_out(before, after) {
+            if let Err(e) = state.slack_client.send_dm(&player.slack_user_id, message).await {
+                tracing::warn!("Failed to tell {} '{}': {}", player.slack_user_id, message, e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_and_ran_out() {
+        let fed = Conditions { drunk: 1, full: 1, thirst: 1, hunger: 2 };
+        let after = fed.tick();
+        assert_eq!(after, Conditions { drunk: 0, full: 0, thirst: 0, hunger: 1 });
+        assert_eq!(ran_out(fed, after), vec!["You are thirsty.", "You are sober."]);
+
+        // Nothing more to say once they've already run out
+        assert!(ran_out(after.tick(), after.tick().tick()).is_empty());
+        assert_eq!(Conditions { drunk: 0, full: 47, thirst: 40, hunger: 40 }.gain(20, 10, 0, 0).full, MAX_CONDITION);
+    }
+
+    #[test]
+    fn test_regen_penalty() {
+        let fed = Conditions { drunk: 0, full: 20, thirst: 20, hunger: 20 };
+        assert_eq!(regen_penalty(20, fed), 20);
+        assert_eq!(regen_penalty(20, Conditions { hunger: 0, ..fed }), 10);
+        assert_eq!(regen_penalty(20, Conditions { hunger: 0, thirst: 0, ..fed }), 5);
+        assert!(is_immune(50) && !is_immune(49));
+    }
+}
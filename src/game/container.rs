@@ -0,0 +1,144 @@
+use crate::AppState;
+use crate::area::types::ContainerFlags;
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::game::door::{DoorAction, Lock};
+use crate::models::{Object, ObjectInstance};
+use std::sync::Arc;
+
+/// Whether things can be taken out of an object: containers and corpses
+pub fn is_container(object: &Object) -> bool {
+    matches!(object.item_type.as_str(), "container" | "npc_corpse" | "pc_corpse")
+}
+
+/// A container's flags: its own once someone has changed them, otherwise its template's value1
+pub fn flags(object: &Object, instance: &ObjectInstance) -> ContainerFlags {
+    match &instance.container_flags {
+        Some(letters) => ContainerFlags::from_str(letters),
+        None => ContainerFlags::from_str(&object.value1.to_string()),
+    }
+}
+
+/// The vnum of the key that locks a container (its value2), or 0 if it has none
+pub fn key_vnum(object: &Object) -> i32 {
+    object.value2.trim().parse().unwrap_or(0)
+}
+
+/// A container's lock, for the door commands
+pub fn lock(object: &Object, instance: &ObjectInstance) -> Lock {
+    let flags = flags(object, instance);
+    Lock {
+        closeable: flags.contains(ContainerFlags::CLOSEABLE),
+        closed: flags.contains(ContainerFlags::CLOSED),
+        locked: flags.contains(ContainerFlags::LOCKED),
+        lockable: key_vnum(object) > 0,
+        pickproof: flags.contains(ContainerFlags::PICKPROOF),
+    }
+}
+
+/// "in" or, for things like tables that hold what's put on them, "on"
+pub fn preposition(object: &Object, instance: &ObjectInstance) -> &'static str {
+    if flags(object, instance).contains(ContainerFlags::PUT_ON) { "on" } else { "in" }
+}
+
+/// Why an object won't go into a container, if it won't (ROM's do_put)
+///
+/// Weights are in tenths of a pound while a container's capacity (value0) and
+/// largest item (value3) are in pounds. `object_weight` includes anything inside
+/// the object; `contents_weight` is what the container already holds.
+pub fn wont_fit(container: &Object, object: &Object, object_weight: i64, contents_weight: i64) -> Option<&'static str> {
+    // A bag that lightens its load (value4 under 100) can't be nested to multiply the effect
+    if object.item_type == "container" && object.value4 != 100 {
+        return Some("You have a feeling that would be a bad idea.");
+    }
+    if object_weight > i64::from(container.value3) * 10
+        || contents_weight + object_weight > i64::from(container.value0) * 10
+    {
+        return Some("It won't fit.");
+    }
+    None
+}
+
+/// An object's weight along with everything inside it
+pub async fn weight_with_contents(state: &Arc<AppState>, object: &Object, instance: &ObjectInstance) -> anyhow::Result<i64> {
+    let contents = if is_container(object) {
+        ObjectInstanceRepository::new(state.db_pool.clone()).get_contents_weight(instance.id).await?
+    } else {
+        0
+    };
+    Ok(i64::from(object.weight) + contents)
+}
+
+/// Open, close, lock, unlock or pick a container that `door::refusal` allowed
+pub async fn work_lock(state: &Arc<AppState>, action: DoorAction, object: &Object, instance: &ObjectInstance) -> anyhow::Result<()> {
+    let (closed, locked) = action.result();
+    let mut flags = flags(object, instance);
+    flags.set(ContainerFlags::CLOSED, closed);
+    flags.set(ContainerFlags::LOCKED, locked);
+    ObjectInstanceRepository::new(state.db_pool.clone())
+        .update_container_flags(instance.id, Some(&flags.to_letters()))
+        .await?;
+    Ok(())
+}
+
+/// Find an object by keyword where someone can reach it: in the room, then carried, then worn (ROM's get_obj_here)
+pub async fn find_here(
+    state: &Arc<AppState>,
+    player_slack_id: &str,
+    room_id: &str,
+    keyword: &str,
+) -> anyhow::Result<Option<(ObjectInstance, Object)>> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let mut instances = object_instance_repo.get_in_room(room_id).await?;
+    instances.extend(object_instance_repo.get_in_player_inventory(player_slack_id).await?);
+    instances.extend(object_instance_repo.get_equipped(player_slack_id).await?);
+    for instance in instances {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
+            if object.matches_keyword(keyword) {
+                return Ok(Some((instance, object)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container(capacity: i32, flags: &str, key: &str, max_item: i32, multiplier: i32) -> Object {
+        let flags = ContainerFlags::from_str(flags).bits() as i32;
+        Object::new(
+            3032, "midgaard".to_string(), "bag".to_string(), "a bag".to_string(), "A small bag.".to_string(),
+            "leather".to_string(), "container".to_string(), String::new(), "A".to_string(),
+            capacity, flags, key.to_string(), max_item, multiplier, 10, 9, 0, "G".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_flags_and_lock() {
+        let chest = container(34, "ABCD", "3122", 20, 100);
+        let mut instance = ObjectInstance::new_in_room(chest.vnum, "vnum_3001".to_string());
+        assert_eq!(
+            lock(&chest, &instance),
+            Lock { closeable: true, closed: true, locked: true, lockable: true, pickproof: true }
+        );
+
+        instance.container_flags = Some("A".to_string());
+        assert!(!lock(&chest, &instance).closed);
+        assert_eq!(preposition(&chest, &instance), "in");
+    }
+
+    #[test]
+    fn test_wont_fit() {
+        let bag = container(50, "A", "0", 5, 100);
+        let pouch = container(5, "0", "0", 5, 100);
+        assert_eq!(wont_fit(&bag, &pouch, 40, 400), None);
+        assert_eq!(wont_fit(&bag, &pouch, 60, 0), Some("It won't fit."));
+        assert_eq!(wont_fit(&bag, &pouch, 40, 480), Some("It won't fit."));
+
+        let holding = container(100, "A", "0", 50, 50);
+        assert_eq!(wont_fit(&bag, &holding, 10, 0), Some("You have a feeling that would be a bad idea."));
+    }
+}
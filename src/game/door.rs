@@ -0,0 +1,206 @@
+use crate::AppState;
+use crate::area::types::ExitFlags;
+use crate::db::exit::ExitRepository;
+use crate::db::object::ObjectInstanceRepository;
+use crate::game::combat::Combatant;
+use crate::models::Exit;
+use crate::models::exit::expand_direction;
+use std::sync::Arc;
+
+/// Something that can be done to a door
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoorAction {
+    Open,
+    Close,
+    Lock,
+    Unlock,
+    Pick,
+}
+
+impl DoorAction {
+    /// The command word
+    pub fn verb(self) -> &'static str {
+        match self {
+            DoorAction::Open => "open",
+            DoorAction::Close => "close",
+            DoorAction::Lock => "lock",
+            DoorAction::Unlock => "unlock",
+            DoorAction::Pick => "pick",
+        }
+    }
+
+    /// Whether the door ends up (closed, locked)
+    pub fn result(self) -> (bool, bool) {
+        match self {
+            DoorAction::Open => (false, false),
+            DoorAction::Close => (true, false),
+            DoorAction::Lock => (true, true),
+            DoorAction::Unlock | DoorAction::Pick => (true, false),
+        }
+    }
+}
+
+/// Find the door someone means by direction or keyword (ROM's find_door)
+///
+/// The error is what to tell them when there's no such door.
+pub fn find_door<'a>(exits: &'a [Exit], arg: &str) -> Result<&'a Exit, String> {
+    if let Some(direction) = expand_direction(arg) {
+        let Some(exit) = exits.iter().find(|exit| exit.direction == direction) else {
+            return Err(format!("I see no door {} here.", direction));
+        };
+        if !exit.is_door() {
+            return Err("You can't do that.".to_string());
+        }
+        return Ok(exit);
+    }
+
+    exits.iter()
+        .find(|exit| exit.is_door() && exit.matches_keyword(arg))
+        .ok_or_else(|| format!("I see no {} here.", arg))
+}
+
+/// The state of a door's or container's lock, as far as the door commands care
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lock {
+    pub closeable: bool,
+    pub closed: bool,
+    pub locked: bool,
+    /// Whether it has a key at all
+    pub lockable: bool,
+    pub pickproof: bool,
+}
+
+impl Lock {
+    pub fn of_exit(exit: &Exit) -> Self {
+        let flags = exit.flags();
+        Self {
+            closeable: flags.contains(ExitFlags::IS_DOOR),
+            closed: flags.contains(ExitFlags::CLOSED),
+            locked: flags.contains(ExitFlags::LOCKED),
+            lockable: exit.key_vnum > 0,
+            pickproof: flags.contains(ExitFlags::PICKPROOF),
+        }
+    }
+}
+
+/// Why a door action can't be done, if it can't; `has_key` only matters for locking and unlocking
+pub fn refusal(action: DoorAction, lock: Lock, has_key: bool) -> Option<&'static str> {
+    match action {
+        DoorAction::Open if !lock.closed => Some("It's already open."),
+        DoorAction::Close if lock.closed => Some("It's already closed."),
+        DoorAction::Open | DoorAction::Close if !lock.closeable => Some("You can't do that."),
+        DoorAction::Open if lock.locked => Some("It's locked."),
+        DoorAction::Lock | DoorAction::Unlock | DoorAction::Pick if !lock.closed => Some("It's not closed."),
+        DoorAction::Lock if !lock.lockable => Some("It can't be locked."),
+        DoorAction::Unlock if !lock.lockable => Some("It can't be unlocked."),
+        DoorAction::Pick if !lock.lockable => Some("It can't be picked."),
+        DoorAction::Lock | DoorAction::Unlock if !has_key => Some("You lack the key."),
+        DoorAction::Lock if lock.locked => Some("It's already locked."),
+        DoorAction::Unlock | DoorAction::Pick if !lock.locked => Some("It's already unlocked."),
+        DoorAction::Pick if lock.pickproof => Some("You failed."),
+        _ => None,
+    }
+}
+
+/// Whether someone is carrying a key, worn or not (ROM's has_key)
+pub async fn has_key(state: &Arc<AppState>, who: &Combatant, key_vnum: i32) -> anyhow::Result<bool> {
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+    let carried = match who {
+        Combatant::Player(id) => object_instance_repo.get_by_owner(id).await?,
+        Combatant::Mobile(id) => {
+            let mut carried = object_instance_repo.get_carried_by_mobile(*id).await?;
+            carried.extend(object_instance_repo.get_worn_by_mobile(*id).await?);
+            carried
+        }
+    };
+    Ok(carried.iter().any(|instance| instance.object_vnum == key_vnum))
+}
+
+/// Do something to a door that `refusal` allowed, on both sides, and tell both rooms
+///
+/// `actor_user_id` is the player doing it, who gets their own version of the message.
+pub async fn work_door(
+    state: &Arc<AppState>,
+    action: DoorAction,
+    exit: &Exit,
+    actor_name: &str,
+    actor_user_id: Option<&str>,
+) -> anyhow::Result<()> {
+    let exit_repo = ExitRepository::new(state.db_pool.clone());
+    let (closed, locked) = action.result();
+    exit_repo.set_door_state(exit, closed, locked).await?;
+
+    let door = exit.door_name();
+    let to_actor = match action {
+        DoorAction::Open | DoorAction::Close => format!("_You {} the {}._", action.verb(), door),
+        DoorAction::Lock | DoorAction::Unlock | DoorAction::Pick => "*Click*".to_string(),
+    };
+    crate::handlers::broadcast_room_action(
+        state,
+        &exit.from_room_id,
+        &format!("_{} {}s the {}._", actor_name, action.verb(), door),
+        actor_user_id,
+        Some(&to_actor),
+    ).await?;
+
+    // Whoever is on the other side hears it open or shut
+    let far_side = match action {
+        DoorAction::Open => Some(format!("_The {} opens._", door)),
+        DoorAction::Close => Some(format!("_The {} closes._", door)),
+        _ => None,
+    };
+    if let (Some(message), Some(reverse)) = (far_side, exit_repo.get_reverse(exit).await?) {
+        crate::handlers::broadcast_room_action(state, &reverse.from_room_id, &message, None, None).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exit(direction: &str, keyword: &str, flags: ExitFlags, key_vnum: i32) -> Exit {
+        let mut exit = Exit::new("vnum_1".to_string(), direction.to_string(), "vnum_2".to_string(), None);
+        exit.keyword = keyword.to_string();
+        exit.exit_flags = flags.to_letters();
+        exit.key_vnum = key_vnum;
+        exit
+    }
+
+    #[test]
+    fn test_find_door() {
+        let exits = vec![
+            exit("north", "gate", ExitFlags::IS_DOOR | ExitFlags::CLOSED, 3133),
+            exit("east", "", ExitFlags::empty(), 0),
+        ];
+        assert_eq!(find_door(&exits, "gate").unwrap().direction, "north");
+        assert_eq!(find_door(&exits, "n").unwrap().direction, "north");
+        assert_eq!(find_door(&exits, "east").unwrap_err(), "You can't do that.");
+        assert_eq!(find_door(&exits, "s").unwrap_err(), "I see no door south here.");
+        assert_eq!(find_door(&exits, "hatch").unwrap_err(), "I see no hatch here.");
+    }
+
+    #[test]
+    fn test_refusal() {
+        let open = Lock::of_exit(&exit("north", "door", ExitFlags::IS_DOOR, 10));
+        let locked = Lock::of_exit(&exit("north", "door", ExitFlags::IS_DOOR | ExitFlags::CLOSED | ExitFlags::LOCKED, 10));
+        let keyless = Lock::of_exit(&exit("north", "door", ExitFlags::IS_DOOR | ExitFlags::CLOSED, -1));
+        let pickproof = Lock::of_exit(&exit(
+            "north", "door", ExitFlags::IS_DOOR | ExitFlags::CLOSED | ExitFlags::LOCKED | ExitFlags::PICKPROOF, 10,
+        ));
+        let fixed = Lock { closeable: false, ..keyless };
+
+        assert_eq!(refusal(DoorAction::Open, open, false), Some("It's already open."));
+        assert_eq!(refusal(DoorAction::Close, open, false), None);
+        assert_eq!(refusal(DoorAction::Lock, open, true), Some("It's not closed."));
+        assert_eq!(refusal(DoorAction::Open, locked, false), Some("It's locked."));
+        assert_eq!(refusal(DoorAction::Unlock, locked, false), Some("You lack the key."));
+        assert_eq!(refusal(DoorAction::Unlock, locked, true), None);
+        assert_eq!(refusal(DoorAction::Lock, keyless, true), Some("It can't be locked."));
+        assert_eq!(refusal(DoorAction::Pick, locked, false), None);
+        assert_eq!(refusal(DoorAction::Pick, pickproof, false), Some("You failed."));
+        assert_eq!(refusal(DoorAction::Close, fixed, false), Some("It's already closed."));
+        assert_eq!(refusal(DoorAction::Open, fixed, false), Some("You can't do that."));
+    }
+}
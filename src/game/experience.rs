@@ -0,0 +1,167 @@
+use crate::AppState;
+use crate::db::class::ClassRepository;
+use crate::db::player::PlayerRepository;
+use crate::models::{Class, Player};
+use rand::Rng;
+use std::sync::Arc;
+
+/// Level wizards are promoted to; never reachable by play
+const WIZARD_LEVEL: i32 = 50;
+/// The highest level a mortal can reach by gaining experience (ROM's LEVEL_HERO)
+pub const HERO_LEVEL: i32 = WIZARD_LEVEL - 1;
+/// Experience per level for players who haven't picked a class yet
+pub const DEFAULT_EXP_PER_LEVEL: i32 = 1000;
+/// Practice sessions gained with each level
+const PRACTICES_PER_LEVEL: i32 = 2;
+/// Experience for walking into a room for the first time
+pub const EXPLORE_EXP: i32 = 5;
+
+/// Total experience needed to reach a level (everyone starts level 1 with none)
+pub fn exp_for_level(level: i32, exp_per_level: i32) -> i32 {
+    (level - 1) * exp_per_level
+}
+
+/// Experience for killing something, before ROM's low-level boost and randomness
+pub fn base_kill_exp(killer_level: i32, victim_level: i32) -> i32 {
+    match victim_level - killer_level {
+        ..=-10 => 0,
+        -9 => 1,
+        -8 => 2,
+        -7 => 5,
+        -6 => 9,
+        -5 => 11,
+        -4 => 22,
+        -3 => 33,
+        -2 => 50,
+        -1 => 66,
+        0 => 83,
+        1 => 99,
+        2 => 121,
+        3 => 143,
+        4 => 165,
+        diff => 160 + 20 * (diff - 4),
+    }
+}
+
+/// Experience a player earns for a kill (ROM's xp_compute for someone fighting alone)
+pub fn kill_exp(killer_level: i32, victim_level: i32) -> i32 {
+    let mut xp = base_kill_exp(killer_level, victim_level);
+
+    // Newbies get a boost
+    if killer_level < 6 {
+        xp = 10 * xp / (killer_level + 4);
+    }
+
+    if xp < 4 {
+        return xp;
+    }
+    rand::thread_rng().gen_range(xp * 3 / 4..=xp * 5 / 4)
+}
+
+/// Experience lost on death: two thirds of the way into the current level, never a whole level
+pub fn death_exp_loss(exp: i32, level: i32, exp_per_level: i32) -> i32 {
+    let progress = exp - exp_for_level(level, exp_per_level);
+    (2 * progress / 3).max(0)
+}
+
+/// Look up a player's class, if they have one
+pub async fn player_class(state: &Arc<AppState>, player: &Player) -> anyhow::Result<Option<Class>> {
+    let Some(class_id) = player.class_id else { return Ok(None) };
+    Ok(ClassRepository::new(state.db_pool.clone()).get_by_id(class_id).await?)
+}
+
+/// Give (or with a negative amount, take away) experience, raising levels as it's earned
+///
+/// Levels stop at `HERO_LEVEL`; experience keeps counting beyond it but nobody
+/// reaches wizard level by play. Returns how much the player's experience actually changed.
+pub async fn gain_exp(state: &Arc<AppState>, slack_user_id: &str, amount: i32) -> anyhow::Result<i32> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let Some(player) = player_repo.get_by_slack_id(slack_user_id).await? else { return Ok(0) };
+
+    let class = player_class(state, &player).await?;
+    let exp_per_level = class.as_ref().map_or(DEFAULT_EXP_PER_LEVEL, |c| c.exp_per_level);
+    // Added in the database, where levels are never lost by losing experience,
+    // so awards landing together are all kept
+    let Some((change, experience, mut level)) = player_repo.gain_experience(slack_user_id, amount, exp_per_level).await? else {
+        return Ok(0);
+    };
+
+    while level < HERO_LEVEL && experience >= exp_for_level(level + 1, exp_per_level) {
+        if !advance_level(state, &player, level, class.as_ref()).await? {
+            // Another award has already raised them
+            break;
+        }
+        level += 1;
+    }
+
+    Ok(change)
+}
+
+/// Raise a player from `level` to the next, rolling their new hit points, mana and movement (ROM's advance_level)
+///
+/// The gains are added to what the player has now rather than to `player`, which may be out of date.
+/// Returns false if the player is no longer at `level`.
+async fn advance_level(state: &Arc<AppState>, player: &Player, level: i32, class: Option<&Class>) -> anyhow::Result<bool> {
+    let (hp, mana, movement) = {
+        let mut rng = rand::thread_rng();
+        let hp = match class {
+            Some(class) => rng.gen_range(class.hp_min..=class.hp_max.max(class.hp_min)),
+            None => rng.gen_range(8..=12),
+        };
+        let mut mana = rng.gen_range(2..=7);
+        if !class.is_none_or(|c| c.gains_mana) {
+            mana /= 2;
+        }
+        (hp, mana, rng.gen_range(1..=4))
+    };
+
+    let Some(player) = PlayerRepository::new(state.db_pool.clone())
+        .advance_level(&player.slack_user_id, level, hp, mana, movement, PRACTICES_PER_LEVEL)
+        .await?
+    else {
+        return Ok(false);
+    };
+
+    let announcement = format!(
+        "*You raise a level!!*  Your gain is: {}/{} hp, {}/{} m, {}/{} mv {}/{} prac.",
+        hp, player.max_hit, mana, player.max_mana, movement, player.max_movement,
+        PRACTICES_PER_LEVEL, player.practices
+    );
+    match player.current_channel_id.as_deref() {
+        Some(room_id) => {
+            crate::handlers::broadcast_room_message(
+                state,
+                room_id,
+                &format!("_{} has reached level {}!_", player.name, player.level),
+                &[(player.slack_user_id.as_str(), announcement.as_str())],
+            ).await?;
+        }
+        None => state.slack_client.send_dm(&player.slack_user_id, &announcement).await?,
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kill_exp_scales_with_level_difference() {
+        assert_eq!(base_kill_exp(10, 0), 0);
+        assert_eq!(base_kill_exp(10, 10), 83);
+        assert_eq!(base_kill_exp(10, 16), 200);
+        assert!(kill_exp(20, 10) == 0);
+        let xp = kill_exp(10, 10);
+        assert!((62..=103).contains(&xp));
+    }
+
+    #[test]
+    fn test_level_thresholds() {
+        assert_eq!(exp_for_level(1, 1000), 0);
+        assert_eq!(exp_for_level(3, 1200), 2400);
+        assert_eq!(death_exp_loss(2300, 2, 1000), 866);
+        assert_eq!(death_exp_loss(1000, 2, 1000), 0);
+        const { assert!(HERO_LEVEL < WIZARD_LEVEL) };
+    }
+}
@@ -0,0 +1,182 @@
+use crate::AppState;
+use crate::area::types::{RoomFlags, SectorType};
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::db::player::PlayerRepository;
+use crate::game::{affect, time};
+use crate::models::{EquipmentSlot, Object, ObjectInstance, Room};
+use std::sync::Arc;
+
+/// Wizards' lights never burn down
+const WIZARD_LEVEL: i32 = 50;
+/// A light starts to flicker when it has this many hours left
+const FLICKER_HOURS: i32 = 5;
+
+/// Hours of light left in a light source (ROM's value2): 0 is burnt out and negative burns forever
+pub fn hours_left(object: &Object, instance: &ObjectInstance) -> i32 {
+    instance.light_hours.unwrap_or_else(|| object.value2.trim().parse().unwrap_or(0))
+}
+
+/// Whether the sun is down: from sunset at 7pm until sunrise at 5am (ROM's SUN_SET and SUN_DARK)
+pub fn is_night(hour: i32) -> bool {
+    !(5..19).contains(&hour)
+}
+
+/// Whether a room with no light in it is dark at an hour (ROM's room_is_dark)
+///
+/// DARK rooms always are; rooms inside or in the city never are; anywhere else is
+/// dark at night.
+fn is_dark(flags: RoomFlags, sector: SectorType, hour: i32) -> bool {
+    if flags.contains(RoomFlags::DARK) {
+        return true;
+    }
+    if matches!(sector, SectorType::Inside | SectorType::City) {
+        return false;
+    }
+    is_night(hour)
+}
+
+/// Whether a room is too dark to see in, unless someone there holds a light that's still burning
+pub async fn room_is_dark(state: &Arc<AppState>, room: &Room) -> anyhow::Result<bool> {
+    if !is_dark(room.flags(), room.sector(), time::current_hour()) {
+        return Ok(false);
+    }
+    Ok(!room_has_light(state, &room.channel_id).await?)
+}
+
+async fn room_has_light(state: &Arc<AppState>, room_id: &str) -> anyhow::Result<bool> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+    let light_slot = EquipmentSlot::Light.to_db_string();
+
+    let mut held = Vec::new();
+    for player in PlayerRepository::new(state.db_pool.clone()).get_players_in_room(room_id).await? {
+        held.extend(object_instance_repo.get_item_in_slot(&player.slack_user_id, light_slot).await?);
+    }
+    for mobile in MobileInstanceRepository::new(state.db_pool.clone()).get_in_room(room_id).await? {
+        held.extend(
+            object_instance_repo.get_worn_by_mobile(mobile.id).await?
+                .into_iter()
+                .filter(|instance| instance.equipped_slot.as_deref() == Some(light_slot)),
+        );
+    }
+
+    for instance in &held {
+        if let Some(object) = object_repo.get_for_instance(instance).await? {
+            if object.item_type == "light" && hours_left(&object, instance) != 0 {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Burn an hour off every light players and mobiles are holding, putting out the ones that run dry (on each `Tick::Point`)
+pub async fn light_update(state: &Arc<AppState>) -> anyhow::Result<()> {
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    for instance in object_instance_repo.get_lights_held().await? {
+        if let Err(e) = burn(state, &instance).await {
+            tracing::warn!("Failed to burn light {}: {}", instance.id, e);
+        }
+    }
+
+    Ok(())
+}
+
+async fn burn(state: &Arc<AppState>, instance: &ObjectInstance) -> anyhow::Result<()> {
+    let Some(object) = ObjectRepository::new(state.db_pool.clone()).get_for_instance(instance).await? else {
+        return Ok(());
+    };
+    let hours = hours_left(&object, instance);
+    if object.item_type != "light" || hours <= 0 {
+        return Ok(());
+    }
+
+    if instance.location_type == "mobile_equipped" {
+        burn_for_mobile(state, instance, &object, hours - 1).await
+    } else {
+        burn_for_player(state, instance, &object, hours - 1).await
+    }
+}
+
+/// Mobiles' lights burn down like anyone's; only their going out is worth a message
+async fn burn_for_mobile(state: &Arc<AppState>, instance: &ObjectInstance, object: &Object, hours: i32) -> anyhow::Result<()> {
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+    if hours > 0 {
+        object_instance_repo.update_light_hours(instance.id, hours).await?;
+        return Ok(());
+    }
+
+    let Ok(mobile_id) = instance.location_id.parse::<i32>() else { return Ok(()) };
+    let Some(holder) = MobileInstanceRepository::new(state.db_pool.clone()).get_by_id(mobile_id).await? else {
+        return Ok(());
+    };
+    let Some(mobile) = MobileRepository::new(state.db_pool.clone()).get_by_vnum(holder.mobile_vnum).await? else {
+        return Ok(());
+    };
+
+    object_instance_repo.delete(instance.id).await?;
+    crate::handlers::broadcast_room_action(
+        state,
+        &holder.room_id,
+        &format!("_{}'s {} goes out._", mobile.display_name(), object.short_description),
+        None,
+        None,
+    ).await
+}
+
+async fn burn_for_player(state: &Arc<AppState>, instance: &ObjectInstance, object: &Object, hours: i32) -> anyhow::Result<()> {
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+    let Some(player) = PlayerRepository::new(state.db_pool.clone()).get_by_slack_id(&instance.location_id).await? else {
+        return Ok(());
+    };
+    if player.level >= WIZARD_LEVEL {
+        return Ok(());
+    }
+
+    if hours > 0 {
+        object_instance_repo.update_light_hours(instance.id, hours).await?;
+        if hours <= FLICKER_HOURS {
+            state.slack_client.send_dm(&player.slack_user_id, &format!("{} flickers.", object.display_name())).await?;
+        }
+        return Ok(());
+    }
+
+    // Burnt out: the light is used up
+    object_instance_repo.delete(instance.id).await?;
+    affect::refresh_gear_points(state, &player.slack_user_id).await?;
+    let to_char = format!("_{} flickers and goes out._", object.display_name());
+    match player.current_channel_id.as_deref() {
+        Some(room_id) => {
+            crate::handlers::broadcast_room_action(
+                state,
+                room_id,
+                &format!("_{}'s {} goes out._", player.name, object.short_description),
+                Some(&player.slack_user_id),
+                Some(&to_char),
+            ).await?
+        }
+        None => state.slack_client.send_dm(&player.slack_user_id, &to_char).await?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_dark() {
+        let street = (RoomFlags::LAW, SectorType::City);
+        let field = (RoomFlags::empty(), SectorType::Field);
+        let cellar = (RoomFlags::DARK | RoomFlags::INDOORS, SectorType::Inside);
+
+        assert!(!is_dark(street.0, street.1, 23));
+        assert!(!is_dark(field.0, field.1, 12));
+        assert!(is_dark(field.0, field.1, 19));
+        assert!(is_dark(field.0, field.1, 4));
+        assert!(!is_dark(field.0, field.1, 5));
+        assert!(is_dark(cellar.0, cellar.1, 12));
+    }
+}
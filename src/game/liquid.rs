@@ -0,0 +1,115 @@
+use crate::models::{Object, ObjectInstance};
+
+/// One entry in the liquid table (ROM's liq_type)
+#[derive(Debug, PartialEq, Eq)]
+pub struct Liquid {
+    pub name: &'static str,
+    pub color: &'static str,
+    /// How drunk it makes you
+    pub proof: i32,
+    /// How filling it is
+    pub full: i32,
+    /// How much it quenches thirst (negative for salt water and the like)
+    pub thirst: i32,
+    /// How much it feeds you
+    pub food: i32,
+    /// How much is swallowed in one sip
+    pub sip: i32,
+}
+
+/// Every liquid drink containers and fountains can hold; the first is what unknown names fall back to
+pub static LIQUIDS: &[Liquid] = &[
+    Liquid { name: "water", color: "clear", proof: 0, full: 1, thirst: 10, food: 0, sip: 16 },
+    Liquid { name: "beer", color: "amber", proof: 12, full: 1, thirst: 8, food: 1, sip: 12 },
+    Liquid { name: "red wine", color: "burgundy", proof: 30, full: 1, thirst: 8, food: 1, sip: 5 },
+    Liquid { name: "ale", color: "brown", proof: 15, full: 1, thirst: 8, food: 1, sip: 12 },
+    Liquid { name: "dark ale", color: "dark", proof: 16, full: 1, thirst: 8, food: 1, sip: 12 },
+    Liquid { name: "whisky", color: "golden", proof: 120, full: 1, thirst: 5, food: 0, sip: 2 },
+    Liquid { name: "lemonade", color: "pink", proof: 0, full: 1, thirst: 9, food: 2, sip: 12 },
+    Liquid { name: "firebreather", color: "boiling", proof: 190, full: 0, thirst: 4, food: 0, sip: 2 },
+    Liquid { name: "local specialty", color: "clear", proof: 151, full: 1, thirst: 3, food: 0, sip: 2 },
+    Liquid { name: "slime mold juice", color: "green", proof: 0, full: 2, thirst: -8, food: 1, sip: 2 },
+    Liquid { name: "milk", color: "white", proof: 0, full: 2, thirst: 9, food: 3, sip: 12 },
+    Liquid { name: "tea", color: "tan", proof: 0, full: 1, thirst: 8, food: 0, sip: 6 },
+    Liquid { name: "coffee", color: "black", proof: 0, full: 1, thirst: 8, food: 0, sip: 6 },
+    Liquid { name: "blood", color: "red", proof: 0, full: 2, thirst: -1, food: 2, sip: 6 },
+    Liquid { name: "salt water", color: "clear", proof: 0, full: 1, thirst: -2, food: 0, sip: 1 },
+    Liquid { name: "coke", color: "brown", proof: 0, full: 2, thirst: 9, food: 2, sip: 12 },
+    Liquid { name: "root beer", color: "brown", proof: 0, full: 2, thirst: 9, food: 2, sip: 12 },
+    Liquid { name: "elvish wine", color: "green", proof: 35, full: 2, thirst: 8, food: 1, sip: 5 },
+    Liquid { name: "white wine", color: "golden", proof: 28, full: 1, thirst: 8, food: 1, sip: 5 },
+    Liquid { name: "champagne", color: "golden", proof: 32, full: 1, thirst: 8, food: 1, sip: 5 },
+    Liquid { name: "mead", color: "honey-colored", proof: 34, full: 2, thirst: 8, food: 2, sip: 12 },
+];
+
+/// Find a liquid by name, falling back to water as ROM does for ones it doesn't know
+pub fn lookup(name: &str) -> &'static Liquid {
+    LIQUIDS
+        .iter()
+        .find(|liquid| liquid.name.eq_ignore_ascii_case(name.trim()))
+        .unwrap_or(&LIQUIDS[0])
+}
+
+/// What a drink container or fountain holds: its own once filled, otherwise its template's value2
+pub fn liquid_in(object: &Object, instance: &ObjectInstance) -> &'static Liquid {
+    lookup(instance.liquid.as_deref().unwrap_or(&object.value2))
+}
+
+/// Drinks left in a drink container (its value1 until some are drunk); fountains never run dry
+pub fn drinks_left(object: &Object, instance: &ObjectInstance) -> i32 {
+    instance.charges.unwrap_or(object.value1)
+}
+
+/// Whether an object holds something drinkable poisoned (ROM's value3)
+pub fn is_poisoned(object: &Object) -> bool {
+    object.value3 != 0
+}
+
+/// How a drink of `amount` changes drunkenness, fullness, thirst and hunger (ROM's do_drink)
+pub fn effects(liquid: &Liquid, amount: i32) -> (i32, i32, i32, i32) {
+    (amount * liquid.proof / 36, amount * liquid.full / 4, amount * liquid.thirst / 10, amount * liquid.food / 2)
+}
+
+/// How much one drink swallows: three sips, but no more than is left (ROM's do_drink)
+pub fn swallow(liquid: &Liquid, left: Option<i32>) -> i32 {
+    let amount = liquid.sip * 3;
+    match left {
+        Some(left) => amount.min(left),
+        None => amount,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_and_swallow() {
+        assert_eq!(lookup("Local Specialty").color, "clear");
+        assert_eq!(lookup("grog").name, "water");
+
+        let beer = lookup("beer");
+        assert_eq!(swallow(beer, None), 36);
+        assert_eq!(swallow(beer, Some(16)), 16);
+        assert_eq!(effects(beer, 36), (12, 9, 28, 18));
+    }
+
+    #[test]
+    fn test_drink_container_state() {
+        let bottle = Object::new(
+            3002, "midgaard".to_string(), "bottle beer".to_string(),
+            "a bottle of beer".to_string(), "A beer bottle has been left here.".to_string(),
+            "glass".to_string(), "drink".to_string(), "0".to_string(), "A".to_string(),
+            16, 16, "beer".to_string(), 0, 0, 10, 5, 0, "P".to_string(),
+        );
+        let mut instance = ObjectInstance::new_in_player_inventory(bottle.vnum, "U1".to_string());
+        assert_eq!(drinks_left(&bottle, &instance), 16);
+        assert_eq!(liquid_in(&bottle, &instance).name, "beer");
+
+        instance.charges = Some(0);
+        instance.liquid = Some("water".to_string());
+        assert_eq!(drinks_left(&bottle, &instance), 0);
+        assert_eq!(liquid_in(&bottle, &instance).color, "clear");
+        assert!(!is_poisoned(&bottle));
+    }
+}
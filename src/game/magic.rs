@@ -0,0 +1,260 @@
+use crate::AppState;
+use crate::db::affect::AffectRepository;
+use crate::db::mobile::MobileInstanceRepository;
+use crate::db::player::PlayerRepository;
+use crate::game::affect;
+use crate::game::combat::{self, Combatant, Fighter};
+use crate::game::liquid;
+use crate::game::skills::Skill;
+use crate::models::{Affect, Object, ObjectInstance};
+use rand::Rng;
+use std::sync::Arc;
+
+/// Base damage of the low-level attack spells by caster level (ROM's dam_each for magic missile)
+const MISSILE_DAMAGE: [i32; 51] = [
+    0, 3, 3, 4, 4, 5, 6, 6, 6, 6, 6, 7, 7, 7, 7, 7, 8, 8, 8, 8, 8, 9, 9, 9, 9, 9,
+    10, 10, 10, 10, 10, 11, 11, 11, 11, 11, 12, 12, 12, 12, 12, 13, 13, 13, 13, 13,
+    14, 14, 14, 14, 14,
+];
+
+/// Chance in percent that the victim shrugs off half a spell (ROM's saves_spell)
+fn save_chance(caster_level: i32, victim_level: i32, saving_throw: i32) -> i32 {
+    (50 + (victim_level - caster_level) * 5 - saving_throw * 2).clamp(5, 95)
+}
+
+fn saves_spell(caster_level: i32, victim: &Fighter) -> bool {
+    rand::thread_rng().gen_range(1..=100) < save_chance(caster_level, victim.level, victim.saving_throw)
+}
+
+/// Tell a player something; mobiles don't need telling
+async fn tell(state: &Arc<AppState>, who: &Combatant, message: &str) -> anyhow::Result<()> {
+    if let Some(id) = who.slack_user_id() {
+        state.slack_client.send_dm(id, message).await?;
+    }
+    Ok(())
+}
+
+/// Whether a spell is already working on someone
+async fn is_affected(state: &Arc<AppState>, who: &Combatant, skill: &str) -> anyhow::Result<bool> {
+    Ok(affect::affects_on(state, who).await?.iter().any(|affect| affect.skill == skill))
+}
+
+async fn add_affect(state: &Arc<AppState>, affect: Affect) -> anyhow::Result<()> {
+    AffectRepository::new(state.db_pool.clone()).create(&affect).await?;
+    Ok(())
+}
+
+/// Charges left in a wand or staff: its value2 until some are used
+pub fn charges_left(object: &Object, instance: &ObjectInstance) -> i32 {
+    instance.charges.unwrap_or_else(|| object.value2.trim().parse().unwrap_or(0))
+}
+
+/// Work a spell at `level` on a character; the caster has already paid for it
+pub async fn cast(
+    state: &Arc<AppState>,
+    skill: &Skill,
+    caster: &Fighter,
+    level: i32,
+    victim: &Fighter,
+) -> anyhow::Result<()> {
+    match skill.name {
+        "armor" => spell_armor(state, caster, level, victim).await,
+        "bless" => spell_bless(state, caster, level, victim).await,
+        "cure light" => spell_cure_light(state, caster, level, victim).await,
+        "detect invis" => spell_detect_invis(state, caster, level, victim).await,
+        "magic missile" => spell_magic_missile(state, caster, level, victim).await,
+        "poison" => spell_poison(state, caster, level, victim).await,
+        _ => {
+            tracing::warn!("Spell {} has no effect written for it", skill.name);
+            Ok(())
+        }
+    }
+}
+
+/// Work a spell on an object the caster is carrying
+pub async fn cast_on_object(state: &Arc<AppState>, skill: &Skill, caster: &Fighter, object: &Object) -> anyhow::Result<()> {
+    match skill.name {
+        "detect poison" => spell_detect_poison(state, caster, object).await,
+        _ => {
+            tracing::warn!("Spell {} has no effect written for it", skill.name);
+            Ok(())
+        }
+    }
+}
+
+/// Work a spell at `level` on the caster's room
+pub async fn cast_on_room(state: &Arc<AppState>, skill: &Skill, caster: &Fighter, level: i32) -> anyhow::Result<()> {
+    match skill.name {
+        "earthquake" => spell_earthquake(state, caster, level).await,
+        _ => {
+            tracing::warn!("Spell {} has no effect written for it", skill.name);
+            Ok(())
+        }
+    }
+}
+
+async fn spell_armor(state: &Arc<AppState>, caster: &Fighter, level: i32, victim: &Fighter) -> anyhow::Result<()> {
+    if is_affected(state, &victim.who, "armor").await? {
+        let message = if caster.who == victim.who {
+            "You are already armored.".to_string()
+        } else {
+            format!("{} is already armored.", victim.name)
+        };
+        return tell(state, &caster.who, &message).await;
+    }
+
+    let (target_type, target_id) = victim.who.affect_target();
+    add_affect(state, Affect::new(target_type, target_id, "armor", level, 24).modifying("ac", -20)).await?;
+    tell(state, &victim.who, "You feel someone protecting you.").await?;
+    if caster.who != victim.who {
+        tell(state, &caster.who, &format!("{} is protected by your magic.", victim.name)).await?;
+    }
+    Ok(())
+}
+
+async fn spell_bless(state: &Arc<AppState>, caster: &Fighter, level: i32, victim: &Fighter) -> anyhow::Result<()> {
+    if is_affected(state, &victim.who, "bless").await? {
+        let message = if caster.who == victim.who {
+            "You are already blessed.".to_string()
+        } else {
+            format!("{} already has divine favor.", victim.name)
+        };
+        return tell(state, &caster.who, &message).await;
+    }
+
+    let (target_type, target_id) = victim.who.affect_target();
+    let duration = 6 + level;
+    add_affect(
+        state,
+        Affect::new(target_type, target_id.clone(), "bless", level, duration).modifying("hitroll", level / 8),
+    ).await?;
+    add_affect(
+        state,
+        Affect::new(target_type, target_id, "bless", level, duration).modifying("saving_spell", -level / 8),
+    ).await?;
+    tell(state, &victim.who, "You feel righteous.").await?;
+    if caster.who != victim.who {
+        tell(state, &caster.who, &format!("You grant {} the favor of your god.", victim.short)).await?;
+    }
+    Ok(())
+}
+
+async fn spell_cure_light(state: &Arc<AppState>, caster: &Fighter, level: i32, victim: &Fighter) -> anyhow::Result<()> {
+    let heal = rand::thread_rng().gen_range(1..=8) + level / 3;
+    match &victim.who {
+        Combatant::Player(id) => { PlayerRepository::new(state.db_pool.clone()).heal(id, heal).await?; }
+        Combatant::Mobile(id) => { MobileInstanceRepository::new(state.db_pool.clone()).heal(*id, heal).await?; }
+    }
+
+    tell(state, &victim.who, "You feel better!").await?;
+    if caster.who != victim.who {
+        tell(state, &caster.who, "Ok.").await?;
+    }
+    Ok(())
+}
+
+async fn spell_detect_invis(state: &Arc<AppState>, caster: &Fighter, level: i32, victim: &Fighter) -> anyhow::Result<()> {
+    if is_affected(state, &victim.who, "detect invis").await? {
+        let message = if caster.who == victim.who {
+            "You can already see invisible.".to_string()
+        } else {
+            format!("{} can already see invisible things.", victim.name)
+        };
+        return tell(state, &caster.who, &message).await;
+    }
+
+    let (target_type, target_id) = victim.who.affect_target();
+    // ROM's AFF_DETECT_INVIS is flag 'D'
+    add_affect(state, Affect::new(target_type, target_id, "detect invis", level, level).granting("D")).await?;
+    tell(state, &victim.who, "Your eyes tingle.").await?;
+    if caster.who != victim.who {
+        tell(state, &caster.who, "Ok.").await?;
+    }
+    Ok(())
+}
+
+async fn spell_detect_poison(state: &Arc<AppState>, caster: &Fighter, object: &Object) -> anyhow::Result<()> {
+    let message = match object.item_type.as_str() {
+        "drink" | "food" if liquid::is_poisoned(object) => "You smell poisonous fumes.",
+        "drink" | "food" => "It looks delicious.",
+        _ => "It doesn't look poisoned.",
+    };
+    tell(state, &caster.who, message).await
+}
+
+/// Shakes every mobile in the room; players can't hurt each other, so they only feel it
+async fn spell_earthquake(state: &Arc<AppState>, caster: &Fighter, level: i32) -> anyhow::Result<()> {
+    crate::handlers::broadcast_room_action(
+        state,
+        &caster.room_id,
+        &format!("_{} makes the earth tremble and shiver._", caster.name),
+        caster.who.slack_user_id(),
+        Some("_The earth trembles beneath your feet!_"),
+    ).await?;
+    if combat::is_safe_room(state, &caster.room_id).await? {
+        return Ok(());
+    }
+
+    for instance in MobileInstanceRepository::new(state.db_pool.clone()).get_in_room(&caster.room_id).await? {
+        let who = Combatant::Mobile(instance.id);
+        if who == caster.who {
+            continue;
+        }
+        let Some(victim) = Fighter::load(state, &who).await? else { continue };
+        let damage = {
+            let mut rng = rand::thread_rng();
+            level + rng.gen_range(1..=8) + rng.gen_range(1..=8)
+        };
+        combat::spell_damage(state, caster, &victim, damage, "earthquake").await?;
+    }
+    Ok(())
+}
+
+async fn spell_magic_missile(state: &Arc<AppState>, caster: &Fighter, level: i32, victim: &Fighter) -> anyhow::Result<()> {
+    let base = MISSILE_DAMAGE[level.clamp(0, MISSILE_DAMAGE.len() as i32 - 1) as usize];
+    let mut damage = rand::thread_rng().gen_range(base / 2..=base * 2);
+    if saves_spell(level, victim) {
+        damage /= 2;
+    }
+    combat::spell_damage(state, caster, victim, damage, "magic missile").await?;
+    Ok(())
+}
+
+async fn spell_poison(state: &Arc<AppState>, caster: &Fighter, level: i32, victim: &Fighter) -> anyhow::Result<()> {
+    if saves_spell(level, victim) {
+        tell(state, &victim.who, "You feel momentarily ill, but it passes.").await?;
+        if caster.who != victim.who {
+            tell(state, &caster.who, &format!("{} turns slightly green, but it passes.", victim.name)).await?;
+        }
+        return Ok(());
+    }
+
+    poison(state, &victim.who, level, level).await?;
+    tell(state, &victim.who, "You feel very sick.").await?;
+    if caster.who != victim.who {
+        tell(state, &caster.who, &format!("{} looks very ill.", victim.name)).await?;
+    }
+    Ok(())
+}
+
+/// Poison someone for `duration` hours, as the spell or bad food and drink do
+pub async fn poison(state: &Arc<AppState>, who: &Combatant, level: i32, duration: i32) -> anyhow::Result<()> {
+    let (target_type, target_id) = who.affect_target();
+    // ROM's AFF_POISON is flag 'M'
+    add_affect(state, Affect::new(target_type, target_id, "poison", level, duration).modifying("str", -2).granting("M")).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_chance() {
+        assert_eq!(save_chance(10, 10, 0), 50);
+        assert_eq!(save_chance(10, 12, 0), 60);
+        // A blessing's negative saving throw makes saving easier
+        assert_eq!(save_chance(10, 10, -2), 54);
+        assert_eq!(save_chance(1, 50, 0), 95);
+        assert_eq!(save_chance(50, 1, 0), 5);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::AppState;
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::game::special;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+
+/// Run the special procedure of every mobile that has one (on each `Tick::Mobile`)
+///
+/// Like ROM, mobiles in areas nobody has visited since their last reset stay idle.
+pub async fn mobile_update(state: &Arc<AppState>) -> anyhow::Result<()> {
+    let mobile_repo = MobileRepository::new(state.db_pool.clone());
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+
+    let mut templates = HashMap::new();
+    for instance in mobile_instance_repo.get_with_spec_fun().await? {
+        let template = match templates.entry(instance.mobile_vnum) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(mobile_repo.get_by_vnum(instance.mobile_vnum).await?),
+        };
+        let Some(mobile) = template.as_ref() else { continue };
+        let Some(spec_fun) = mobile.spec_fun.as_deref().and_then(special::lookup) else { continue };
+
+        let area_empty = state.area_ages.lock().unwrap()
+            .get(&mobile.area_name)
+            .is_some_and(|age| age.empty);
+        if area_empty {
+            continue;
+        }
+
+        if let Err(e) = spec_fun(state, &instance, mobile).await {
+            tracing::warn!("Special procedure for mobile {} failed: {}", instance.id, e);
+        }
+    }
+
+    Ok(())
+}
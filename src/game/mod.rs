@@ -0,0 +1,18 @@
+pub mod affect;
+pub mod carry;
+pub mod combat;
+pub mod condition;
+pub mod container;
+pub mod door;
+pub mod experience;
+pub mod light;
+pub mod liquid;
+pub mod magic;
+pub mod mobile;
+pub mod object;
+pub mod regen;
+pub mod reset;
+pub mod skills;
+pub mod special;
+pub mod tick;
+pub mod time;
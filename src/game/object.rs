@@ -0,0 +1,257 @@
+use crate::AppState;
+use crate::area::types::has_flag;
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::game::affect;
+use crate::game::combat::{Combatant, Fighter};
+use crate::models::{Object, ObjectInstance};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::sync::Arc;
+
+/// ROM's ITEM_ROT_DEATH: crumbles away soon after its owner dies
+const ROT_DEATH: char = 'P';
+/// ROM's ITEM_MELT_DROP: dissolves as soon as it's dropped
+const MELT_DROP: char = 'U';
+/// Chance in a hundred that a landed blow wears down the attacker's weapon, and again the victim's armor
+const WEAR_CHANCE: i32 = 10;
+
+/// How many ticks a freshly made object lasts before it decays, if it decays at all
+///
+/// Food goes off after a couple of days; corpses get their timers when they're made.
+pub fn spawn_timer(object: &Object) -> Option<i32> {
+    match object.item_type.as_str() {
+        "food" => Some(rand::thread_rng().gen_range(48..=96)),
+        _ => None,
+    }
+}
+
+/// A freshly made instance of an object, with its decay timer and the condition its area file gives it
+pub fn fresh(instance: ObjectInstance, object: &Object) -> ObjectInstance {
+    instance.with_timer(spawn_timer(object)).with_condition(object.base_condition())
+}
+
+/// How an object's condition reads in `look` and `equipment`
+pub fn condition_name(condition: i32) -> &'static str {
+    match condition {
+        100.. => "perfect",
+        90..=99 => "excellent",
+        75..=89 => "good",
+        50..=74 => "worn",
+        25..=49 => "damaged",
+        1..=24 => "nearly broken",
+        _ => "broken",
+    }
+}
+
+/// What a shopkeeper charges to mend an object back to perfect, given what they'd sell it for
+pub fn repair_cost(price: i32, condition: i32) -> i32 {
+    (price * (100 - condition.clamp(0, 100)) / 100).max(1)
+}
+
+/// Whether an object falls apart once whoever carried it has died
+pub fn rots_on_death(object: &Object) -> bool {
+    has_flag(&object.extra_flags, ROT_DEATH)
+}
+
+/// Whether an object dissolves into smoke when dropped
+pub fn melts_on_drop(object: &Object) -> bool {
+    has_flag(&object.extra_flags, MELT_DROP)
+}
+
+/// What everyone nearby sees when an object's time is up (ROM's obj_update)
+pub fn expire_message(object: &Object) -> String {
+    let name = object.display_name();
+    match object.item_type.as_str() {
+        "fountain" => format!("_{} dries up._", name),
+        "npc_corpse" | "pc_corpse" => format!("_{} decays into dust._", name),
+        "food" => format!("_{} decomposes._", name),
+        "potion" => format!("_{} has evaporated from disuse._", name),
+        "portal" => format!("_{} fades out of existence._", name),
+        _ => format!("_{} crumbles into dust._", name),
+    }
+}
+
+/// Start the countdown on everything in a fresh corpse that rots when its owner dies
+///
+/// As in ROM, they last five to ten ticks.
+pub async fn rot_contents(state: &Arc<AppState>, corpse_id: i32) -> anyhow::Result<()> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    for instance in object_instance_repo.get_in_container(corpse_id).await? {
+        let Some(object) = object_repo.get_for_instance(&instance).await? else { continue };
+        if rots_on_death(&object) {
+            let timer = rand::thread_rng().gen_range(5..=10);
+            object_instance_repo.update_timer(instance.id, Some(timer)).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Maybe wear down the attacker's weapon and a piece of the victim's armor after a blow lands
+pub async fn wear_from_blow(state: &Arc<AppState>, attacker: &Fighter, victim: &Fighter) -> anyhow::Result<()> {
+    let (weapon, armor) = {
+        let mut rng = rand::thread_rng();
+        (rng.gen_range(0..100) < WEAR_CHANCE, rng.gen_range(0..100) < WEAR_CHANCE)
+    };
+    if weapon {
+        wear_gear(state, attacker, "weapon").await?;
+    }
+    if armor {
+        wear_gear(state, victim, "armor").await?;
+    }
+    Ok(())
+}
+
+/// Knock a few points off one piece of gear of the given type someone has equipped, breaking it at 0
+async fn wear_gear(state: &Arc<AppState>, owner: &Fighter, item_type: &str) -> anyhow::Result<()> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let worn = match &owner.who {
+        Combatant::Player(id) => object_instance_repo.get_equipped(id).await?,
+        Combatant::Mobile(id) => object_instance_repo.get_worn_by_mobile(*id).await?,
+    };
+    let mut candidates = Vec::new();
+    for instance in worn {
+        let Some(object) = object_repo.get_for_instance(&instance).await? else { continue };
+        if object.item_type == item_type && !instance.is_broken() {
+            candidates.push((instance, object));
+        }
+    }
+
+    let (chosen, amount) = {
+        let mut rng = rand::thread_rng();
+        (candidates.choose(&mut rng).cloned(), rng.gen_range(1..=5))
+    };
+    let Some((instance, object)) = chosen else { return Ok(()) };
+
+    let condition = (instance.current_condition - amount).max(0);
+    object_instance_repo.update_condition(instance.id, condition).await?;
+    if condition > 0 {
+        return Ok(());
+    }
+
+    // Broken gear is no use to anyone and falls out of its slot
+    let object = instance.restring(object);
+    match &owner.who {
+        Combatant::Player(id) => {
+            object_instance_repo.unequip_item(instance.id, id).await?;
+            affect::refresh_gear_points(state, id).await?;
+        }
+        Combatant::Mobile(id) => object_instance_repo.unequip_from_mobile(instance.id, *id).await?,
+    }
+    crate::handlers::broadcast_room_action(
+        state,
+        &owner.room_id,
+        &format!("_{} breaks and falls away from {}!_", object.display_name(), owner.short),
+        owner.who.slack_user_id(),
+        Some(&format!("_{} breaks and falls away from you!_", object.display_name())),
+    ).await?;
+
+    Ok(())
+}
+
+/// Count down object timers and remove objects whose time is up (on each `Tick::Point`)
+pub async fn object_update(state: &Arc<AppState>) -> anyhow::Result<()> {
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    for instance in object_instance_repo.decrement_timers().await? {
+        if let Err(e) = expire(state, &instance).await {
+            tracing::warn!("Failed to expire object instance {}: {}", instance.id, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove an expired object, telling whoever can see it
+///
+/// Whatever it held spills out to wherever it was: the room it lay in, the
+/// inventory of whoever carried it, or the container it was in.
+async fn expire(state: &Arc<AppState>, instance: &ObjectInstance) -> anyhow::Result<()> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let Some(object) = object_repo.get_for_instance(instance).await? else {
+        object_instance_repo.delete_with_contents(instance.id).await?;
+        return Ok(());
+    };
+    let object = instance.restring(object);
+    let message = expire_message(&object);
+
+    // Where its contents end up, if anywhere
+    let spill_to = match instance.location_type.as_str() {
+        "room" => {
+            crate::handlers::broadcast_room_action(state, &instance.location_id, &message, None, None).await?;
+            Some(("room", instance.location_id.clone()))
+        }
+        "player" | "equipped" => {
+            state.slack_client.send_dm(&instance.location_id, &message).await?;
+            Some(("player", instance.location_id.clone()))
+        }
+        "mobile" | "mobile_equipped" => Some(("mobile", instance.location_id.clone())),
+        "container" => Some(("container", instance.location_id.clone())),
+        _ => None,
+    };
+
+    if let Some((location_type, location_id)) = spill_to {
+        for content in object_instance_repo.get_in_container(instance.id).await? {
+            object_instance_repo.update_location(content.id, location_type, &location_id).await?;
+        }
+    }
+
+    object_instance_repo.delete_with_contents(instance.id).await?;
+    if instance.location_type == "equipped" {
+        affect::refresh_gear_points(state, &instance.location_id).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(item_type: &str, extra_flags: &str) -> Object {
+        Object::new(
+            3010, "midgaard".to_string(), "thing".to_string(),
+            "a thing".to_string(), "A thing is here.".to_string(),
+            "wood".to_string(), item_type.to_string(), extra_flags.to_string(), "A".to_string(),
+            0, 0, String::new(), 0, 0, 1, 10, 0, "P".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_spawn_timer() {
+        let timer = spawn_timer(&object("food", "")).unwrap();
+        assert!((48..=96).contains(&timer));
+        assert_eq!(spawn_timer(&object("weapon", "")), None);
+    }
+
+    #[test]
+    fn test_condition() {
+        let mut shield = object("armor", "");
+        shield.condition = "W".to_string();
+        let instance = fresh(ObjectInstance::new_in_room(shield.vnum, "vnum_3001".to_string()), &shield);
+        assert_eq!(instance.current_condition, 50);
+        assert_eq!(condition_name(instance.current_condition), "worn");
+        assert_eq!(condition_name(100), "perfect");
+        assert_eq!(condition_name(0), "broken");
+
+        assert_eq!(repair_cost(200, 50), 100);
+        assert_eq!(repair_cost(200, 100), 1);
+        assert_eq!(repair_cost(200, -5), 200);
+    }
+
+    #[test]
+    fn test_expire_message_and_flags() {
+        assert_eq!(expire_message(&object("food", "")), "_A thing decomposes._");
+        assert_eq!(expire_message(&object("fountain", "")), "_A thing dries up._");
+        assert_eq!(expire_message(&object("armor", "")), "_A thing crumbles into dust._");
+
+        assert!(rots_on_death(&object("armor", "GP")));
+        assert!(!melts_on_drop(&object("armor", "GP")));
+        assert!(melts_on_drop(&object("armor", "U")));
+    }
+}
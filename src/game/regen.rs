@@ -0,0 +1,170 @@
+use crate::AppState;
+use crate::db::class::ClassRepository;
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::db::player::PlayerRepository;
+use crate::db::room::RoomRepository;
+use crate::game::combat::{self, Combatant};
+use crate::game::condition::{self, Conditions};
+use crate::models::Position;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+
+/// Scale a gain by how restful a player's position is (ROM's hit_gain/mana_gain)
+fn player_position_gain(gain: i32, position: Position) -> i32 {
+    match position {
+        Position::Sleeping => gain,
+        Position::Resting => gain / 2,
+        Position::Fighting => gain / 6,
+        _ => gain / 4,
+    }
+}
+
+/// Hit points a player regains in one point tick, before the room's heal rate
+pub fn player_hit_gain(level: i32, position: Position) -> i32 {
+    player_position_gain((10 + level / 2).max(3), position)
+}
+
+/// Mana a player regains in one point tick, before the room's mana rate
+///
+/// Classes that aren't casters (see `Class::gains_mana`) recover mana at half speed.
+pub fn player_mana_gain(level: i32, gains_mana: bool, position: Position) -> i32 {
+    let mut gain = (26 + level) / 2;
+    if !gains_mana {
+        gain /= 2;
+    }
+    player_position_gain(gain, position)
+}
+
+/// Movement a player regains in one point tick, before the room's heal rate
+pub fn player_move_gain(level: i32, position: Position) -> i32 {
+    let gain = level.max(15);
+    match position {
+        Position::Sleeping => gain + 13,
+        Position::Resting => gain + 6,
+        _ => gain,
+    }
+}
+
+/// Hit points or mana a mobile regains in one point tick, before the room's rates
+pub fn mobile_gain(level: i32, position: Position) -> i32 {
+    let gain = 5 + level;
+    match position {
+        Position::Sleeping => 3 * gain / 2,
+        Position::Resting => gain,
+        Position::Fighting => gain / 3,
+        _ => gain / 2,
+    }
+}
+
+/// Scale a gain by a room rate, given as a percentage
+fn at_rate(gain: i32, rate: i32) -> i32 {
+    gain * rate / 100
+}
+
+/// Regenerate hit points, mana and movement for everyone, slower for the starving (on each `Tick::Point`)
+///
+/// Gains are added in the database rather than saving values worked out from the
+/// snapshot read here, so damage, spells and spending that land during the loop aren't undone.
+pub async fn point_update(state: &Arc<AppState>) -> anyhow::Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let class_repo = ClassRepository::new(state.db_pool.clone());
+    let mobile_repo = MobileRepository::new(state.db_pool.clone());
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+    let room_repo = RoomRepository::new(state.db_pool.clone());
+
+    // Heal and mana rates by room
+    let mut rates = HashMap::new();
+    let mut room_rates = async |room_id: &str| -> anyhow::Result<(i32, i32)> {
+        Ok(match rates.entry(room_id.to_string()) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => *entry.insert(
+                room_repo.get_by_channel_id(room_id).await?
+                    .map_or((100, 100), |room| (room.heal_rate, room.mana_rate))
+            ),
+        })
+    };
+
+    let mut casters = HashMap::new();
+    for player in player_repo.get_all_players().await? {
+        let Some(room_id) = player.current_channel_id.as_deref() else { continue };
+        if player.hit >= player.max_hit && player.mana >= player.max_mana && player.movement >= player.max_movement {
+            continue;
+        }
+
+        // Players without a class yet regain mana at full speed
+        let gains_mana = match player.class_id {
+            Some(id) => match casters.entry(id) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => *entry.insert(class_repo.get_by_id(id).await?.is_none_or(|c| c.gains_mana)),
+            },
+            None => true,
+        };
+
+        let position = combat::effective_position(
+            state,
+            &Combatant::Player(player.slack_user_id.clone()),
+            player.position(),
+        );
+        let (heal_rate, mana_rate) = room_rates(room_id).await?;
+
+        // Going hungry or thirsty slows healing
+        let conditions = Conditions::of(&player);
+        let penalty = |gain: i32| {
+            if condition::is_immune(player.level) { gain } else { condition::regen_penalty(gain, conditions) }
+        };
+
+        player_repo.regenerate(
+            &player.slack_user_id,
+            at_rate(penalty(player_hit_gain(player.level, position)), heal_rate),
+            at_rate(penalty(player_mana_gain(player.level, gains_mana, position)), mana_rate),
+            at_rate(penalty(player_move_gain(player.level, position)), heal_rate),
+        ).await?;
+    }
+
+    let mut levels = HashMap::new();
+    for instance in mobile_instance_repo.get_wounded().await? {
+        let level = match levels.entry(instance.mobile_vnum) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => *entry.insert(
+                mobile_repo.get_by_vnum(instance.mobile_vnum).await?.map_or(0, |m| m.level)
+            ),
+        };
+
+        let position = combat::effective_position(state, &Combatant::Mobile(instance.id), instance.position());
+        let gain = mobile_gain(level, position);
+        let (heal_rate, mana_rate) = room_rates(&instance.room_id).await?;
+
+        mobile_instance_repo.regenerate(
+            instance.id,
+            at_rate(gain, heal_rate),
+            at_rate(gain, mana_rate),
+            at_rate(level, heal_rate),
+        ).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resting_heals_faster() {
+        let standing = player_hit_gain(10, Position::Standing);
+        let resting = player_hit_gain(10, Position::Resting);
+        let sleeping = player_hit_gain(10, Position::Sleeping);
+        assert!(standing < resting && resting < sleeping);
+        assert!(player_hit_gain(10, Position::Fighting) < standing);
+        assert!(player_mana_gain(10, false, Position::Sleeping) < player_mana_gain(10, true, Position::Sleeping));
+        assert!(mobile_gain(10, Position::Sleeping) > mobile_gain(10, Position::Standing));
+    }
+
+    #[test]
+    fn test_gain_scales_with_room_rate() {
+        assert_eq!(at_rate(20, 100), 20);
+        assert_eq!(at_rate(20, 150), 30);
+        assert_eq!(at_rate(20, 0), 0);
+    }
+}
@@ -0,0 +1,308 @@
+use crate::AppState;
+use crate::area::types::{Direction, Reset};
+use crate::db::area::{AreaRepository, AreaResetRepository};
+use crate::db::exit::ExitRepository;
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::db::player::PlayerRepository;
+use crate::db::shop::ShopRepository;
+use crate::game::object;
+use crate::models::{Area, EquipmentSlot, MobileInstance, ObjectInstance};
+use rand::Rng;
+use sqlx::PgPool;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// ROM-style area age used to decide when an area repops
+#[derive(Debug, Clone, Copy)]
+pub struct AreaAge {
+    pub age: i32,
+    /// True once the area has reset with nobody in it, cleared when a player is present
+    pub empty: bool,
+}
+
+impl Default for AreaAge {
+    fn default() -> Self {
+        // Like ROM at boot: old enough to reset on the first pulse
+        Self { age: 15, empty: false }
+    }
+}
+
+impl AreaAge {
+    /// Advance the age by one area pulse, returning true if the area should reset now
+    ///
+    /// An area someone has visited resets quickly once they leave, an occupied
+    /// area resets every ~15 pulses, and an untouched empty area every ~31.
+    pub fn advance(&mut self, players_present: bool) -> bool {
+        if players_present {
+            self.empty = false;
+        }
+
+        self.age += 1;
+        if self.age < 3 {
+            return false;
+        }
+
+        if (!self.empty && (!players_present || self.age >= 15)) || self.age >= 31 {
+            self.age = rand::thread_rng().gen_range(0..=3);
+            self.empty = !players_present;
+            return true;
+        }
+
+        false
+    }
+}
+
+/// Counts of what a reset pass spawned
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ResetSummary {
+    pub objects_spawned: i32,
+    pub mobiles_spawned: i32,
+}
+
+/// Apply an area's resets, spawning objects and mobiles into its rooms
+///
+/// Follows ROM semantics: `M` resets honour the world-wide `limit` and the
+/// per-room `max_in_room`, and `G`/`E` resets only apply to the mobile
+/// spawned by the most recent `M` reset (if it actually spawned). Items given
+/// to a shopkeeper become its permanent shop stock. Object
+/// resets honour `limit` against existing instances and never stack a
+/// second copy in the same room. A negative limit means unlimited. `D` resets
+/// put doors back to open (0), closed (1) or closed and locked (2). `P` resets
+/// put an object in the container the preceding `O`, `G` or `E` reset placed
+/// (or, failing that, the first one lying in a room of the area) if it lacks
+/// one, and close and lock that container again as its template says.
+pub async fn apply_resets(
+    pool: &PgPool,
+    resets: &[Reset],
+    min_vnum: i32,
+    max_vnum: i32,
+) -> Result<ResetSummary, sqlx::Error> {
+    let mobile_repo = MobileRepository::new(pool.clone());
+    let mobile_instance_repo = MobileInstanceRepository::new(pool.clone());
+    let object_repo = ObjectRepository::new(pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(pool.clone());
+    let shop_repo = ShopRepository::new(pool.clone());
+    let exit_repo = ExitRepository::new(pool.clone());
+
+    let in_area = |vnum: i32| vnum >= min_vnum && vnum <= max_vnum;
+    let at_limit = |limit: i32, count: i64| limit >= 0 && count >= i64::from(limit);
+
+    let mut summary = ResetSummary::default();
+    // The mobile spawned by the last M reset, if any, and whether it keeps a shop
+    let mut last_mobile: Option<(i32, bool)> = None;
+    // The object instance and vnum the last O, G or E reset placed, for P resets to fill
+    let mut last_object: Option<(i32, i32)> = None;
+
+    for reset in resets {
+        match reset {
+            Reset::ObjectInRoom { obj_vnum, limit, room_vnum, .. } => {
+                // Skip if room doesn't exist (outside area range)
+                if !in_area(*room_vnum) {
+                    continue;
+                }
+                last_object = None;
+                let Some(template) = object_repo.get_by_vnum(*obj_vnum).await? else { continue };
+
+                // A copy already lying here is the one any following P resets fill
+                let room_id = format!("vnum_{}", room_vnum);
+                if let Some(existing) = object_instance_repo.get_in_room(&room_id).await?
+                    .into_iter()
+                    .find(|instance| instance.object_vnum == *obj_vnum)
+                {
+                    last_object = Some((existing.id, *obj_vnum));
+                    continue;
+                }
+                if at_limit(*limit, object_instance_repo.count_by_vnum(*obj_vnum).await?) {
+                    continue;
+                }
+
+                let instance = object::fresh(ObjectInstance::new_in_room(*obj_vnum, room_id), &template);
+                last_object = Some((object_instance_repo.create(&instance).await?, *obj_vnum));
+                summary.objects_spawned += 1;
+            }
+            Reset::Mobile { mob_vnum, limit, room_vnum, max_in_room, .. } => {
+                last_mobile = None;
+
+                if !in_area(*room_vnum) {
+                    continue;
+                }
+                let Some(mobile) = mobile_repo.get_by_vnum(*mob_vnum).await? else { continue };
+
+                let room_id = format!("vnum_{}", room_vnum);
+
+                if at_limit(*limit, mobile_instance_repo.count_by_vnum(*mob_vnum).await?) {
+                    continue;
+                }
+                if mobile_instance_repo.count_in_room(*mob_vnum, &room_id).await? >= i64::from(*max_in_room) {
+                    continue;
+                }
+
+                let instance = MobileInstance::new_in_room(&mobile, room_id);
+                let mobile_id = mobile_instance_repo.create(&instance).await?;
+                let is_shopkeeper = shop_repo.get_by_keeper(*mob_vnum).await?.is_some();
+                last_mobile = Some((mobile_id, is_shopkeeper));
+                summary.mobiles_spawned += 1;
+            }
+            Reset::GiveObject { obj_vnum, limit, .. } => {
+                last_object = None;
+                let Some((mobile_id, is_shopkeeper)) = last_mobile else { continue };
+                let Some(template) = object_repo.get_by_vnum(*obj_vnum).await? else { continue };
+
+                // Shop stock is unlimited; anything else honours the limit
+                let instance = if is_shopkeeper {
+                    ObjectInstance::new_in_shop(*obj_vnum, mobile_id)
+                } else if at_limit(*limit, object_instance_repo.count_by_vnum(*obj_vnum).await?) {
+                    continue;
+                } else {
+                    ObjectInstance::new_on_mobile(*obj_vnum, mobile_id)
+                };
+                last_object = Some((object_instance_repo.create(&object::fresh(instance, &template)).await?, *obj_vnum));
+                summary.objects_spawned += 1;
+            }
+            Reset::EquipObject { obj_vnum, limit, wear_location, .. } => {
+                last_object = None;
+                let Some((mobile_id, _)) = last_mobile else { continue };
+                let Some(template) = object_repo.get_by_vnum(*obj_vnum).await? else { continue };
+                if at_limit(*limit, object_instance_repo.count_by_vnum(*obj_vnum).await?) {
+                    continue;
+                }
+
+                // Unknown wear locations just go into the mobile's inventory
+                let instance = match EquipmentSlot::from_wear_location(*wear_location) {
+                    Some(slot) => ObjectInstance::new_equipped_on_mobile(*obj_vnum, mobile_id, &slot),
+                    None => ObjectInstance::new_on_mobile(*obj_vnum, mobile_id),
+                };
+                last_object = Some((object_instance_repo.create(&object::fresh(instance, &template)).await?, *obj_vnum));
+                summary.objects_spawned += 1;
+            }
+            Reset::Door { room_vnum, direction, state } => {
+                let Some(direction) = Direction::from_code(*direction) else { continue };
+                let room_id = format!("vnum_{}", room_vnum);
+                let Some(exit) = exit_repo.get_exit_in_direction(&room_id, direction.as_str()).await? else { continue };
+                if exit.is_door() {
+                    exit_repo.set_door_state(&exit, *state >= 1, *state >= 2).await?;
+                }
+            }
+            Reset::PutInContainer { obj_vnum, limit, container_vnum, .. } => {
+                let Some(template) = object_repo.get_by_vnum(*obj_vnum).await? else { continue };
+                let container_id = match last_object {
+                    Some((id, vnum)) if vnum == *container_vnum => id,
+                    _ => {
+                        let in_area_room = |instance: &ObjectInstance| {
+                            instance.location_id
+                                .strip_prefix("vnum_")
+                                .and_then(|vnum| vnum.parse().ok())
+                                .is_some_and(in_area)
+                        };
+                        let lying = object_instance_repo.get_lying_by_vnum(*container_vnum).await?;
+                        let Some(container) = lying.into_iter().find(in_area_room) else { continue };
+                        container.id
+                    }
+                };
+
+                // Whatever's been done to the container, it's shut and locked again as the area file has it
+                object_instance_repo.update_container_flags(container_id, None).await?;
+
+                let contents = object_instance_repo.get_in_container(container_id).await?;
+                if contents.iter().any(|content| content.object_vnum == *obj_vnum)
+                    || at_limit(*limit, object_instance_repo.count_by_vnum(*obj_vnum).await?)
+                {
+                    continue;
+                }
+
+                let instance = object::fresh(ObjectInstance::new_in_container(*obj_vnum, container_id), &template);
+                object_instance_repo.create(&instance).await?;
+                summary.objects_spawned += 1;
+            }
+            _ => {
+                // Skip other reset types for now (random exits)
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Advance every area's age by one pulse and reset the ones that are due (on each `Tick::Area`)
+pub async fn area_update(state: &Arc<AppState>) -> anyhow::Result<()> {
+    let area_repo = AreaRepository::new(state.db_pool.clone());
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+
+    for area in area_repo.get_all().await? {
+        let players = player_repo.get_players_in_vnum_range(area.min_vnum, area.max_vnum).await?;
+
+        let due = {
+            let mut ages = state.area_ages.lock().unwrap();
+            ages.entry(area.name.clone()).or_default().advance(!players.is_empty())
+        };
+
+        if due {
+            let rooms: BTreeSet<String> = players.into_iter()
+                .filter_map(|p| p.current_channel_id)
+                .collect();
+            reset_area(state, &area, &rooms).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Re-apply an area's stored resets and announce it to the occupied rooms
+async fn reset_area(state: &Arc<AppState>, area: &Area, occupied_rooms: &BTreeSet<String>) -> anyhow::Result<()> {
+    let reset_repo = AreaResetRepository::new(state.db_pool.clone());
+    let resets = reset_repo.get_by_area(&area.name).await?;
+
+    let summary = apply_resets(&state.db_pool, &resets, area.min_vnum, area.max_vnum).await?;
+    tracing::debug!(
+        "Reset area '{}': {} objects, {} mobiles spawned",
+        area.name,
+        summary.objects_spawned,
+        summary.mobiles_spawned
+    );
+
+    let message = format!("_{}_", area.reset_message);
+    for room_id in occupied_rooms {
+        crate::handlers::broadcast_room_action(state, room_id, &message, None, None).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_area_age_resets_on_first_pulse_after_boot() {
+        let mut age = AreaAge::default();
+        assert!(age.advance(false));
+        assert!(age.empty);
+        assert!((0..=3).contains(&age.age));
+    }
+
+    #[test]
+    fn test_area_age_empty_area_waits_for_old_age() {
+        let mut age = AreaAge { age: 0, empty: true };
+        let pulses = (1..=40).find(|_| age.advance(false)).unwrap();
+        assert_eq!(pulses, 31);
+    }
+
+    #[test]
+    fn test_area_age_resets_quickly_after_players_leave() {
+        let mut age = AreaAge { age: 0, empty: true };
+        assert!(!age.advance(true));
+        assert!(!age.empty);
+        assert!(!age.advance(true));
+        // Players have left: reset as soon as the minimum age is reached
+        assert!(age.advance(false));
+    }
+
+    #[test]
+    fn test_area_age_occupied_area_resets_at_fifteen() {
+        let mut age = AreaAge { age: 0, empty: false };
+        let pulses = (1..=40).find(|_| age.advance(true)).unwrap();
+        assert_eq!(pulses, 15);
+        assert!(!age.empty);
+    }
+}
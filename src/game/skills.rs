@@ -0,0 +1,174 @@
+use crate::models::Position;
+
+/// Highest percentage a skill can be practiced to at a guildmaster
+pub const ADEPT: i32 = 75;
+
+/// What a spell can be cast on (ROM's TAR_* values)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// The room, or nothing in particular
+    Room,
+    /// Someone to attack; defaults to whoever the caster is fighting
+    CharOffensive,
+    /// Someone to help; defaults to the caster
+    CharDefensive,
+    /// Only ever the caster
+    CharSelf,
+    /// An object the caster is carrying
+    ObjectInventory,
+}
+
+/// One entry in the skill table (ROM's skill_type)
+#[derive(Debug)]
+pub struct Skill {
+    pub name: &'static str,
+    /// Level each class can learn it at; classes not listed never can
+    pub levels: &'static [(&'static str, i32)],
+    pub target: Target,
+    /// Lowest position it can be used from
+    pub min_position: Position,
+    /// Mana cost at the highest levels; it costs more when first learned
+    pub min_mana: i32,
+    /// What the target is told when its affect wears off, for spells that leave one
+    pub wear_off: Option<&'static str>,
+    /// False for skills used through their own command rather than cast
+    pub spell: bool,
+}
+
+/// Every skill and spell in the game
+pub static SKILLS: &[Skill] = &[
+    Skill {
+        name: "armor",
+        levels: &[("Mage", 7), ("Cleric", 1), ("Rogue", 13), ("Warrior", 15)],
+        target: Target::CharDefensive,
+        min_position: Position::Standing,
+        min_mana: 5,
+        wear_off: Some("You feel less armored."),
+        spell: true,
+    },
+    Skill {
+        name: "bless",
+        levels: &[("Mage", 7), ("Cleric", 7)],
+        target: Target::CharDefensive,
+        min_position: Position::Standing,
+        min_mana: 5,
+        wear_off: Some("You feel less righteous."),
+        spell: true,
+    },
+    Skill {
+        name: "cure light",
+        levels: &[("Mage", 10), ("Cleric", 1), ("Rogue", 15), ("Warrior", 15)],
+        target: Target::CharDefensive,
+        min_position: Position::Fighting,
+        min_mana: 10,
+        wear_off: None,
+        spell: true,
+    },
+    Skill {
+        name: "detect invis",
+        levels: &[("Mage", 3), ("Cleric", 8), ("Rogue", 6)],
+        target: Target::CharSelf,
+        min_position: Position::Standing,
+        min_mana: 5,
+        wear_off: Some("You no longer see invisible objects."),
+        spell: true,
+    },
+    Skill {
+        name: "detect poison",
+        levels: &[("Mage", 15), ("Cleric", 7), ("Rogue", 9), ("Warrior", 12)],
+        target: Target::ObjectInventory,
+        min_position: Position::Standing,
+        min_mana: 5,
+        wear_off: None,
+        spell: true,
+    },
+    Skill {
+        name: "earthquake",
+        levels: &[("Cleric", 10), ("Warrior", 14)],
+        target: Target::Room,
+        min_position: Position::Fighting,
+        min_mana: 15,
+        wear_off: None,
+        spell: true,
+    },
+    Skill {
+        name: "magic missile",
+        levels: &[("Mage", 1), ("Rogue", 2), ("Warrior", 2)],
+        target: Target::CharOffensive,
+        min_position: Position::Fighting,
+        min_mana: 15,
+        wear_off: None,
+        spell: true,
+    },
+    Skill {
+        name: "poison",
+        levels: &[("Mage", 17), ("Cleric", 12), ("Rogue", 15), ("Warrior", 21)],
+        target: Target::CharOffensive,
+        min_position: Position::Fighting,
+        min_mana: 10,
+        wear_off: Some("You feel less sick."),
+        spell: true,
+    },
+    Skill {
+        name: "pick lock",
+        levels: &[("Mage", 25), ("Cleric", 25), ("Rogue", 7), ("Warrior", 25)],
+        target: Target::Room,
+        min_position: Position::Standing,
+        min_mana: 0,
+        wear_off: None,
+        spell: false,
+    },
+];
+
+impl Skill {
+    /// The level a class can learn this at, if it can at all
+    pub fn level_for(&self, class_name: &str) -> Option<i32> {
+        self.levels
+            .iter()
+            .find(|(class, _)| class.eq_ignore_ascii_case(class_name))
+            .map(|(_, level)| *level)
+    }
+
+    /// Mana to cast this at a caster's level: steep when just learned, down to `min_mana` (ROM's formula)
+    pub fn mana_cost(&self, caster_level: i32, skill_level: i32) -> i32 {
+        self.min_mana.max(100 / (2 + caster_level - skill_level).max(1))
+    }
+}
+
+/// Find a skill by exact name
+pub fn lookup(name: &str) -> Option<&'static Skill> {
+    SKILLS.iter().find(|skill| skill.name == name)
+}
+
+/// Find a skill by (the start of) its name, preferring ones `known` returns true for
+pub fn find(name: &str, known: impl Fn(&Skill) -> bool) -> Option<&'static Skill> {
+    let name = name.to_lowercase();
+    let mut matching = SKILLS.iter().filter(|skill| skill.name.starts_with(&name));
+    let first = matching.clone().next();
+    matching.find(|skill| known(skill)).or(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_by_prefix_and_class_level() {
+        let missile = find("magic", |_| true).unwrap();
+        assert_eq!(missile.name, "magic missile");
+        assert_eq!(missile.level_for("mage"), Some(1));
+        assert_eq!(missile.level_for("Cleric"), None);
+        assert!(find("fireball", |_| true).is_none());
+        assert_eq!(lookup("bless").unwrap().target, Target::CharDefensive);
+        assert_eq!(find("detect p", |_| true).unwrap().target, Target::ObjectInventory);
+        assert_eq!(lookup("earthquake").unwrap().level_for("Mage"), None);
+    }
+
+    #[test]
+    fn test_mana_cost_falls_with_level() {
+        let missile = lookup("magic missile").unwrap();
+        assert_eq!(missile.mana_cost(1, 1), 50);
+        assert_eq!(missile.mana_cost(5, 1), 16);
+        assert_eq!(missile.mana_cost(30, 1), 15);
+    }
+}
@@ -0,0 +1,394 @@
+use crate::AppState;
+use crate::area::types::{has_flag, RoomFlags};
+use crate::db::exit::ExitRepository;
+use crate::game::combat::{self, Combatant, Fighter};
+use crate::game::door::{self, DoorAction, Lock};
+use crate::game::{magic, skills};
+use crate::game::skills::Target;
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::db::player::PlayerRepository;
+use crate::db::room::RoomRepository;
+use crate::models::{Mobile, MobileInstance, Player, Position};
+use once_cell::sync::Lazy;
+use rand::Rng;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Future returned by a special procedure; resolves to true if the mobile acted
+pub type SpecFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<bool>> + Send + 'a>>;
+
+/// A special procedure, run for a mobile on every mobile pulse (ROM's SPEC_FUN)
+pub type SpecFun = for<'a> fn(&'a Arc<AppState>, &'a MobileInstance, &'a Mobile) -> SpecFuture<'a>;
+
+/// Registry of implemented special procedures, keyed by their #SPECIALS name
+///
+/// Names from area files that aren't listed here are stored but ignored.
+pub static SPEC_FUNS: Lazy<HashMap<&'static str, SpecFun>> = Lazy::new(|| {
+    let mut specs: HashMap<&'static str, SpecFun> = HashMap::new();
+    specs.insert("spec_janitor", |s, i, m| Box::pin(spec_janitor(s, i, m)));
+    specs.insert("spec_fido", |s, i, m| Box::pin(spec_fido(s, i, m)));
+    specs.insert("spec_cast_adept", |s, i, m| Box::pin(spec_cast_adept(s, i, m)));
+    specs.insert("spec_thief", |s, i, m| Box::pin(spec_thief(s, i, m)));
+    specs.insert("spec_mayor", |s, i, m| Box::pin(spec_mayor(s, i, m)));
+    specs.insert("spec_guard", |s, i, m| Box::pin(spec_guard(s, i, m)));
+    specs.insert("spec_cast_cleric", |s, i, m| Box::pin(spec_cast_cleric(s, i, m)));
+    specs.insert("spec_cast_mage", |s, i, m| Box::pin(spec_cast_mage(s, i, m)));
+    specs
+});
+
+/// Look up a special procedure by name
+pub fn lookup(name: &str) -> Option<SpecFun> {
+    SPEC_FUNS.get(name.to_lowercase().as_str()).copied()
+}
+
+/// Tell everyone in a mobile's room what it just did
+async fn act(state: &Arc<AppState>, instance: &MobileInstance, message: &str) -> anyhow::Result<()> {
+    crate::handlers::broadcast_room_action(state, &instance.room_id, &format!("_{}_", message), None, None).await
+}
+
+/// Players in the mobile's room
+async fn players_here(state: &Arc<AppState>, instance: &MobileInstance) -> anyhow::Result<Vec<Player>> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    Ok(player_repo.get_players_in_room(&instance.room_id).await?)
+}
+
+/// Picks up litter: takeable trash, drink containers and anything nearly worthless
+async fn spec_janitor(state: &Arc<AppState>, instance: &MobileInstance, mobile: &Mobile) -> anyhow::Result<bool> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    for trash in object_instance_repo.get_in_room(&instance.room_id).await? {
+        let Some(object) = object_repo.get_by_vnum(trash.object_vnum).await? else { continue };
+
+        // ROM wear flag 'A' is ITEM_TAKE
+        if !has_flag(&object.wear_flags, 'A') {
+            continue;
+        }
+
+        if object.item_type == "drink" || object.item_type == "trash" || object.cost < 10 {
+            object_instance_repo.update_location(trash.id, "mobile", &instance.id.to_string()).await?;
+            act(state, instance, &format!("{} picks up some trash.", mobile.display_name())).await?;
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Devours NPC corpses, leaving their contents on the floor
+async fn spec_fido(state: &Arc<AppState>, instance: &MobileInstance, mobile: &Mobile) -> anyhow::Result<bool> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    for corpse in object_instance_repo.get_in_room(&instance.room_id).await? {
+        let Some(object) = object_repo.get_by_vnum(corpse.object_vnum).await? else { continue };
+        if object.item_type != "npc_corpse" {
+            continue;
+        }
+
+        act(state, instance, &format!("{} savagely devours a corpse.", mobile.display_name())).await?;
+        for content in object_instance_repo.get_in_container(corpse.id).await? {
+            object_instance_repo.update_location(content.id, "room", &instance.room_id).await?;
+        }
+        object_instance_repo.delete(corpse.id).await?;
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+/// Casts helpful spells on low-level players
+async fn spec_cast_adept(state: &Arc<AppState>, instance: &MobileInstance, mobile: &Mobile) -> anyhow::Result<bool> {
+    let players = players_here(state, instance).await?;
+    let (victim, spell) = {
+        let mut rng = rand::thread_rng();
+        let victim = players.into_iter().find(|p| p.level < 11 && rng.gen_bool(0.5));
+        (victim, rng.gen_range(0..16))
+    };
+    let Some(victim) = victim else { return Ok(false) };
+
+    let (words, cast, effect) = match spell {
+        0 => ("abrazak", Some("armor"), None),
+        1 => ("fido", Some("bless"), None),
+        2 => ("judicandus noselacri", None, None),
+        3 => ("judicandus dies", Some("cure light"), None),
+        4 => ("judicandus sausabru", None, None),
+        5 => ("candusima", None, Some("You feel less tired.")),
+        6 => ("judicandus eugzagz", None, None),
+        _ => return Ok(false),
+    };
+
+    act(state, instance, &format!("{} utters the word '{}'.", mobile.display_name(), words)).await?;
+    if let Some(skill) = cast.and_then(skills::lookup) {
+        let caster = Fighter::load(state, &Combatant::Mobile(instance.id)).await?;
+        let target = Fighter::load(state, &Combatant::Player(victim.slack_user_id.clone())).await?;
+        if let (Some(caster), Some(target)) = (caster, target) {
+            magic::cast(state, skill, &caster, mobile.level, &target).await?;
+        }
+    }
+    // The cures only have an effect on someone blind, poisoned or diseased
+    if let Some(effect) = effect {
+        state.slack_client.send_dm(&victim.slack_user_id, effect).await?;
+    }
+
+    // ROM's adept never counts as having acted
+    Ok(false)
+}
+
+/// Wizards' pockets are never picked
+const WIZARD_LEVEL: i32 = 50;
+
+/// Picks the pockets of players in the room
+async fn spec_thief(state: &Arc<AppState>, instance: &MobileInstance, mobile: &Mobile) -> anyhow::Result<bool> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+
+    for victim in players_here(state, instance).await? {
+        if victim.level >= WIZARD_LEVEL {
+            continue;
+        }
+
+        // Each player only has a 1 in 32 chance of being targeted
+        let (caught, gold, silver) = {
+            let mut rng = rand::thread_rng();
+            if rng.gen_range(0..32) != 0 {
+                continue;
+            }
+            let caught = rng.gen_range(0..=mobile.level) == 0;
+            let share = |rng: &mut rand::rngs::ThreadRng| rng.gen_range(1..=20).min(mobile.level / 2);
+            let gold = (victim.gold * share(&mut rng) / 100).min(mobile.level * mobile.level * 10);
+            let silver = (victim.silver * share(&mut rng) / 100).min(mobile.level * mobile.level * 25);
+            (caught, gold, silver)
+        };
+
+        if caught {
+            state.slack_client.send_dm(
+                &victim.slack_user_id,
+                &format!("You discover {}'s hands in your wallet!", mobile.short_description)
+            ).await?;
+            return Ok(true);
+        }
+
+        // The purse may have changed since it was read; if it no longer holds as much, nothing is taken
+        if player_repo.spend_money(&victim.slack_user_id, gold, silver).await? {
+            mobile_instance_repo.add_money(instance.id, gold, silver).await?;
+        }
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+/// Alignment below which a guard steps in against someone fighting (ROM's starting max_evil)
+const GUARD_MAX_EVIL: i32 = 300;
+
+/// Joins in against the most evil mobile fighting in its room
+///
+/// ROM's guards also go after players flagged KILLER or THIEF; players here carry
+/// no such flags or alignment, so only evil mobiles draw their attention.
+async fn spec_guard(state: &Arc<AppState>, instance: &MobileInstance, mobile: &Mobile) -> anyhow::Result<bool> {
+    let me = Combatant::Mobile(instance.id);
+    if instance.position() <= Position::Sleeping || combat::fighting(state, &me).is_some() {
+        return Ok(false);
+    }
+
+    let mobile_repo = MobileRepository::new(state.db_pool.clone());
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+
+    let mut max_evil = GUARD_MAX_EVIL;
+    let mut villain = None;
+    for other in mobile_instance_repo.get_in_room(&instance.room_id).await? {
+        let who = Combatant::Mobile(other.id);
+        if other.id == instance.id || combat::fighting(state, &who).is_none_or(|foe| foe == me) {
+            continue;
+        }
+        let Some(template) = mobile_repo.get_by_vnum(other.mobile_vnum).await? else { continue };
+        if template.alignment < max_evil {
+            max_evil = template.alignment;
+            villain = Some(who);
+        }
+    }
+    let Some(villain) = villain else { return Ok(false) };
+
+    let (Some(guard), Some(victim)) = (Fighter::load(state, &me).await?, Fighter::load(state, &villain).await?) else {
+        return Ok(false);
+    };
+    act(state, instance, &format!("{} screams 'PROTECT THE INNOCENT!!  BANZAI!!'", mobile.display_name())).await?;
+    combat::attack(state, &guard, &victim).await?;
+    Ok(true)
+}
+
+/// Heals itself when badly hurt, otherwise poisons or blasts whoever it's fighting
+async fn spec_cast_cleric(state: &Arc<AppState>, instance: &MobileInstance, mobile: &Mobile) -> anyhow::Result<bool> {
+    let wounded = instance.hit < instance.max_hit / 2;
+    let spell = {
+        let mut rng = rand::thread_rng();
+        if wounded && rng.gen_bool(0.5) {
+            "cure light"
+        } else if rng.gen_range(0..3) == 0 {
+            "poison"
+        } else {
+            "magic missile"
+        }
+    };
+    cast_in_combat(state, instance, mobile, spell).await
+}
+
+/// Blasts whoever it's fighting, poisoning them now and then once it's experienced enough
+async fn spec_cast_mage(state: &Arc<AppState>, instance: &MobileInstance, mobile: &Mobile) -> anyhow::Result<bool> {
+    let spell = if mobile.level >= 10 && rand::thread_rng().gen_range(0..4) == 0 {
+        "poison"
+    } else {
+        "magic missile"
+    };
+    cast_in_combat(state, instance, mobile, spell).await
+}
+
+/// Cast a spell during a fight: healing on the caster, anything else on its opponent
+///
+/// As in ROM, a caster only gets a spell off about a quarter of the time.
+async fn cast_in_combat(state: &Arc<AppState>, instance: &MobileInstance, mobile: &Mobile, spell: &str) -> anyhow::Result<bool> {
+    let me = Combatant::Mobile(instance.id);
+    let Some(foe) = combat::fighting(state, &me) else { return Ok(false) };
+    if !rand::thread_rng().gen_bool(0.25) {
+        return Ok(false);
+    }
+    let Some(skill) = skills::lookup(spell) else { return Ok(false) };
+
+    let target = if skill.target == Target::CharOffensive { foe } else { me.clone() };
+    let (Some(caster), Some(victim)) = (Fighter::load(state, &me).await?, Fighter::load(state, &target).await?) else {
+        return Ok(false);
+    };
+    act(state, instance, &format!("{} utters the words '{}'.", mobile.display_name(), skill.name)).await?;
+    magic::cast(state, skill, &caster, mobile.level, &victim).await?;
+    Ok(true)
+}
+
+/// Route the Midgaard mayor walks to open the city gates in the morning
+const MAYOR_OPEN_PATH: &str = "W3a3003b33000c111d0d111Oe333333Oe22c222112212111a1S.";
+/// Route the Midgaard mayor walks to close the city gates in the evening
+const MAYOR_CLOSE_PATH: &str = "W3a3003b33000c111d0d111CE333333CE22c222112212111a1S.";
+
+/// Where a walking mayor is along its path
+#[derive(Debug, Clone, Copy)]
+pub struct MayorWalk {
+    path: &'static str,
+    pos: usize,
+}
+
+/// Walks the city at dawn and dusk, greeting citizens and opening or closing the gates
+async fn spec_mayor(state: &Arc<AppState>, instance: &MobileInstance, mobile: &Mobile) -> anyhow::Result<bool> {
+    // The mayor can't keep walking while someone is attacking him
+    if combat::fighting(state, &Combatant::Mobile(instance.id)).is_some() {
+        return Ok(false);
+    }
+
+    let step = {
+        let mut walks = state.mayor_walks.lock().unwrap();
+        if let Entry::Vacant(entry) = walks.entry(instance.id) {
+            let path = match crate::game::time::current_hour() {
+                6 => Some(MAYOR_OPEN_PATH),
+                20 => Some(MAYOR_CLOSE_PATH),
+                _ => None,
+            };
+            if let Some(path) = path {
+                entry.insert(MayorWalk { path, pos: 0 });
+            }
+        }
+
+        let Some(walk) = walks.get_mut(&instance.id) else { return Ok(false) };
+        let step = walk.path.as_bytes()[walk.pos];
+        walk.pos += 1;
+        if step == b'.' || walk.pos >= walk.path.len() {
+            walks.remove(&instance.id);
+        }
+        step
+    };
+
+    let name = mobile.display_name();
+    match step {
+        b'0'..=b'3' => {
+            let direction = ["north", "east", "south", "west"][usize::from(step - b'0')];
+            let exit_repo = ExitRepository::new(state.db_pool.clone());
+            let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+            let exit = exit_repo.get_exit_in_direction(&instance.room_id, direction).await?.filter(|exit| !exit.is_closed());
+            // Like any mobile, the mayor won't set foot in a NO_MOB room
+            let to_room = match &exit {
+                Some(exit) => RoomRepository::new(state.db_pool.clone()).get_by_channel_id(&exit.to_room_id).await?,
+                None => None,
+            };
+            let allowed = to_room.is_some_and(|room| !room.flags().contains(RoomFlags::NO_MOB));
+            if let Some(exit) = exit.filter(|_| allowed) {
+                act(state, instance, &format!("{} heads {}.", name, direction)).await?;
+                mobile_instance_repo.update_room(instance.id, &exit.to_room_id).await?;
+                crate::handlers::broadcast_room_action(
+                    state,
+                    &exit.to_room_id,
+                    &format!("_{} arrives._", name),
+                    None,
+                    None,
+                ).await?;
+            }
+        }
+        b'W' => {
+            combat::set_position(state, &Combatant::Mobile(instance.id), Position::Standing).await?;
+            act(state, instance, &format!("{} awakens and groans loudly.", name)).await?;
+        }
+        b'S' => {
+            combat::set_position(state, &Combatant::Mobile(instance.id), Position::Sleeping).await?;
+            act(state, instance, &format!("{} lies down and falls asleep.", name)).await?;
+        }
+        b'a' => act(state, instance, &format!("{} says 'Hello Honey!'", name)).await?,
+        b'b' => act(state, instance, &format!("{} says 'What a view!  I must do something about that dump!'", name)).await?,
+        b'c' => act(state, instance, &format!("{} says 'Vandals!  Youngsters have no respect for anything!'", name)).await?,
+        b'd' => act(state, instance, &format!("{} says 'Good day, citizens!'", name)).await?,
+        b'e' => act(state, instance, &format!("{} says 'I hereby declare the city of Midgaard open!'", name)).await?,
+        b'E' => act(state, instance, &format!("{} says 'I hereby declare the city of Midgaard closed!'", name)).await?,
+        b'O' | b'C' => {
+            let actions = if step == b'O' {
+                [DoorAction::Unlock, DoorAction::Open]
+            } else {
+                [DoorAction::Close, DoorAction::Lock]
+            };
+            let exit_repo = ExitRepository::new(state.db_pool.clone());
+            let me = Combatant::Mobile(instance.id);
+            for action in actions {
+                let exits = exit_repo.get_exits_from_room(&instance.room_id).await?;
+                let Ok(gate) = door::find_door(&exits, "gate") else { break };
+                let has_key = door::has_key(state, &me, gate.key_vnum).await?;
+                if door::refusal(action, Lock::of_exit(gate), has_key).is_none() {
+                    door::work_door(state, action, gate, &name, None).await?;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_spec_funs() {
+        assert!(lookup("spec_janitor").is_some());
+        assert!(lookup("SPEC_FIDO").is_some());
+        assert!(lookup("spec_guard").is_some());
+        assert!(lookup("spec_cast_mage").is_some());
+        assert!(lookup("spec_no_such_thing").is_none());
+    }
+
+    #[test]
+    fn test_mayor_paths_end_asleep() {
+        for path in [MAYOR_OPEN_PATH, MAYOR_CLOSE_PATH] {
+            assert!(path.starts_with('W'));
+            assert!(path.ends_with("S."));
+        }
+    }
+}
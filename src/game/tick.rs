@@ -0,0 +1,165 @@
+use crate::AppState;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Length of one pulse, the scheduler's base unit of time (ROM runs 4 pulses per second)
+pub const PULSE: Duration = Duration::from_millis(250);
+
+/// The periodic ticks handlers can hook into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(dead_code)] // Not every tick has a handler registered yet
+pub enum Tick {
+    /// Every pulse
+    Pulse,
+    /// Combat rounds (ROM's PULSE_VIOLENCE, 3 seconds)
+    Violence,
+    /// Mobile AI and special procedures (ROM's PULSE_MOBILE, 4 seconds)
+    Mobile,
+    /// Regeneration, hunger and object timers (ROM's PULSE_TICK, 60 seconds)
+    Point,
+    /// Area aging and resets (ROM's PULSE_AREA, 120 seconds)
+    Area,
+}
+
+impl Tick {
+    /// How many pulses between firings of this tick
+    pub fn period(self) -> u64 {
+        match self {
+            Tick::Pulse => 1,
+            Tick::Violence => 12,
+            Tick::Mobile => 16,
+            Tick::Point => 240,
+            Tick::Area => 480,
+        }
+    }
+}
+
+/// Future returned by a tick handler
+pub type TickFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'a>>;
+
+/// A function run each time its tick fires
+pub type TickHandler<C> = for<'a> fn(&'a C) -> TickFuture<'a>;
+
+/// Drives registered handlers from a single pulse counter, like ROM's `update_handler`
+///
+/// Handlers run one after another in registration order, so a slow handler
+/// delays the pulse rather than overlapping with the next one. `advance` steps
+/// the clock by hand, which lets tests drive the scheduler deterministically.
+pub struct TickScheduler<C> {
+    pulse: u64,
+    handlers: Vec<(Tick, &'static str, TickHandler<C>)>,
+}
+
+impl<C> Default for TickScheduler<C> {
+    fn default() -> Self {
+        Self { pulse: 0, handlers: Vec::new() }
+    }
+}
+
+impl<C> TickScheduler<C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler to run whenever `tick` fires
+    pub fn register(&mut self, tick: Tick, name: &'static str, handler: TickHandler<C>) -> &mut Self {
+        self.handlers.push((tick, name, handler));
+        self
+    }
+
+    /// Advance the clock by `pulses`, running every handler that comes due
+    ///
+    /// A failing handler is logged and doesn't stop the others.
+    pub async fn advance(&mut self, context: &C, pulses: u64) {
+        for _ in 0..pulses {
+            self.pulse += 1;
+            for (tick, name, handler) in &self.handlers {
+                if self.pulse.is_multiple_of(tick.period()) {
+                    if let Err(e) = handler(context).await {
+                        tracing::error!("{:?} tick handler '{}' failed: {}", tick, name, e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Run forever, advancing one pulse every `PULSE` of real time
+    pub async fn run(mut self, context: C) {
+        let mut interval = tokio::time::interval(PULSE);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        interval.tick().await; // The first tick completes immediately
+
+        loop {
+            interval.tick().await;
+            self.advance(&context, 1).await;
+        }
+    }
+}
+
+/// The game's scheduler with every periodic system registered
+pub fn game_scheduler() -> TickScheduler<Arc<AppState>> {
+    let mut scheduler = TickScheduler::new();
+    scheduler
+        .register(Tick::Violence, "violence_update", |state| Box::pin(super::combat::violence_update(state)))
+        .register(Tick::Mobile, "mobile_update", |state| Box::pin(super::mobile::mobile_update(state)))
+        .register(Tick::Point, "point_update", |state| Box::pin(super::regen::point_update(state)))
+        .register(Tick::Point, "condition_update", |state| Box::pin(super::condition::condition_update(state)))
+        .register(Tick::Point, "object_update", |state| Box::pin(super::object::object_update(state)))
+        .register(Tick::Point, "affect_update", |state| Box::pin(super::affect::affect_update(state)))
+        .register(Tick::Point, "light_update", |state| Box::pin(super::light::light_update(state)))
+        .register(Tick::Area, "area_update", |state| Box::pin(super::reset::area_update(state)));
+    scheduler
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Default)]
+    struct Counters {
+        violence: AtomicU64,
+        area: AtomicU64,
+    }
+
+    fn count_violence(counters: &Counters) -> TickFuture<'_> {
+        counters.violence.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn count_area(counters: &Counters) -> TickFuture<'_> {
+        counters.area.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async { anyhow::bail!("failures don't stop the scheduler") })
+    }
+
+    #[tokio::test]
+    async fn test_manual_advance_fires_ticks_on_their_period() {
+        let counters = Counters::default();
+        let mut scheduler = TickScheduler::new();
+        scheduler
+            .register(Tick::Violence, "violence", count_violence)
+            .register(Tick::Area, "area", count_area);
+
+        scheduler.advance(&counters, 11).await;
+        assert_eq!(counters.violence.load(Ordering::SeqCst), 0);
+
+        scheduler.advance(&counters, 1).await;
+        assert_eq!(counters.violence.load(Ordering::SeqCst), 1);
+
+        scheduler.advance(&counters, Tick::Area.period() - 12).await;
+        assert_eq!(scheduler.pulse, Tick::Area.period());
+        assert_eq!(counters.violence.load(Ordering::SeqCst), 40);
+        assert_eq!(counters.area.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_tick_periods_match_rom() {
+        let seconds = |tick: Tick| PULSE * tick.period() as u32;
+        assert_eq!(seconds(Tick::Violence), Duration::from_secs(3));
+        assert_eq!(seconds(Tick::Mobile), Duration::from_secs(4));
+        assert_eq!(seconds(Tick::Point), Duration::from_secs(60));
+        assert_eq!(seconds(Tick::Area), Duration::from_secs(120));
+    }
+}
@@ -0,0 +1,26 @@
+/// Real seconds per game hour (one ROM tick)
+pub const SECONDS_PER_HOUR: i64 = 60;
+
+/// Game hour of the day (0-23) at a unix timestamp
+pub fn hour_at(timestamp: i64) -> i32 {
+    (timestamp / SECONDS_PER_HOUR).rem_euclid(24) as i32
+}
+
+/// Current game hour of the day (0-23)
+pub fn current_hour() -> i32 {
+    hour_at(chrono::Utc::now().timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hour_at() {
+        assert_eq!(hour_at(0), 0);
+        assert_eq!(hour_at(59), 0);
+        assert_eq!(hour_at(60), 1);
+        assert_eq!(hour_at(23 * 60), 23);
+        assert_eq!(hour_at(24 * 60), 0);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::AppState;
+use crate::slack::SlashCommand;
+use crate::db::player::PlayerRepository;
+use crate::db::exit::ExitRepository;
+use crate::db::room::RoomRepository;
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::game::affect;
+use crate::game::combat::{self, Combatant, Fighter};
+use crate::game::experience;
+use crate::models::{Mobile, MobileInstance, Player};
+use crate::models::exit::VALID_DIRECTIONS;
+use rand::seq::SliceRandom;
+use std::sync::Arc;
+use anyhow::Result;
+
+/// Handle kill command - attack a mobile in the room
+pub async fn handle_kill(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    let target = args.trim();
+    if target.is_empty() {
+        state.slack_client.send_dm(
+            &command.user_id,
+            "Usage: `/mud kill <target>`\nExample: `/mud kill fido`"
+        ).await?;
+        return Ok(());
+    }
+
+    kill(state, &command.user_id, player, target).await
+}
+
+/// Handle kill command from DM
+pub async fn handle_kill_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    let target = args.trim();
+    if target.is_empty() {
+        state.slack_client.send_dm(
+            &user_id,
+            "Usage: `kill <target>`\nExample: `kill fido`"
+        ).await?;
+        return Ok(());
+    }
+
+    kill(state, &user_id, player, target).await
+}
+
+/// Handle flee command - try to escape from a fight through a random exit
+pub async fn handle_flee(state: Arc<AppState>, command: SlashCommand) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    flee(state, &command.user_id, player).await
+}
+
+/// Handle flee command from DM
+pub async fn handle_flee_dm(state: Arc<AppState>, user_id: String, user_name: String) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    flee(state, &user_id, player).await
+}
+
+/// Handle consider command - judge how tough a mobile is
+pub async fn handle_consider(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    let target = args.trim();
+    if target.is_empty() {
+        state.slack_client.send_dm(
+            &command.user_id,
+            "Usage: `/mud consider <target>`\nExample: `/mud consider cityguard`"
+        ).await?;
+        return Ok(());
+    }
+
+    consider(state, &command.user_id, player, target).await
+}
+
+/// Handle consider command from DM
+pub async fn handle_consider_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    let target = args.trim();
+    if target.is_empty() {
+        state.slack_client.send_dm(
+            &user_id,
+            "Usage: `consider <target>`\nExample: `consider cityguard`"
+        ).await?;
+        return Ok(());
+    }
+
+    consider(state, &user_id, player, target).await
+}
+
+/// Find a mobile in the player's room by keyword, telling the player if it isn't there
+async fn find_mobile_in_room(
+    state: &Arc<AppState>,
+    user_id: &str,
+    player: &Player,
+    target: &str,
+) -> Result<Option<(MobileInstance, Mobile)>> {
+    let Some(room_id) = player.current_channel_id.as_deref() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(None);
+    };
+
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+    let mobile_repo = MobileRepository::new(state.db_pool.clone());
+
+    let sees_invisible = affect::detects_invisible(state, player).await?;
+    for instance in mobile_instance_repo.get_in_room(room_id).await? {
+        if let Some(mobile) = mobile_repo.get_by_vnum(instance.mobile_vnum).await? {
+            if mobile.matches_keyword(target) && (sees_invisible || !affect::is_invisible_mobile(&mobile)) {
+                return Ok(Some((instance, mobile)));
+            }
+        }
+    }
+
+    // Players can be named but not attacked
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let players = player_repo.get_players_in_room(room_id).await?;
+    let message = if players.iter().any(|p| p.name.eq_ignore_ascii_case(target)) {
+        if target.eq_ignore_ascii_case(&player.name) {
+            "Suicide is a mortal sin."
+        } else {
+            "You can't attack other players."
+        }
+    } else {
+        "They aren't here."
+    };
+    state.slack_client.send_dm(user_id, message).await?;
+    Ok(None)
+}
+
+async fn kill(state: Arc<AppState>, user_id: &str, player: Player, target: &str) -> Result<()> {
+    let me = Combatant::Player(player.slack_user_id.clone());
+    if combat::fighting(&state, &me).is_some() {
+        state.slack_client.send_dm(user_id, "You do the best you can!").await?;
+        return Ok(());
+    }
+    if let Some(excuse) = player.position().excuse() {
+        state.slack_client.send_dm(user_id, excuse).await?;
+        return Ok(());
+    }
+
+    let Some((instance, _)) = find_mobile_in_room(&state, user_id, &player, target).await? else {
+        return Ok(());
+    };
+
+    if combat::is_safe_room(&state, &instance.room_id).await? {
+        state.slack_client.send_dm(user_id, "Not in this room.").await?;
+        return Ok(());
+    }
+
+    let attacker = Fighter::load(&state, &me).await?;
+    let victim = Fighter::load(&state, &Combatant::Mobile(instance.id)).await?;
+    if let (Some(attacker), Some(victim)) = (attacker, victim) {
+        combat::attack(&state, &attacker, &victim).await?;
+    }
+
+    Ok(())
+}
+
+async fn flee(state: Arc<AppState>, user_id: &str, player: Player) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let exit_repo = ExitRepository::new(state.db_pool.clone());
+    let room_repo = RoomRepository::new(state.db_pool.clone());
+
+    let me = Combatant::Player(player.slack_user_id.clone());
+    let (Some(_), Some(room_id)) = (combat::fighting(&state, &me), player.current_channel_id.clone()) else {
+        state.slack_client.send_dm(user_id, "You aren't fighting anyone.").await?;
+        return Ok(());
+    };
+
+    // Like ROM, pick six random directions and take the first one that leads somewhere
+    // the player is allowed to go
+    let mut escape = None;
+    for _ in 0..6 {
+        let direction = *VALID_DIRECTIONS.choose(&mut rand::thread_rng()).unwrap();
+        let Some(exit) = exit_repo.get_exit_in_direction(&room_id, direction).await? else { continue };
+        if exit.is_closed() {
+            continue;
+        }
+        if let Some(to_room) = room_repo.get_by_channel_id(&exit.to_room_id).await? {
+            if super::r#move::entry_refusal(&state, &player, &to_room).await?.is_some() {
+                continue;
+            }
+        }
+        escape = Some(exit);
+        break;
+    }
+
+    let Some(exit) = escape else {
+        state.slack_client.send_dm(user_id, "PANIC! You couldn't escape!").await?;
+        return Ok(());
+    };
+
+    combat::stop_fighting(&state, &me, true);
+
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &format!("_{} has fled!_", player.name),
+        Some(user_id),
+        Some("_You flee from combat!_"),
+    ).await?;
+
+    // Running away costs a little experience, though never a level
+    let lost = -experience::gain_exp(&state, user_id, -10).await?;
+    if lost > 0 {
+        state.slack_client.send_dm(user_id, &format!("You lost {} exp.", lost)).await?;
+    }
+
+    player_repo.update_current_channel(&player.slack_user_id, &exit.to_room_id).await?;
+
+    super::broadcast_room_action(
+        &state,
+        &exit.to_room_id,
+        &format!("_{} arrives._", player.name),
+        Some(user_id),
+        Some("_You arrive._"),
+    ).await?;
+
+    super::look::handle_look_dm(state, user_id.to_string(), player.name, "").await
+}
+
+async fn consider(state: Arc<AppState>, user_id: &str, player: Player, target: &str) -> Result<()> {
+    let Some((_, mobile)) = find_mobile_in_room(&state, user_id, &player, target).await? else {
+        return Ok(());
+    };
+
+    let name = mobile.display_name();
+    let message = match mobile.level - player.level {
+        ..=-10 => format!("You can kill {} naked and weaponless.", mobile.short_description),
+        -9..=-5 => format!("{} is no match for you.", name),
+        -4..=-2 => format!("{} looks like an easy kill.", name),
+        -1..=1 => "The perfect match!".to_string(),
+        2..=4 => format!("{} says 'Do you feel lucky, punk?'.", name),
+        5..=9 => format!("{} laughs at you mercilessly.", name),
+        _ => "Death will thank you for your gift.".to_string(),
+    };
+
+    state.slack_client.send_dm(user_id, &message).await?;
+    Ok(())
+}
@@ -0,0 +1,427 @@
+use crate::AppState;
+use crate::slack::SlashCommand;
+use crate::db::player::PlayerRepository;
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::game::combat::Combatant;
+use crate::game::condition::{self, Conditions};
+use crate::game::{container, liquid, magic};
+use crate::models::{Object, ObjectInstance, Player};
+use std::sync::Arc;
+use anyhow::Result;
+
+/// Handle eat command - eat food or swallow a pill
+pub async fn handle_eat(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    eat(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle eat command from DM
+pub async fn handle_eat_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    eat(state, &user_id, player, args.trim()).await
+}
+
+/// Handle drink command - drink from a drink container or fountain
+pub async fn handle_drink(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    drink(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle drink command from DM
+pub async fn handle_drink_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    drink(state, &user_id, player, args.trim()).await
+}
+
+/// Handle fill command - fill a drink container at a fountain
+pub async fn handle_fill(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    fill(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle fill command from DM
+pub async fn handle_fill_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    fill(state, &user_id, player, args.trim()).await
+}
+
+/// Handle pour command - empty a drink container or pour it into another
+pub async fn handle_pour(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    pour(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle pour command from DM
+pub async fn handle_pour_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    pour(state, &user_id, player, args.trim()).await
+}
+
+/// Whether an object is a drink container ("drink" in area files, ROM's ITEM_DRINK_CON)
+pub(super) fn is_drink_container(object: &Object) -> bool {
+    matches!(object.item_type.as_str(), "drink" | "drink_con")
+}
+
+/// Find something a player is carrying by keyword
+pub(super) async fn find_carried(
+    state: &Arc<AppState>,
+    user_id: &str,
+    keyword: &str,
+) -> Result<Option<(ObjectInstance, Object)>> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    for instance in ObjectInstanceRepository::new(state.db_pool.clone()).get_in_player_inventory(user_id).await? {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
+            if object.matches_keyword(keyword) {
+                return Ok(Some((instance, object)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Find a fountain in a room
+async fn find_fountain(state: &Arc<AppState>, room_id: &str) -> Result<Option<(ObjectInstance, Object)>> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    for instance in ObjectInstanceRepository::new(state.db_pool.clone()).get_in_room(room_id).await? {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
+            if object.item_type == "fountain" {
+                return Ok(Some((instance, object)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Poison whoever just ate or drank something tainted, letting the room see them choke
+async fn choke(state: &Arc<AppState>, user_id: &str, player_name: &str, room_id: &str, level: i32, duration: i32) -> Result<()> {
+    super::broadcast_room_action(
+        state,
+        room_id,
+        &format!("_{} chokes and gags._", player_name),
+        Some(user_id),
+        Some("_You choke and gag._"),
+    ).await?;
+    magic::poison(state, &Combatant::Player(user_id.to_string()), level, duration).await
+}
+
+async fn eat(state: Arc<AppState>, user_id: &str, player: Player, args: &str) -> Result<()> {
+    if args.is_empty() {
+        state.slack_client.send_dm(user_id, "Eat what?").await?;
+        return Ok(());
+    }
+
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let Some((instance, object)) = find_carried(&state, user_id, args).await? else {
+        state.slack_client.send_dm(user_id, "You do not have that item.").await?;
+        return Ok(());
+    };
+
+    if !matches!(object.item_type.as_str(), "food" | "pill") {
+        state.slack_client.send_dm(user_id, "That's not edible.").await?;
+        return Ok(());
+    }
+    if object.item_type == "pill" && !super::magic_item::has_castable_spell(&object) {
+        state.slack_client.send_dm(user_id, "Nothing happens.").await?;
+        return Ok(());
+    }
+
+    // Pills go down however full you are
+    let immune = condition::is_immune(player.level);
+    let before = Conditions::of(&player);
+    if object.item_type == "food" && !immune && before.full > 40 {
+        state.slack_client.send_dm(user_id, "You are too full to eat more.").await?;
+        return Ok(());
+    }
+
+    // Only one of two meals begun at once gets to eat it
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+    if !object_instance_repo.delete_if_at(instance.id, &instance.location_type, &instance.location_id).await? {
+        state.slack_client.send_dm(user_id, "You do not have that item.").await?;
+        return Ok(());
+    }
+
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &format!("_{} eats {}._", player.name, object.short_description),
+        Some(user_id),
+        Some(&format!("_You eat {}._", object.short_description)),
+    ).await?;
+
+    // Food's value0 is how filling it is, value1 how much it feeds and value3 whether it's poisoned;
+    // a pill's value0 is its spell level
+    if object.item_type == "pill" {
+        let me = Combatant::Player(player.slack_user_id.clone());
+        return super::magic_item::cast_spells(&state, user_id, &room_id, &object, &me, Some(&me)).await;
+    }
+    if !immune {
+        let after = condition::gain(&state, user_id, 0, object.value0, 0, object.value1).await?;
+        if before.is_starving() && !after.is_starving() {
+            state.slack_client.send_dm(user_id, "You are no longer hungry.").await?;
+        } else if after.full > 40 {
+            state.slack_client.send_dm(user_id, "You are full.").await?;
+        }
+    }
+    if liquid::is_poisoned(&object) {
+        choke(&state, user_id, &player.name, &room_id, object.value0, 2 * object.value0).await?;
+    }
+    Ok(())
+}
+
+async fn drink(state: Arc<AppState>, user_id: &str, player: Player, args: &str) -> Result<()> {
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    // With nothing named, drink from whatever fountain is here
+    let found = if args.is_empty() {
+        find_fountain(&state, &room_id).await?
+    } else {
+        container::find_here(&state, user_id, &room_id, args).await?
+    };
+    let Some((instance, object)) = found else {
+        let message = if args.is_empty() { "Drink what?" } else { "You can't find it." };
+        state.slack_client.send_dm(user_id, message).await?;
+        return Ok(());
+    };
+
+    let left = if object.item_type == "fountain" {
+        None
+    } else if is_drink_container(&object) {
+        Some(liquid::drinks_left(&object, &instance))
+    } else {
+        state.slack_client.send_dm(user_id, "You can't drink from that.").await?;
+        return Ok(());
+    };
+    if left == Some(0) {
+        state.slack_client.send_dm(user_id, "It is already empty.").await?;
+        return Ok(());
+    }
+
+    let immune = condition::is_immune(player.level);
+    let before = Conditions::of(&player);
+    if !immune && before.is_drunk() {
+        state.slack_client.send_dm(user_id, "You fail to reach your mouth.  *Hic*").await?;
+        return Ok(());
+    }
+    if !immune && before.full > 45 {
+        state.slack_client.send_dm(user_id, "You're too full to drink more.").await?;
+        return Ok(());
+    }
+
+    let drunk = liquid::liquid_in(&object, &instance);
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &format!("_{} drinks {} from {}._", player.name, drunk.name, object.short_description),
+        Some(user_id),
+        Some(&format!("_You drink {} from {}._", drunk.name, object.short_description)),
+    ).await?;
+
+    let amount = liquid::swallow(drunk, left);
+    if let Some(left) = left {
+        ObjectInstanceRepository::new(state.db_pool.clone()).update_drink(instance.id, left - amount, drunk.name).await?;
+    }
+    if !immune {
+        let (drunkenness, full, thirst, hunger) = liquid::effects(drunk, amount);
+        let after = condition::gain(&state, user_id, drunkenness, full, thirst, hunger).await?;
+        if after.is_drunk() {
+            state.slack_client.send_dm(user_id, "You feel drunk.").await?;
+        }
+        if after.full > 40 {
+            state.slack_client.send_dm(user_id, "You are full.").await?;
+        }
+        if after.thirst > 40 {
+            state.slack_client.send_dm(user_id, "Your thirst is quenched.").await?;
+        }
+    }
+    if liquid::is_poisoned(&object) {
+        choke(&state, user_id, &player.name, &room_id, amount, 3 * amount).await?;
+    }
+    Ok(())
+}
+
+async fn fill(state: Arc<AppState>, user_id: &str, player: Player, args: &str) -> Result<()> {
+    if args.is_empty() {
+        state.slack_client.send_dm(user_id, "Fill what?").await?;
+        return Ok(());
+    }
+
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let Some((instance, object)) = find_carried(&state, user_id, args).await? else {
+        state.slack_client.send_dm(user_id, "You do not have that item.").await?;
+        return Ok(());
+    };
+
+    let Some((fountain_instance, fountain)) = find_fountain(&state, &room_id).await? else {
+        state.slack_client.send_dm(user_id, "There is no fountain here!").await?;
+        return Ok(());
+    };
+
+    if !is_drink_container(&object) {
+        state.slack_client.send_dm(user_id, "You can't fill that.").await?;
+        return Ok(());
+    }
+
+    // A drink container's value0 is how much it holds
+    let left = liquid::drinks_left(&object, &instance);
+    let source = liquid::liquid_in(&fountain, &fountain_instance);
+    if left != 0 && liquid::liquid_in(&object, &instance) != source {
+        state.slack_client.send_dm(user_id, "There is already another liquid in it.").await?;
+        return Ok(());
+    }
+    if left >= object.value0 {
+        state.slack_client.send_dm(user_id, "Your container is full.").await?;
+        return Ok(());
+    }
+
+    ObjectInstanceRepository::new(state.db_pool.clone()).update_drink(instance.id, object.value0, source.name).await?;
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &format!("_{} fills {} with {} from {}._", player.name, object.short_description, source.name, fountain.short_description),
+        Some(user_id),
+        Some(&format!("_You fill {} with {} from {}._", object.short_description, source.name, fountain.short_description)),
+    ).await
+}
+
+async fn pour(state: Arc<AppState>, user_id: &str, player: Player, args: &str) -> Result<()> {
+    let words: Vec<&str> = args.split_whitespace().collect();
+    let (from_name, to_name) = match words.as_slice() {
+        [from, "into" | "in", to] | [from, to] => (*from, *to),
+        _ => {
+            state.slack_client.send_dm(user_id, "Pour what into what?").await?;
+            return Ok(());
+        }
+    };
+
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let Some((from_instance, from)) = find_carried(&state, user_id, from_name).await? else {
+        state.slack_client.send_dm(user_id, "You don't have that item.").await?;
+        return Ok(());
+    };
+
+    if !is_drink_container(&from) {
+        state.slack_client.send_dm(user_id, "That's not a drink container.").await?;
+        return Ok(());
+    }
+
+    let poured = liquid::liquid_in(&from, &from_instance);
+    let left = liquid::drinks_left(&from, &from_instance);
+    if left == 0 {
+        state.slack_client.send_dm(user_id, &format!("{} is already empty.", from.display_name())).await?;
+        return Ok(());
+    }
+
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+    if to_name.eq_ignore_ascii_case("out") {
+        object_instance_repo.update_drink(from_instance.id, 0, poured.name).await?;
+        return super::broadcast_room_action(
+            &state,
+            &room_id,
+            &format!("_{} inverts {}, spilling {} all over the ground._", player.name, from.short_description, poured.name),
+            Some(user_id),
+            Some(&format!("_You invert {}, spilling {} all over the ground._", from.short_description, poured.name)),
+        ).await;
+    }
+
+    let Some((to_instance, to)) = container::find_here(&state, user_id, &room_id, to_name).await? else {
+        state.slack_client.send_dm(user_id, "Pour into what?").await?;
+        return Ok(());
+    };
+
+    if !is_drink_container(&to) {
+        state.slack_client.send_dm(user_id, "You can only pour into other drink containers.").await?;
+        return Ok(());
+    }
+    if to_instance.id == from_instance.id {
+        state.slack_client.send_dm(user_id, "You cannot change the laws of physics!").await?;
+        return Ok(());
+    }
+
+    let to_left = liquid::drinks_left(&to, &to_instance);
+    if to_left != 0 && liquid::liquid_in(&to, &to_instance) != poured {
+        state.slack_client.send_dm(user_id, "They don't hold the same liquid.").await?;
+        return Ok(());
+    }
+    if to_left >= to.value0 {
+        state.slack_client.send_dm(user_id, &format!("{} is already filled to the top.", to.display_name())).await?;
+        return Ok(());
+    }
+
+    let amount = left.min(to.value0 - to_left);
+    object_instance_repo.update_drink(from_instance.id, left - amount, poured.name).await?;
+    object_instance_repo.update_drink(to_instance.id, to_left + amount, poured.name).await?;
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &format!("_{} pours {} from {} into {}._", player.name, poured.name, from.short_description, to.short_description),
+        Some(user_id),
+        Some(&format!("_You pour {} from {} into {}._", poured.name, from.short_description, to.short_description)),
+    ).await
+}
@@ -0,0 +1,354 @@
+use crate::AppState;
+use crate::slack::SlashCommand;
+use crate::area::types::ContainerFlags;
+use crate::db::player::PlayerRepository;
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::game::affect;
+use crate::game::carry;
+use crate::game::container;
+use crate::models::{Object, ObjectInstance, Player};
+use std::sync::Arc;
+use anyhow::Result;
+
+/// Handle put command - put something you're carrying into a container
+pub async fn handle_put(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    put(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle put command from DM
+pub async fn handle_put_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    put(state, &user_id, player, args.trim()).await
+}
+
+/// Split "<item> [in|on] <container>" into the item and the container
+fn parse_put(args: &str) -> Option<(&str, &str)> {
+    let words: Vec<&str> = args.split_whitespace().collect();
+    match words.as_slice() {
+        [item, "in" | "on", container] | [item, container] => Some((item, container)),
+        _ => None,
+    }
+}
+
+/// The part of a get command that isn't a single item from the floor:
+/// "all", "all <container>" or "<item> <container>"
+pub(super) fn parse_get(args: &str) -> Option<(&str, Option<&str>)> {
+    let words: Vec<&str> = args.split_whitespace().collect();
+    match words.as_slice() {
+        ["all"] => Some(("all", None)),
+        [item, "from" | "in", container] | [item, container] => Some((item, Some(container))),
+        _ => None,
+    }
+}
+
+/// Join names into "a, b and c"
+fn list_names(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+/// Find a container someone can reach, or tell them why it won't do
+async fn find_open_container(
+    state: &Arc<AppState>,
+    user_id: &str,
+    room_id: &str,
+    name: &str,
+    allow_corpses: bool,
+) -> Result<Option<(ObjectInstance, Object)>> {
+    let Some((instance, object)) = container::find_here(state, user_id, room_id, name).await? else {
+        state.slack_client.send_dm(user_id, &format!("I see no {} here.", name)).await?;
+        return Ok(None);
+    };
+
+    let holds = if allow_corpses { container::is_container(&object) } else { object.item_type == "container" };
+    if !holds {
+        state.slack_client.send_dm(user_id, "That's not a container.").await?;
+        return Ok(None);
+    }
+    if container::flags(&object, &instance).contains(ContainerFlags::CLOSED) {
+        state.slack_client.send_dm(user_id, &format!("The {} is closed.", object.primary_keyword())).await?;
+        return Ok(None);
+    }
+
+    Ok(Some((instance, object)))
+}
+
+async fn put(state: Arc<AppState>, user_id: &str, player: Player, args: &str) -> Result<()> {
+    let Some((item_name, container_name)) = parse_put(args) else {
+        state.slack_client.send_dm(user_id, "Put what in what?").await?;
+        return Ok(());
+    };
+
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let Some((bag_instance, bag)) = find_open_container(&state, user_id, &room_id, container_name, false).await? else {
+        return Ok(());
+    };
+
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let mut carried = Vec::new();
+    for instance in object_instance_repo.get_in_player_inventory(&player.slack_user_id).await? {
+        if instance.id == bag_instance.id {
+            if item_name != "all" && bag.matches_keyword(item_name) {
+                state.slack_client.send_dm(user_id, "You can't fold it into itself.").await?;
+                return Ok(());
+            }
+            continue;
+        }
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
+            if item_name == "all" || object.matches_keyword(item_name) {
+                carried.push((instance, object));
+                if item_name != "all" {
+                    break;
+                }
+            }
+        }
+    }
+
+    if carried.is_empty() {
+        let message = if item_name == "all" { "You aren't carrying anything." } else { "You do not have that item." };
+        state.slack_client.send_dm(user_id, message).await?;
+        return Ok(());
+    }
+
+    // Putting everything skips whatever won't fit; putting one thing says why it won't
+    let mut contents_weight = object_instance_repo.get_contents_weight(bag_instance.id).await?;
+    let mut put_in = Vec::new();
+    for (instance, object) in &carried {
+        let weight = container::weight_with_contents(&state, object, instance).await?;
+        if let Some(refusal) = container::wont_fit(&bag, object, weight, contents_weight) {
+            if item_name != "all" {
+                state.slack_client.send_dm(user_id, refusal).await?;
+                return Ok(());
+            }
+            continue;
+        }
+        object_instance_repo.update_location(instance.id, "container", &bag_instance.id.to_string()).await?;
+        contents_weight += weight;
+        put_in.push(object.short_description.clone());
+    }
+
+    if put_in.is_empty() {
+        state.slack_client.send_dm(user_id, "Nothing you're carrying will fit.").await?;
+        return Ok(());
+    }
+
+    let things = list_names(&put_in);
+    let preposition = container::preposition(&bag, &bag_instance);
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &format!("_{} puts {} {} {}._", player.name, things, preposition, bag.short_description),
+        Some(user_id),
+        Some(&format!("_You put {} {} {}._", things, preposition, bag.short_description)),
+    ).await
+}
+
+/// Take everything from the floor, or one thing or everything from a container
+///
+/// `what` is an item keyword or "all"; `from` is the container, if any.
+pub(super) async fn get(
+    state: &Arc<AppState>,
+    user_id: &str,
+    player_name: &str,
+    room_id: &str,
+    what: &str,
+    from: Option<&str>,
+) -> Result<()> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let (instances, source) = match from {
+        Some(container_name) => {
+            let Some((bag_instance, bag)) = find_open_container(state, user_id, room_id, container_name, true).await? else {
+                return Ok(());
+            };
+            let source = Some((bag_instance.clone(), bag));
+            (object_instance_repo.get_in_container(bag_instance.id).await?, source)
+        }
+        None => (object_instance_repo.get_in_room(room_id).await?, None),
+    };
+
+    // Taking from a bag you're carrying doesn't change your load; anything else must fit it
+    let carrying_bag = source.as_ref().is_some_and(|(bag_instance, _)| {
+        matches!(bag_instance.location_type.as_str(), "player" | "equipped") && bag_instance.location_id == user_id
+    });
+    let Some(player) = PlayerRepository::new(state.db_pool.clone()).get_by_slack_id(user_id).await? else {
+        return Ok(());
+    };
+    let mut load = None;
+    if !carrying_bag {
+        load = Some((carry::carried(state, user_id).await?, carry::player_limits(state, &player).await?));
+    }
+    let sees_invisible = affect::detects_invisible(state, &player).await?;
+
+    // Coins go straight into the purse, so they're found without being among what's taken
+    let mut found = false;
+    let mut taken = Vec::new();
+    for instance in instances {
+        let Some(object) = object_repo.get_for_instance(&instance).await? else { continue };
+        if what != "all" && !object.matches_keyword(what) {
+            continue;
+        }
+        if affect::is_invisible_object(&object) && !sees_invisible {
+            continue;
+        }
+        // Fountains and the like stay where they are
+        if source.is_none() && !object.can_take() {
+            continue;
+        }
+
+        found = true;
+        if object.item_type.eq_ignore_ascii_case("money") {
+            super::item::pick_up_money(state, user_id, player_name, room_id, &instance, &object).await?;
+        } else {
+            if let Some((carried, limits)) = load.as_mut() {
+                let adding = carry::load_of(state, instance.id).await?;
+                if let Some(overload) = carry::overload(*carried, adding, *limits) {
+                    state.slack_client.send_dm(user_id, &carry::too_much(overload, &object.display_name())).await?;
+                    if what != "all" {
+                        break;
+                    }
+                    continue;
+                }
+                *carried = carried.plus(adding);
+            }
+            object_instance_repo.update_location(instance.id, "player", user_id).await?;
+            taken.push(object.short_description.clone());
+        }
+        if what != "all" {
+            break;
+        }
+    }
+
+    let Some((bag_instance, bag)) = source else {
+        if !found {
+            state.slack_client.send_dm(user_id, "I see nothing here.").await?;
+        }
+        if taken.is_empty() {
+            return Ok(());
+        }
+        let things = list_names(&taken);
+        return super::broadcast_room_action(
+            state,
+            room_id,
+            &format!("_{} picks up {}._", player_name, things),
+            Some(user_id),
+            Some(&format!("_You pick up {}._", things)),
+        ).await;
+    };
+
+    let preposition = container::preposition(&bag, &bag_instance);
+    if !found {
+        let message = if what == "all" {
+            format!("I see nothing {} the {}.", preposition, bag.primary_keyword())
+        } else {
+            format!("I see nothing like that {} the {}.", preposition, bag.primary_keyword())
+        };
+        state.slack_client.send_dm(user_id, &message).await?;
+        return Ok(());
+    }
+    if taken.is_empty() {
+        return Ok(());
+    }
+
+    let things = list_names(&taken);
+    super::broadcast_room_action(
+        state,
+        room_id,
+        &format!("_{} gets {} from {}._", player_name, things, bag.short_description),
+        Some(user_id),
+        Some(&format!("_You get {} from {}._", things, bag.short_description)),
+    ).await
+}
+
+/// Show what's inside a container (ROM's "look in")
+pub(super) async fn look_in(state: &Arc<AppState>, user_id: &str, name: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let Some(room_id) = player_repo.get_by_slack_id(user_id).await?.and_then(|p| p.current_channel_id) else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    if name.is_empty() {
+        state.slack_client.send_dm(user_id, "Look in what?").await?;
+        return Ok(());
+    }
+
+    let Some((instance, object)) = container::find_here(state, user_id, &room_id, name).await? else {
+        state.slack_client.send_dm(user_id, "You do not see that here.").await?;
+        return Ok(());
+    };
+
+    if !container::is_container(&object) {
+        state.slack_client.send_dm(user_id, "That is not a container.").await?;
+        return Ok(());
+    }
+    if container::flags(&object, &instance).contains(ContainerFlags::CLOSED) {
+        state.slack_client.send_dm(user_id, "It is closed.").await?;
+        return Ok(());
+    }
+
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let contents = ObjectInstanceRepository::new(state.db_pool.clone()).get_in_container(instance.id).await?;
+    let mut message = format!("*{} holds:*\n", object.display_name());
+    if contents.is_empty() {
+        message.push_str("_Nothing._\n");
+    }
+    for content in &contents {
+        if let Some(content_object) = object_repo.get_for_instance(content).await? {
+            message.push_str(&format!("• {}\n", content_object.short_description));
+        }
+    }
+    state.slack_client.send_dm(user_id, &message).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_put_and_get() {
+        assert_eq!(parse_put("sword bag"), Some(("sword", "bag")));
+        assert_eq!(parse_put("all in chest"), Some(("all", "chest")));
+        assert_eq!(parse_put("sword"), None);
+
+        assert_eq!(parse_get("all"), Some(("all", None)));
+        assert_eq!(parse_get("all corpse"), Some(("all", Some("corpse"))));
+        assert_eq!(parse_get("key from chest"), Some(("key", Some("chest"))));
+        assert_eq!(parse_get("sword"), None);
+    }
+
+    #[test]
+    fn test_list_names() {
+        let names = ["a key".to_string(), "a bag".to_string(), "some bread".to_string()];
+        assert_eq!(list_names(&names[..1]), "a key");
+        assert_eq!(list_names(&names), "a key, a bag and some bread");
+    }
+}
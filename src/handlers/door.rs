@@ -0,0 +1,164 @@
+use crate::AppState;
+use crate::slack::SlashCommand;
+use crate::db::exit::ExitRepository;
+use crate::db::player::PlayerRepository;
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::db::skill::SkillRepository;
+use crate::game::combat::{self, Combatant};
+use crate::game::container;
+use crate::game::door::{self, DoorAction, Lock};
+use crate::models::{Object, ObjectInstance, Player, Position};
+use rand::Rng;
+use std::sync::Arc;
+use anyhow::Result;
+
+/// Guards this many levels above a player keep them from picking a lock
+const GUARD_LEVEL_MARGIN: i32 = 5;
+
+/// Handle open, close, lock, unlock and pick commands
+pub async fn handle_door(state: Arc<AppState>, command: SlashCommand, args: &str, action: DoorAction) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    work_door(state, &command.user_id, player, args.trim(), action).await
+}
+
+/// Handle open, close, lock, unlock and pick commands from DM
+pub async fn handle_door_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+    action: DoorAction,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    work_door(state, &user_id, player, args.trim(), action).await
+}
+
+async fn work_door(state: Arc<AppState>, user_id: &str, player: Player, args: &str, action: DoorAction) -> Result<()> {
+    if args.is_empty() {
+        let verb = action.verb();
+        state.slack_client.send_dm(user_id, &format!("{}{} what?", verb[..1].to_uppercase(), &verb[1..])).await?;
+        return Ok(());
+    }
+
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    // Doors can be worked from a chair, but picking a lock needs you on your feet
+    let me = Combatant::Player(player.slack_user_id.clone());
+    let position = combat::effective_position(&state, &me, player.position());
+    let needed = if action == DoorAction::Pick { Position::Standing } else { Position::Resting };
+    if position < needed {
+        if let Some(excuse) = position.excuse() {
+            state.slack_client.send_dm(user_id, excuse).await?;
+            return Ok(());
+        }
+    }
+
+    // Like ROM, a container by that name comes before any door
+    if let Some((instance, object)) = container::find_here(&state, &player.slack_user_id, &room_id, args).await? {
+        return work_container(&state, user_id, &player, &room_id, action, &instance, &object).await;
+    }
+
+    let exits = ExitRepository::new(state.db_pool.clone()).get_exits_from_room(&room_id).await?;
+    let exit = match door::find_door(&exits, args) {
+        Ok(exit) => exit,
+        Err(message) => {
+            state.slack_client.send_dm(user_id, &message).await?;
+            return Ok(());
+        }
+    };
+
+    if action == DoorAction::Pick && !try_pick(&state, user_id, &player, &room_id).await? {
+        return Ok(());
+    }
+
+    let has_key = match action {
+        DoorAction::Lock | DoorAction::Unlock => door::has_key(&state, &me, exit.key_vnum).await?,
+        _ => false,
+    };
+    if let Some(refusal) = door::refusal(action, Lock::of_exit(exit), has_key) {
+        state.slack_client.send_dm(user_id, refusal).await?;
+        return Ok(());
+    }
+
+    door::work_door(&state, action, exit, &player.name, Some(user_id)).await
+}
+
+/// Open, close, lock, unlock or pick a container (ROM's do_open and friends, for objects)
+async fn work_container(
+    state: &Arc<AppState>,
+    user_id: &str,
+    player: &Player,
+    room_id: &str,
+    action: DoorAction,
+    instance: &ObjectInstance,
+    object: &Object,
+) -> Result<()> {
+    if object.item_type != "container" {
+        state.slack_client.send_dm(user_id, "That's not a container.").await?;
+        return Ok(());
+    }
+
+    if action == DoorAction::Pick && !try_pick(state, user_id, player, room_id).await? {
+        return Ok(());
+    }
+
+    let me = Combatant::Player(player.slack_user_id.clone());
+    let has_key = match action {
+        DoorAction::Lock | DoorAction::Unlock => door::has_key(state, &me, container::key_vnum(object)).await?,
+        _ => false,
+    };
+    if let Some(refusal) = door::refusal(action, container::lock(object, instance), has_key) {
+        state.slack_client.send_dm(user_id, refusal).await?;
+        return Ok(());
+    }
+
+    container::work_lock(state, action, object, instance).await?;
+
+    let (to_char, to_room) = match action {
+        DoorAction::Pick => (
+            format!("_You pick the lock on {}._", object.short_description),
+            format!("_{} picks the lock on {}._", player.name, object.short_description),
+        ),
+        _ => (
+            format!("_You {} {}._", action.verb(), object.short_description),
+            format!("_{} {}s {}._", player.name, action.verb(), object.short_description),
+        ),
+    };
+    super::broadcast_room_action(state, room_id, &to_room, Some(user_id), Some(&to_char)).await
+}
+
+/// See whether a pick attempt gets past any guards and the player's skill (ROM's do_pick)
+async fn try_pick(state: &Arc<AppState>, user_id: &str, player: &Player, room_id: &str) -> Result<bool> {
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+    let mobile_repo = MobileRepository::new(state.db_pool.clone());
+    for instance in mobile_instance_repo.get_in_room(room_id).await? {
+        let Some(mobile) = mobile_repo.get_by_vnum(instance.mobile_vnum).await? else { continue };
+        if instance.position() > Position::Sleeping && mobile.level > player.level + GUARD_LEVEL_MARGIN {
+            state.slack_client.send_dm(
+                user_id,
+                &format!("{} is standing too close to the lock.", mobile.display_name())
+            ).await?;
+            return Ok(false);
+        }
+    }
+
+    let learned = SkillRepository::new(state.db_pool.clone()).get_learned(&player.slack_user_id).await?;
+    let percent = learned.get("pick lock").copied().unwrap_or(0);
+    if rand::thread_rng().gen_range(1..=100) > percent {
+        state.slack_client.send_dm(user_id, "You failed.").await?;
+        return Ok(false);
+    }
+
+    Ok(true)
+}
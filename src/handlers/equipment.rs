@@ -2,6 +2,7 @@ use crate::AppState;
 use crate::slack::SlashCommand;
 use crate::db::player::PlayerRepository;
 use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::game::affect;
 use crate::models::EquipmentSlot;
 use std::sync::Arc;
 use anyhow::Result;
@@ -32,7 +33,7 @@ pub async fn handle_wear(state: Arc<AppState>, command: SlashCommand, args: &str
     let mut found_object = None;
 
     for instance in inventory_instances {
-        if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
             if object.matches_keyword(item_name) {
                 found_instance = Some(instance);
                 found_object = Some(object);
@@ -61,8 +62,20 @@ pub async fn handle_wear(state: Arc<AppState>, command: SlashCommand, args: &str
         return Ok(());
     }
 
-    // Get valid slots for this item
-    let valid_slots = EquipmentSlot::from_wear_flags(&object.wear_flags);
+    if instance.is_broken() {
+        state.slack_client.send_dm(
+            &command.user_id,
+            &format!("{} is broken and needs repairing first.", object.display_name())
+        ).await?;
+        return Ok(());
+    }
+
+    // Get valid slots for this item; light sources only need to be taken, as in ROM
+    let valid_slots = if object.item_type == "light" {
+        vec![EquipmentSlot::Light]
+    } else {
+        EquipmentSlot::from_wear_flags(&object.wear_flags)
+    };
 
     if valid_slots.is_empty() {
         state.slack_client.send_dm(
@@ -99,15 +112,29 @@ pub async fn handle_wear(state: Arc<AppState>, command: SlashCommand, args: &str
         &player.slack_user_id,
         slot.to_db_string(),
     ).await?;
+    affect::refresh_gear_points(&state, &player.slack_user_id).await?;
 
     // Send success message
-    let wear_message = format!("You wear {} {}.", object.short_description, get_slot_location_text(slot));
+    let wear_message = if *slot == EquipmentSlot::Light {
+        format!("You light {} and hold it.", object.short_description)
+    } else {
+        format!("You wear {} {}.", object.short_description, get_slot_location_text(slot))
+    };
     state.slack_client.send_dm(&command.user_id, &wear_message).await?;
 
     // Broadcast action to room
     if let Some(room_id) = player.current_channel_id {
-        let third_person = format!("_{} wears {}._", player.name, object.short_description);
-        let first_person = format!("_You wear {}._", object.short_description);
+        let (third_person, first_person) = if *slot == EquipmentSlot::Light {
+            (
+                format!("_{} lights {} and holds it._", player.name, object.short_description),
+                format!("_You light {} and hold it._", object.short_description),
+            )
+        } else {
+            (
+                format!("_{} wears {}._", player.name, object.short_description),
+                format!("_You wear {}._", object.short_description),
+            )
+        };
         super::broadcast_room_action(
             &state,
             &room_id,
@@ -146,7 +173,7 @@ pub async fn handle_wield(state: Arc<AppState>, command: SlashCommand, args: &st
     let mut found_object = None;
 
     for instance in inventory_instances {
-        if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
             if object.matches_keyword(item_name) {
                 found_instance = Some(instance);
                 found_object = Some(object);
@@ -175,8 +202,16 @@ pub async fn handle_wield(state: Arc<AppState>, command: SlashCommand, args: &st
         return Ok(());
     }
 
+    if instance.is_broken() {
+        state.slack_client.send_dm(
+            &command.user_id,
+            &format!("{} is broken and needs repairing first.", object.display_name())
+        ).await?;
+        return Ok(());
+    }
+
     // Check if item can be wielded
-    if !object.wear_flags.to_lowercase().contains("wield") {
+    if !EquipmentSlot::from_wear_flags(&object.wear_flags).contains(&EquipmentSlot::Wield) {
         state.slack_client.send_dm(
             &command.user_id,
             &format!("You can't wield {}.", object.short_description)
@@ -199,6 +234,7 @@ pub async fn handle_wield(state: Arc<AppState>, command: SlashCommand, args: &st
         &player.slack_user_id,
         "wield",
     ).await?;
+    affect::refresh_gear_points(&state, &player.slack_user_id).await?;
 
     // Send success message
     state.slack_client.send_dm(
@@ -248,7 +284,7 @@ pub async fn handle_remove(state: Arc<AppState>, command: SlashCommand, args: &s
     let mut found_object = None;
 
     for instance in equipped_instances {
-        if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
             if object.matches_keyword(item_name) {
                 found_instance = Some(instance);
                 found_object = Some(object);
@@ -270,6 +306,7 @@ pub async fn handle_remove(state: Arc<AppState>, command: SlashCommand, args: &s
 
     // Unequip the item
     object_instance_repo.unequip_item(instance.id, &player.slack_user_id).await?;
+    affect::refresh_gear_points(&state, &player.slack_user_id).await?;
 
     // Send success message
     state.slack_client.send_dm(
@@ -327,11 +364,12 @@ pub async fn handle_equipment(state: Arc<AppState>, command: SlashCommand) -> Re
         if let Some(instance) = equipped_instances.iter().find(|i| {
             i.equipped_slot.as_ref().map(|s| s.as_str()) == Some(slot_str)
         }) {
-            if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+            if let Some(object) = object_repo.get_for_instance(instance).await? {
                 equipment_text.push_str(&format!(
-                    "{:<20} {}\\n",
+                    "{:<20} {} ({})\\n",
                     slot.display_label(),
-                    object.short_description
+                    object.short_description,
+                    crate::game::object::condition_name(instance.current_condition)
                 ));
 
                 // Calculate totals
@@ -1,5 +1,6 @@
 use crate::AppState;
 use crate::slack::{EventWrapper, Event, MessageEvent};
+use crate::game::door::DoorAction;
 use axum::{
     extract::State,
     response::{IntoResponse, Json, Response},
@@ -154,6 +155,13 @@ async fn handle_message_event(
                 user_id.clone(),
             ).await
         }
+        "score" | "sc" => {
+            super::score::handle_score_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+            ).await
+        }
         "exits" => {
             handle_exits_dm(state.clone(), user_id.clone(), user_name).await
         }
@@ -214,6 +222,30 @@ async fn handle_message_event(
                 "down",
             ).await
         }
+        "recall" => {
+            super::r#move::handle_recall_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+            ).await
+        }
+        // Door commands
+        "open" | "close" | "lock" | "unlock" | "pick" => {
+            let action = match command.to_lowercase().as_str() {
+                "open" => DoorAction::Open,
+                "close" => DoorAction::Close,
+                "lock" => DoorAction::Lock,
+                "unlock" => DoorAction::Unlock,
+                _ => DoorAction::Pick,
+            };
+            super::door::handle_door_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+                action,
+            ).await
+        }
         "dig" => {
             super::dig::handle_dig_dm(
                 state.clone(),
@@ -222,6 +254,14 @@ async fn handle_message_event(
                 _args,
             ).await
         }
+        "extra" => {
+            super::extra::handle_extra_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
         "attach" => {
             super::attach::handle_attach_dm(
                 state.clone(),
@@ -286,6 +326,14 @@ async fn handle_message_event(
                 _args,
             ).await
         }
+        "put" => {
+            super::container::handle_put_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
         "give" => {
             super::item::handle_give_dm(
                 state.clone(),
@@ -309,6 +357,169 @@ async fn handle_message_event(
                 _args,
             ).await
         }
+        // Item use commands
+        "eat" => {
+            super::consume::handle_eat_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "drink" => {
+            super::consume::handle_drink_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "fill" => {
+            super::consume::handle_fill_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "pour" => {
+            super::consume::handle_pour_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "quaff" => {
+            super::magic_item::handle_quaff_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "recite" => {
+            super::magic_item::handle_recite_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "zap" => {
+            super::magic_item::handle_zap_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "brandish" => {
+            super::magic_item::handle_brandish_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+            ).await
+        }
+        // Shop commands
+        "list" => {
+            super::shop::handle_list_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+            ).await
+        }
+        "buy" => {
+            super::shop::handle_buy_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "sell" => {
+            super::shop::handle_sell_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "value" => {
+            super::shop::handle_value_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "repair" => {
+            super::shop::handle_repair_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        // Combat commands
+        "kill" | "k" => {
+            super::combat::handle_kill_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "flee" => {
+            super::combat::handle_flee_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+            ).await
+        }
+        "consider" | "con" => {
+            super::combat::handle_consider_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        // Position commands
+        "rest" | "sit" | "sleep" | "stand" => {
+            let to = crate::models::Position::parse(command).unwrap_or(crate::models::Position::Standing);
+            super::position::handle_position_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                to,
+            ).await
+        }
+        "wake" => {
+            super::position::handle_wake_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        // Skill commands
+        "cast" => {
+            super::skill::handle_cast_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
+        "practice" | "prac" => {
+            super::skill::handle_practice_dm(
+                state.clone(),
+                user_id.clone(),
+                user_name,
+                _args,
+            ).await
+        }
         // Communication commands
         "say" => {
             super::communication::handle_say_dm(
@@ -406,6 +617,11 @@ async fn handle_exits_dm(state: Arc<AppState>, user_id: String, user_name: Strin
     } else {
         let mut msg = format!("*Exits from #{}:*\n", room_name);
         for exit in &exits {
+            // A closed door hides where it leads
+            if exit.is_closed() {
+                msg.push_str(&format!("• *{}* - _the {} is closed_\n", exit.direction, exit.door_name()));
+                continue;
+            }
             let target_room_name = if let Some(room) = room_repo.get_by_channel_id(&exit.to_room_id).await? {
                 room.channel_name
             } else {
@@ -435,12 +651,42 @@ async fn handle_help_dm(state: Arc<AppState>, user_id: String) -> anyhow::Result
     let mut help_text = String::from("*SlackMUD Commands*\n\n");
     help_text.push_str("• `look` or `l` - Look around the current room\n");
     help_text.push_str("• `look <item>` - Examine an item in detail\n");
+    help_text.push_str("• `look in <container>` - See what's inside a container\n");
     help_text.push_str("• `exits` - Show available exits\n");
     help_text.push_str("• `n/s/e/w/u/d` or `north/south/east/west/up/down` - Move in a direction\n");
+    help_text.push_str("• `recall` - Pray to be taken back to the temple\n");
+    help_text.push_str("• `open/close <door|direction|container>` - Open or close a door or container\n");
+    help_text.push_str("• `lock/unlock <door|direction|container>` - Lock or unlock a door or container with its key\n");
+    help_text.push_str("• `pick <door|direction|container>` - Try to pick a lock\n");
     help_text.push_str("• `get <item>` or `take <item>` - Pick up an item\n");
+    help_text.push_str("• `get all` - Pick up everything you can\n");
+    help_text.push_str("• `get <item|all> <container>` - Take things out of a container or corpse\n");
+    help_text.push_str("• `put <item|all> <container>` - Put things in a container\n");
     help_text.push_str("• `drop <item>` - Drop an item\n");
     help_text.push_str("• `give <item> <player>` - Give an item to another player\n");
+    help_text.push_str("• `give <n> gold|silver <player>` - Give coins to another player\n");
     help_text.push_str("• `inventory` or `i` - Show what you're carrying\n");
+    help_text.push_str("• `eat <food|pill>` - Eat something you're carrying\n");
+    help_text.push_str("• `drink [container]` - Drink from a container, or from a fountain here\n");
+    help_text.push_str("• `fill <container>` - Fill a drink container at a fountain\n");
+    help_text.push_str("• `pour <container> out|<container>` - Empty a drink container or pour it into another\n");
+    help_text.push_str("• `quaff <potion>` - Drink a potion\n");
+    help_text.push_str("• `recite <scroll> [target]` - Read a scroll aloud\n");
+    help_text.push_str("• `zap [target]` - Use the wand you're holding\n");
+    help_text.push_str("• `brandish` - Use the staff you're holding on everyone here\n");
+    help_text.push_str("• `list` - List what a shopkeeper has for sale\n");
+    help_text.push_str("• `buy <item>` - Buy an item from a shopkeeper\n");
+    help_text.push_str("• `sell <item>` - Sell an item to a shopkeeper\n");
+    help_text.push_str("• `value <item>` - Ask a shopkeeper what an item is worth\n");
+    help_text.push_str("• `repair <item>` - Have an armourer or weaponsmith mend worn gear\n");
+    help_text.push_str("• `kill <target>` or `k <target>` - Attack a creature\n");
+    help_text.push_str("• `flee` - Try to escape from a fight\n");
+    help_text.push_str("• `consider <target>` or `con <target>` - Judge how tough a creature is\n");
+    help_text.push_str("• `rest`, `sit`, `sleep`, `stand` - Change position (resting and sleeping heal faster)\n");
+    help_text.push_str("• `wake [target]` - Wake up, or wake someone else\n");
+    help_text.push_str("• `cast '<spell>' [target]` - Cast a spell (quote spell names with spaces)\n");
+    help_text.push_str("• `practice [skill]` - List your skills, or practice one at a guildmaster\n");
+    help_text.push_str("• `score` - Show your stats and purse\n");
     help_text.push_str("• `character` or `c` - View your character info\n");
     help_text.push_str("• `say <message>` - Say something to everyone in the room\n");
     help_text.push_str("• `tell <player> <message>` - Send a private message to another player\n");
@@ -451,6 +697,7 @@ async fn handle_help_dm(state: Arc<AppState>, user_id: String) -> anyhow::Result
     if is_wizard {
         help_text.push_str("\n*Wizard Commands:*\n");
         help_text.push_str("• `dig <direction> #channel` - Create an exit\n");
+        help_text.push_str("• `extra [<keywords> = <description> | remove <keyword>]` - List or edit this room's extra descriptions\n");
         help_text.push_str("• `attach #channel` - Attach current room to a Slack channel\n");
         help_text.push_str("• `detach` - Detach current room from its Slack channel\n");
         help_text.push_str("• `import-area <url>` - Import MUD area file (creates virtual rooms)\n");
@@ -0,0 +1,139 @@
+use crate::AppState;
+use crate::slack::SlashCommand;
+use crate::db::player::PlayerRepository;
+use crate::db::room::RoomExtraDescriptionRepository;
+use crate::models::{Player, RoomExtraDescription};
+use std::sync::Arc;
+use anyhow::Result;
+
+const WIZARD_LEVEL: i32 = 50;
+
+/// Handle extra command - list, add or remove the current room's extra descriptions (wizard only)
+pub async fn handle_extra(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    extra(state, &command.user_id, player, args.trim(), "/mud ").await
+}
+
+/// Handle extra command from DM
+pub async fn handle_extra_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    extra(state, &user_id, player, args.trim(), "").await
+}
+
+/// What the wizard asked for
+#[derive(Debug, PartialEq, Eq)]
+enum Request<'a> {
+    List,
+    Set { keywords: &'a str, description: &'a str },
+    Remove(&'a str),
+}
+
+fn parse_request(args: &str) -> Option<Request<'_>> {
+    if args.is_empty() {
+        return Some(Request::List);
+    }
+    if let Some((keywords, description)) = args.split_once('=') {
+        let (keywords, description) = (keywords.trim(), description.trim());
+        if keywords.is_empty() || description.is_empty() {
+            return None;
+        }
+        return Some(Request::Set { keywords, description });
+    }
+    match args.split_once(char::is_whitespace) {
+        Some(("remove", keyword)) => Some(Request::Remove(keyword.trim())),
+        _ => None,
+    }
+}
+
+async fn extra(state: Arc<AppState>, user_id: &str, player: Player, args: &str, prefix: &str) -> Result<()> {
+    if player.level < WIZARD_LEVEL {
+        state.slack_client.send_dm(
+            user_id,
+            &format!("You must be a wizard (level {}) to edit extra descriptions.", WIZARD_LEVEL)
+        ).await?;
+        return Ok(());
+    }
+
+    let Some(room_id) = player.current_channel_id else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let Some(request) = parse_request(args) else {
+        state.slack_client.send_dm(
+            user_id,
+            &format!(
+                "Usage:\n• `{0}extra` - List this room's extra descriptions\n• `{0}extra <keywords> = <description>` - Add one\n• `{0}extra remove <keyword>` - Remove one\nExample: `{0}extra fountain water = A marble fountain gurgles here.`",
+                prefix
+            )
+        ).await?;
+        return Ok(());
+    };
+
+    let extra_repo = RoomExtraDescriptionRepository::new(state.db_pool.clone());
+    let message = match request {
+        Request::List => {
+            let extras = extra_repo.get_for_room(&room_id).await?;
+            if extras.is_empty() {
+                "This room has no extra descriptions.".to_string()
+            } else {
+                let mut message = String::from("*Extra descriptions here:*\n");
+                for extra in &extras {
+                    message.push_str(&format!("• `{}` - {}\n", extra.keywords, extra.description));
+                }
+                message
+            }
+        }
+        Request::Set { keywords, description } => {
+            // A new description replaces any old one answering to the same keywords
+            for existing in extra_repo.get_for_room(&room_id).await? {
+                if keywords.split_whitespace().any(|k| existing.matches_keyword(k)) {
+                    extra_repo.delete(existing.id).await?;
+                }
+            }
+            let keywords = keywords.to_lowercase();
+            extra_repo.create(&RoomExtraDescription::new(room_id.clone(), keywords.clone(), description.to_string())).await?;
+            format!("Extra description added for `{}`.", keywords)
+        }
+        Request::Remove(keyword) => match extra_repo.find(&room_id, keyword).await? {
+            Some(existing) => {
+                extra_repo.delete(existing.id).await?;
+                format!("Removed the extra description for `{}`.", existing.keywords)
+            }
+            None => format!("There's no extra description for `{}` here.", keyword),
+        },
+    };
+
+    state.slack_client.send_dm(user_id, &message).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        assert_eq!(parse_request(""), Some(Request::List));
+        assert_eq!(
+            parse_request("fountain water = A marble fountain."),
+            Some(Request::Set { keywords: "fountain water", description: "A marble fountain." })
+        );
+        assert_eq!(parse_request("remove sign"), Some(Request::Remove("sign")));
+        assert_eq!(parse_request("fountain ="), None);
+        assert_eq!(parse_request("fountain"), None);
+    }
+}
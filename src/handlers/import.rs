@@ -1,11 +1,15 @@
 use crate::AppState;
 use crate::slack::SlashCommand;
 use crate::db::player::PlayerRepository;
-use crate::db::room::RoomRepository;
+use crate::db::room::{RoomRepository, RoomExtraDescriptionRepository};
 use crate::db::exit::ExitRepository;
-use crate::db::area::AreaRepository;
+use crate::db::area::{AreaRepository, AreaResetRepository};
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::db::object::ObjectRepository;
+use crate::db::shop::ShopRepository;
 use crate::area::parser::parse_area_file;
-use crate::models::{Room, Exit, Area};
+use crate::area::types::ExitFlags;
+use crate::models::{Room, RoomExtraDescription, Exit, Area, Object, Mobile, Shop};
 use std::sync::Arc;
 use anyhow::Result;
 
@@ -137,7 +141,12 @@ async fn import_area_from_content(
 ) -> Result<()> {
     let area_repo = AreaRepository::new(state.db_pool.clone());
     let room_repo = RoomRepository::new(state.db_pool.clone());
+    let extra_repo = RoomExtraDescriptionRepository::new(state.db_pool.clone());
     let exit_repo = ExitRepository::new(state.db_pool.clone());
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let mobile_repo = MobileRepository::new(state.db_pool.clone());
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+    let shop_repo = ShopRepository::new(state.db_pool.clone());
 
     // Parse the area file
     state.slack_client.send_dm(
@@ -159,9 +168,11 @@ async fn import_area_from_content(
     // Report what was parsed
     state.slack_client.send_dm(
         user_id,
-        &format!("✅ Parsed area: *{}*\n📖 Rooms found: {}\n📊 Vnum range: {}-{}",
+        &format!("✅ Parsed area: *{}*\n📖 Rooms found: {}\n🎒 Objects found: {}\n👹 Mobiles found: {}\n📊 Vnum range: {}-{}",
             area_file.header.name,
             area_file.rooms.len(),
+            area_file.objects.len(),
+            area_file.mobiles.len(),
             area_file.header.min_vnum,
             area_file.header.max_vnum
         )
@@ -183,6 +194,10 @@ async fn import_area_from_content(
                 user_id,
                 &format!("🗑️  Deleting existing area *{}*...", area_file.header.name)
             ).await?;
+            mobile_instance_repo.delete_by_area(&area_file.header.name).await?;
+            object_repo.delete_by_area(&area_file.header.name).await?;
+            shop_repo.delete_by_area(&area_file.header.name).await?;
+            mobile_repo.delete_by_area(&area_file.header.name).await?;
             area_repo.delete_by_name(&area_file.header.name).await?;
         }
     }
@@ -195,6 +210,9 @@ async fn import_area_from_content(
 
     let mut rooms_created = 0;
     let mut exits_created = 0;
+    let mut objects_created = 0;
+    let mut mobiles_created = 0;
+    let mut shops_created = 0;
 
     // First pass: Create all rooms
     for area_room in &area_file.rooms {
@@ -205,11 +223,20 @@ async fn import_area_from_content(
             channel_name: area_room.name.clone(),
             description: area_room.description.clone(),
             attached_channel_id: None, // Virtual room (not attached)
+            heal_rate: area_room.heal_rate,
+            mana_rate: area_room.mana_rate,
+            room_flags: area_room.room_flags.to_letters(),
+            sector_type: area_room.sector_type as i32,
             created_at: chrono::Utc::now().timestamp(),
             updated_at: chrono::Utc::now().timestamp(),
         };
 
         room_repo.create(&room).await?;
+        extra_repo.delete_for_room(&room_id).await?;
+        for extra in &area_room.extra_descs {
+            let extra = RoomExtraDescription::new(room_id.clone(), extra.keywords.join(" "), extra.description.clone());
+            extra_repo.create(&extra).await?;
+        }
         rooms_created += 1;
     }
 
@@ -226,18 +253,60 @@ async fn import_area_from_content(
                 continue;
             }
 
-            let exit = Exit::new(
+            let mut exit = Exit::new(
                 room_id.clone(),
                 area_exit.direction.as_str().to_string(),
                 to_room_id,
                 Some(player_slack_id.clone()),
             );
+            if let Some(keyword) = &area_exit.keyword {
+                exit.keyword = keyword.clone();
+            }
+            exit.description = area_exit.description.clone();
+            exit.exit_flags = ExitFlags::from_lock_code(area_exit.door_flags).to_letters();
+            exit.key_vnum = area_exit.key_vnum;
 
             exit_repo.create(&exit).await?;
             exits_created += 1;
         }
     }
 
+    // Third pass: Create all objects
+    for area_object in &area_file.objects {
+        let object = Object::from_area_object(area_file.header.name.clone(), area_object)?;
+
+        object_repo.create(&object).await?;
+        objects_created += 1;
+    }
+
+    // Fourth pass: Create all mobiles
+    for area_mobile in &area_file.mobiles {
+        let mut mobile = Mobile::from_area_mobile(area_file.header.name.clone(), area_mobile);
+        mobile.spec_fun = area_file.specials.iter()
+            .find(|s| s.mob_vnum == area_mobile.vnum)
+            .map(|s| s.spec_fun.clone());
+        mobile_repo.create(&mobile).await?;
+        mobiles_created += 1;
+    }
+
+    // Create shops for shopkeepers defined in this area
+    for area_shop in &area_file.shops {
+        if !area_file.mobiles.iter().any(|m| m.vnum == area_shop.keeper_vnum) {
+            continue;
+        }
+        let shop = Shop::from_area_shop(area_file.header.name.clone(), area_shop);
+        shop_repo.create(&shop).await?;
+        shops_created += 1;
+    }
+
+    // Fifth pass: Process resets and spawn object and mobile instances
+    let spawned = crate::game::reset::apply_resets(
+        &state.db_pool,
+        &area_file.resets,
+        area_file.header.min_vnum,
+        area_file.header.max_vnum,
+    ).await?;
+
     // Record the area in the database
     let area = Area::new(
         area_file.header.name.clone(),
@@ -249,13 +318,22 @@ async fn import_area_from_content(
     );
     area_repo.create(&area).await?;
 
+    // Store the resets so the area can be repopulated later
+    let reset_repo = AreaResetRepository::new(state.db_pool.clone());
+    reset_repo.replace_for_area(&area_file.header.name, &area_file.resets).await?;
+
     // Report success
     state.slack_client.send_dm(
         user_id,
-        &format!("✨ *Import complete!*\n\n📦 Area: *{}*\n🏠 Rooms created: {}\n🚪 Exits created: {}\n\n💡 These are virtual rooms (not attached to Slack channels). Use `attach #channel` to make a room visible in a channel.",
+        &format!("✨ *Import complete!*\n\n📦 Area: *{}*\n🏠 Rooms created: {}\n🚪 Exits created: {}\n🎒 Objects created: {}\n👹 Mobiles created: {}\n🏪 Shops created: {}\n✨ Spawned: {} objects, {} mobiles\n\n💡 These are virtual rooms (not attached to Slack channels). Use `attach #channel` to make a room visible in a channel.",
             area_file.header.name,
             rooms_created,
-            exits_created
+            exits_created,
+            objects_created,
+            mobiles_created,
+            shops_created,
+            spawned.objects_spawned,
+            spawned.mobiles_spawned
         )
     ).await?;
 
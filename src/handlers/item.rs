@@ -2,6 +2,7 @@ use crate::AppState;
 use crate::slack::SlashCommand;
 use crate::db::player::PlayerRepository;
 use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::game::affect;
 use std::sync::Arc;
 use anyhow::Result;
 
@@ -16,7 +17,7 @@ pub async fn handle_get(state: Arc<AppState>, command: SlashCommand, args: &str)
     let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
 
     // Check if player has a current room
-    let room_id = match player.current_channel_id {
+    let room_id = match player.current_channel_id.clone() {
         Some(id) => id,
         None => {
             state.slack_client.send_dm(
@@ -36,16 +37,22 @@ pub async fn handle_get(state: Arc<AppState>, command: SlashCommand, args: &str)
         return Ok(());
     }
 
+    // Taking everything, or taking from a container
+    if let Some((what, from)) = super::container::parse_get(item_name) {
+        return super::container::get(&state, &command.user_id, &player.name, &room_id, what, from).await;
+    }
+
     // Get all object instances in the room
     let instances = object_instance_repo.get_in_room(&room_id).await?;
 
-    // Find matching object
+    // Find matching object, among those the player can see
+    let sees_invisible = affect::detects_invisible(&state, &player).await?;
     let mut found_instance = None;
     let mut found_object = None;
 
     for instance in instances {
-        if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
-            if object.matches_keyword(item_name) {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
+            if object.matches_keyword(item_name) && (sees_invisible || !affect::is_invisible_object(&object)) {
                 found_instance = Some(instance);
                 found_object = Some(object);
                 break;
@@ -54,6 +61,19 @@ pub async fn handle_get(state: Arc<AppState>, command: SlashCommand, args: &str)
     }
 
     if let (Some(instance), Some(object)) = (found_instance, found_object) {
+        // Money goes straight into the purse
+        if object.item_type.eq_ignore_ascii_case("money") {
+            return pick_up_money(&state, &command.user_id, &player.name, &room_id, &instance, &object).await;
+        }
+
+        if let Some(overload) = crate::game::carry::refusal(&state, &player, instance.id).await? {
+            state.slack_client.send_dm(
+                &command.user_id,
+                &crate::game::carry::too_much(overload, &object.display_name())
+            ).await?;
+            return Ok(());
+        }
+
         // Move object from room to player inventory
         object_instance_repo.update_location(
             instance.id,
@@ -102,7 +122,7 @@ pub async fn handle_get_dm(
     let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
 
     // Check if player has a current room
-    let room_id = match player.current_channel_id {
+    let room_id = match player.current_channel_id.clone() {
         Some(id) => id,
         None => {
             state.slack_client.send_dm(
@@ -122,16 +142,22 @@ pub async fn handle_get_dm(
         return Ok(());
     }
 
+    // Taking everything, or taking from a container
+    if let Some((what, from)) = super::container::parse_get(item_name) {
+        return super::container::get(&state, &user_id, &player.name, &room_id, what, from).await;
+    }
+
     // Get all object instances in the room
     let instances = object_instance_repo.get_in_room(&room_id).await?;
 
-    // Find matching object
+    // Find matching object, among those the player can see
+    let sees_invisible = affect::detects_invisible(&state, &player).await?;
     let mut found_instance = None;
     let mut found_object = None;
 
     for instance in instances {
-        if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
-            if object.matches_keyword(item_name) {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
+            if object.matches_keyword(item_name) && (sees_invisible || !affect::is_invisible_object(&object)) {
                 found_instance = Some(instance);
                 found_object = Some(object);
                 break;
@@ -140,6 +166,19 @@ pub async fn handle_get_dm(
     }
 
     if let (Some(instance), Some(object)) = (found_instance, found_object) {
+        // Money goes straight into the purse
+        if object.item_type.eq_ignore_ascii_case("money") {
+            return pick_up_money(&state, &user_id, &player.name, &room_id, &instance, &object).await;
+        }
+
+        if let Some(overload) = crate::game::carry::refusal(&state, &player, instance.id).await? {
+            state.slack_client.send_dm(
+                &user_id,
+                &crate::game::carry::too_much(overload, &object.display_name())
+            ).await?;
+            return Ok(());
+        }
+
         // Move object from room to player inventory
         object_instance_repo.update_location(
             instance.id,
@@ -212,7 +251,7 @@ pub async fn handle_drop(state: Arc<AppState>, command: SlashCommand, args: &str
     let mut found_object = None;
 
     for instance in instances {
-        if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
             if object.matches_keyword(item_name) {
                 found_instance = Some(instance);
                 found_object = Some(object);
@@ -245,6 +284,17 @@ pub async fn handle_drop(state: Arc<AppState>, command: SlashCommand, args: &str
             Some(&command.user_id),
             Some(&first_person),
         ).await?;
+
+        if crate::game::object::melts_on_drop(&object) {
+            object_instance_repo.delete_with_contents(instance.id).await?;
+            super::broadcast_room_action(
+                &state,
+                &room_id,
+                &format!("_{} dissolves into smoke._", object.display_name()),
+                None,
+                None,
+            ).await?;
+        }
     } else {
         state.slack_client.send_dm(
             &command.user_id,
@@ -298,7 +348,7 @@ pub async fn handle_drop_dm(
     let mut found_object = None;
 
     for instance in instances {
-        if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
             if object.matches_keyword(item_name) {
                 found_instance = Some(instance);
                 found_object = Some(object);
@@ -331,6 +381,17 @@ pub async fn handle_drop_dm(
             Some(&user_id),
             Some(&first_person),
         ).await?;
+
+        if crate::game::object::melts_on_drop(&object) {
+            object_instance_repo.delete_with_contents(instance.id).await?;
+            super::broadcast_room_action(
+                &state,
+                &room_id,
+                &format!("_{} dissolves into smoke._", object.display_name()),
+                None,
+                None,
+            ).await?;
+        }
     } else {
         state.slack_client.send_dm(
             &user_id,
@@ -362,10 +423,13 @@ pub async fn handle_inventory(state: Arc<AppState>, command: SlashCommand) -> Re
     } else {
         let mut inventory_text = String::from("*Inventory:*\n");
         for instance in instances {
-            if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+            if let Some(object) = object_repo.get_for_instance(&instance).await? {
                 inventory_text.push_str(&format!("• {}\n", object.short_description));
             }
         }
+        let carried = crate::game::carry::carried(&state, &player.slack_user_id).await?;
+        let limits = crate::game::carry::player_limits(&state, &player).await?;
+        inventory_text.push_str(&format!("\n{}", crate::game::carry::summary(carried, limits)));
         state.slack_client.send_dm(&command.user_id, &inventory_text).await?;
     }
 
@@ -396,10 +460,13 @@ pub async fn handle_inventory_dm(
     } else {
         let mut inventory_text = String::from("*Inventory:*\n");
         for instance in instances {
-            if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+            if let Some(object) = object_repo.get_for_instance(&instance).await? {
                 inventory_text.push_str(&format!("• {}\n", object.short_description));
             }
         }
+        let carried = crate::game::carry::carried(&state, &player.slack_user_id).await?;
+        let limits = crate::game::carry::player_limits(&state, &player).await?;
+        inventory_text.push_str(&format!("\n{}", crate::game::carry::summary(carried, limits)));
         state.slack_client.send_dm(&user_id, &inventory_text).await?;
     }
 
@@ -459,7 +526,10 @@ pub async fn handle_manifest(state: Arc<AppState>, command: SlashCommand, args:
     match object {
         Some(obj) => {
             // Create object instance in the room
-            let instance = crate::models::ObjectInstance::new_in_room(obj.vnum, room_id.clone());
+            let instance = crate::game::object::fresh(
+                crate::models::ObjectInstance::new_in_room(obj.vnum, room_id.clone()),
+                &obj,
+            );
             object_instance_repo.create(&instance).await?;
 
             // Broadcast dramatic action to room
@@ -566,7 +636,10 @@ pub async fn handle_manifest_dm(
     match object {
         Some(obj) => {
             // Create object instance in the room
-            let instance = crate::models::ObjectInstance::new_in_room(obj.vnum, room_id.clone());
+            let instance = crate::game::object::fresh(
+                crate::models::ObjectInstance::new_in_room(obj.vnum, room_id.clone()),
+                &obj,
+            );
             object_instance_repo.create(&instance).await?;
 
             // Broadcast dramatic action to room
@@ -655,12 +728,17 @@ pub async fn handle_give(state: Arc<AppState>, command: SlashCommand, args: &str
         return Ok(());
     }
 
+    // "give <n> gold|silver <player>" hands over coins instead of an item
+    if let Some((amount, is_gold)) = parse_coins(item_name) {
+        return give_coins(&state, &command.user_id, &player, &room_id, amount, is_gold, target_name).await;
+    }
+
     // Find the item in player's inventory or equipped
     let instances = object_instance_repo.get_by_owner(&player.slack_user_id).await?;
 
     let mut item_to_give = None;
     for instance in instances {
-        let object = object_repo.get_by_vnum(instance.object_vnum).await?;
+        let object = object_repo.get_for_instance(&instance).await?;
         if let Some(obj) = object {
             if obj.matches_keyword(item_name) {
                 item_to_give = Some((instance, obj));
@@ -703,6 +781,15 @@ pub async fn handle_give(state: Arc<AppState>, command: SlashCommand, args: &str
         return Ok(());
     }
 
+    if let Some(overload) = crate::game::carry::refusal(&state, &target_player, instance.id).await? {
+        let message = match overload {
+            crate::game::carry::Overload::Items => format!("{} has their hands full.", target_player.name),
+            crate::game::carry::Overload::Weight => format!("{} can't carry that much weight.", target_player.name),
+        };
+        state.slack_client.send_dm(&command.user_id, &message).await?;
+        return Ok(());
+    }
+
     // Transfer the item
     object_instance_repo.transfer_to_player(instance.id, &target_player.slack_user_id).await?;
 
@@ -786,12 +873,17 @@ pub async fn handle_give_dm(
         return Ok(());
     }
 
+    // "give <n> gold|silver <player>" hands over coins instead of an item
+    if let Some((amount, is_gold)) = parse_coins(item_name) {
+        return give_coins(&state, &user_id, &player, &room_id, amount, is_gold, target_name).await;
+    }
+
     // Find the item in player's inventory or equipped
     let instances = object_instance_repo.get_by_owner(&player.slack_user_id).await?;
 
     let mut item_to_give = None;
     for instance in instances {
-        let object = object_repo.get_by_vnum(instance.object_vnum).await?;
+        let object = object_repo.get_for_instance(&instance).await?;
         if let Some(obj) = object {
             if obj.matches_keyword(item_name) {
                 item_to_give = Some((instance, obj));
@@ -834,6 +926,15 @@ pub async fn handle_give_dm(
         return Ok(());
     }
 
+    if let Some(overload) = crate::game::carry::refusal(&state, &target_player, instance.id).await? {
+        let message = match overload {
+            crate::game::carry::Overload::Items => format!("{} has their hands full.", target_player.name),
+            crate::game::carry::Overload::Weight => format!("{} can't carry that much weight.", target_player.name),
+        };
+        state.slack_client.send_dm(&user_id, &message).await?;
+        return Ok(());
+    }
+
     // Transfer the item
     object_instance_repo.transfer_to_player(instance.id, &target_player.slack_user_id).await?;
 
@@ -875,3 +976,122 @@ async fn find_player_in_room(
 
     Ok(None)
 }
+
+/// Pick up a money object: its coins go into the player's purse and the object disappears
+pub(super) async fn pick_up_money(
+    state: &Arc<AppState>,
+    user_id: &str,
+    player_name: &str,
+    room_id: &str,
+    instance: &crate::models::ObjectInstance,
+    object: &crate::models::Object,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    // ROM money: value0 = silver, value1 = gold
+    let silver = object.value0.max(0);
+    let gold = object.value1.max(0);
+
+    // Only whoever actually removes the coins gets them
+    if !object_instance_repo.delete_if_at(instance.id, &instance.location_type, &instance.location_id).await? {
+        state.slack_client.send_dm(user_id, &format!("{} is no longer there.", object.display_name())).await?;
+        return Ok(());
+    }
+    player_repo.add_money(user_id, gold, silver).await?;
+
+    let coins = match (gold, silver) {
+        (0, silver) => format!("{} silver coin{}", silver, if silver == 1 { "" } else { "s" }),
+        (gold, 0) => format!("{} gold coin{}", gold, if gold == 1 { "" } else { "s" }),
+        (gold, silver) => format!("{} gold and {} silver coins", gold, silver),
+    };
+
+    let third_person = format!("_{} picks up {}._", player_name, object.short_description);
+    let first_person = format!("_You get {}._", coins);
+    super::broadcast_room_action(
+        state,
+        room_id,
+        &third_person,
+        Some(user_id),
+        Some(&first_person),
+    ).await?;
+
+    Ok(())
+}
+
+/// Parse "<n> gold", "<n> silver" or "<n> coins" into an amount and whether it is gold
+fn parse_coins(text: &str) -> Option<(i32, bool)> {
+    let mut parts = text.split_whitespace();
+    let amount = parts.next()?.parse::<i32>().ok()?;
+    let is_gold = match parts.next()?.to_lowercase().as_str() {
+        "gold" => true,
+        "silver" | "coin" | "coins" => false,
+        _ => return None,
+    };
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((amount, is_gold))
+}
+
+/// Give coins from a player's purse to another player in the same room
+async fn give_coins(
+    state: &Arc<AppState>,
+    user_id: &str,
+    player: &crate::models::Player,
+    room_id: &str,
+    amount: i32,
+    is_gold: bool,
+    target_name: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+
+    if amount <= 0 {
+        state.slack_client.send_dm(user_id, "Sorry, you can't do that.").await?;
+        return Ok(());
+    }
+
+    let (available, coin) = if is_gold { (player.gold, "gold") } else { (player.silver, "silver") };
+    if available < amount {
+        state.slack_client.send_dm(user_id, "You haven't got that much.").await?;
+        return Ok(());
+    }
+
+    let target_player = match find_player_in_room(state, room_id, target_name).await? {
+        Some(p) => p,
+        None => {
+            state.slack_client.send_dm(user_id, &format!("You don't see '{}' here.", target_name)).await?;
+            return Ok(());
+        }
+    };
+
+    if target_player.slack_user_id == player.slack_user_id {
+        state.slack_client.send_dm(user_id, "You can't give items to yourself!").await?;
+        return Ok(());
+    }
+
+    let (gold, silver) = if is_gold { (amount, 0) } else { (0, amount) };
+    if !player_repo.spend_money(&player.slack_user_id, gold, silver).await? {
+        state.slack_client.send_dm(user_id, "You haven't got that much.").await?;
+        return Ok(());
+    }
+    player_repo.add_money(&target_player.slack_user_id, gold, silver).await?;
+
+    let first_person = format!("You give {} {} {}.", target_player.name, amount, coin);
+    let second_person = format!("{} gives you {} {}.", player.name, amount, coin);
+    let third_person = format!("_{} gives {} some coins._", player.name, target_player.name);
+
+    state.slack_client.send_dm(&target_player.slack_user_id, &second_person).await?;
+
+    super::broadcast_room_action(
+        state,
+        room_id,
+        &third_person,
+        Some(user_id),
+        Some(&first_person),
+    ).await?;
+
+    Ok(())
+}
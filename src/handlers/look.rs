@@ -1,10 +1,12 @@
 use crate::AppState;
 use crate::slack::{SlashCommand, Block};
 use crate::db::player::PlayerRepository;
-use crate::db::room::RoomRepository;
+use crate::db::room::{RoomRepository, RoomExtraDescriptionRepository};
 use crate::db::exit::ExitRepository;
 use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
-use crate::models::Player;
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::game::{affect, light};
+use crate::models::{Player, Position};
 use std::sync::Arc;
 use anyhow::Result;
 
@@ -16,11 +18,25 @@ pub async fn handle_look(state: Arc<AppState>, command: SlashCommand) -> Result<
 
     // If there's an argument, try looking at a player first, then object
     if !args.is_empty() {
+        // Nothing in the room can be made out in the dark, not even yourself
+        if in_the_dark(&state, &command.user_id).await? {
+            return Ok(());
+        }
+
         // Check if player is looking at themselves
         if args.to_lowercase() == "self" || args.to_lowercase() == "me" {
             return handle_look_at_self(state, &command.user_id).await;
         }
 
+        if let Some(container_name) = args.strip_prefix("in ") {
+            return super::container::look_in(&state, &command.user_id, container_name.trim()).await;
+        }
+
+        // Things described in the room itself come before anyone or anything in it
+        if look_at_room_extra(&state, &command.user_id, args).await? {
+            return Ok(());
+        }
+
         // Try to look at a player
         if let Ok(_) = handle_look_at_player(state.clone(), &command.user_id, args).await {
             return Ok(());
@@ -115,11 +131,25 @@ pub async fn handle_look_dm(
 
     // If there's an argument, try looking at a player first, then object
     if !args.is_empty() {
+        // Nothing in the room can be made out in the dark, not even yourself
+        if in_the_dark(&state, &user_id).await? {
+            return Ok(());
+        }
+
         // Check if player is looking at themselves
         if args.to_lowercase() == "self" || args.to_lowercase() == "me" {
             return handle_look_at_self(state, &user_id).await;
         }
 
+        if let Some(container_name) = args.strip_prefix("in ") {
+            return super::container::look_in(&state, &user_id, container_name.trim()).await;
+        }
+
+        // Things described in the room itself come before anyone or anything in it
+        if look_at_room_extra(&state, &user_id, args).await? {
+            return Ok(());
+        }
+
         // Try to look at a player
         if let Ok(_) = handle_look_at_player(state.clone(), &user_id, args).await {
             return Ok(());
@@ -192,6 +222,27 @@ pub async fn handle_look_dm(
     Ok(())
 }
 
+/// Tell a player it's pitch black if their room is too dark to see anything in, returning whether it was
+///
+/// Wizards see in the dark, as they do when looking around the room.
+async fn in_the_dark(state: &Arc<AppState>, user_id: &str) -> Result<bool> {
+    let Some(player) = PlayerRepository::new(state.db_pool.clone()).get_by_slack_id(user_id).await? else {
+        return Ok(false);
+    };
+    let Some(room_id) = player.current_channel_id.as_deref() else { return Ok(false) };
+    if player.level >= 50 {
+        return Ok(false);
+    }
+    let Some(room) = RoomRepository::new(state.db_pool.clone()).get_by_channel_id(room_id).await? else {
+        return Ok(false);
+    };
+    if !light::room_is_dark(state, &room).await? {
+        return Ok(false);
+    }
+    state.slack_client.send_dm(user_id, "It is pitch black ... ").await?;
+    Ok(true)
+}
+
 /// Helper function to send room description with player list and exits
 async fn send_room_description(
     state: Arc<AppState>,
@@ -206,10 +257,22 @@ async fn send_room_description(
     let room_repo = RoomRepository::new(state.db_pool.clone());
     let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
     let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+    let mobile_repo = MobileRepository::new(state.db_pool.clone());
 
     // Get full room details to check for attached channel
     let room = room_repo.get_by_channel_id(room_channel_id).await?;
 
+    // Nobody but a wizard sees anything in the dark without a light
+    if current_player.level < 50 {
+        if let Some(ref room_data) = room {
+            if light::room_is_dark(&state, room_data).await? {
+                state.slack_client.send_dm(user_id, "It is pitch black ... ").await?;
+                return Ok(());
+            }
+        }
+    }
+
     // Build room title - show vnum and attached channel for wizards
     let room_title = if current_player.level >= 50 {
         // Extract vnum from channel_id (format: vnum_3014)
@@ -245,11 +308,25 @@ async fn send_room_description(
         Block::section(room_description),
     ];
 
+    // Wizards also see how the room is set up
+    if current_player.level >= 50 {
+        if let Some(ref room_data) = room {
+            let flags = room_data.flags().names();
+            let flags = if flags.is_empty() { "none".to_string() } else { flags.join(" ") };
+            blocks.push(Block::section(&format!("_Room flags: {} | Sector: {}_", flags, room_data.sector().as_str())));
+        }
+    }
+
     // Add exits section
     let exits = exit_repo.get_exits_from_room(room_channel_id).await?;
     if !exits.is_empty() {
         let mut exits_text = String::from("*Exits:*\n");
         for exit in &exits {
+            // A closed door hides where it leads
+            if exit.is_closed() {
+                exits_text.push_str(&format!("• *{}* - _the {} is closed_\n", exit.direction, exit.door_name()));
+                continue;
+            }
             // Get target room name
             let target_room_name = if let Some(room) = room_repo.get_by_channel_id(&exit.to_room_id).await? {
                 room.channel_name
@@ -265,10 +342,14 @@ async fn send_room_description(
     if !players_in_room.is_empty() {
         let mut players_text = String::from("*Players here:*\n");
         for player in players_in_room {
+            let position = match player.position() {
+                Position::Standing => String::new(),
+                position => format!(" ({})", position.as_str()),
+            };
             if player.slack_user_id == current_player.slack_user_id {
-                players_text.push_str(&format!("• {} (you)\n", player.name));
+                players_text.push_str(&format!("• {} (you){}\n", player.name, position));
             } else {
-                players_text.push_str(&format!("• {}\n", player.name));
+                players_text.push_str(&format!("• {}{}\n", player.name, position));
             }
         }
         blocks.push(Block::section(&players_text));
@@ -276,17 +357,43 @@ async fn send_room_description(
         blocks.push(Block::section("*Players here:*\n_You are alone._"));
     }
 
+    // Invisible mobiles and objects only show up for those who can detect them
+    let sees_invisible = affect::detects_invisible(&state, current_player).await?;
+
+    // Add mobiles in room section
+    let mut mobiles_text = String::new();
+    for instance in &mobile_instance_repo.get_in_room(room_channel_id).await? {
+        // Get the mobile definition
+        if let Some(mobile) = mobile_repo.get_by_vnum(instance.mobile_vnum).await? {
+            if affect::is_invisible_mobile(&mobile) && !sees_invisible {
+                continue;
+            }
+            // Like ROM, the long description only fits a mobile in its usual position
+            let position = instance.position();
+            if position == Position::parse_or_standing(&mobile.default_position) {
+                mobiles_text.push_str(&format!("• {}\n", mobile.long_description));
+            } else {
+                mobiles_text.push_str(&format!("• {} is {} here.\n", mobile.display_name(), position.as_str()));
+            }
+        }
+    }
+    if !mobiles_text.is_empty() {
+        blocks.push(Block::section(&format!("*Also here:*\n{}", mobiles_text)));
+    }
+
     // Add objects in room section
-    let object_instances = object_instance_repo.get_in_room(room_channel_id).await?;
-    if !object_instances.is_empty() {
-        let mut objects_text = String::from("*Items here:*\n");
-        for instance in &object_instances {
-            // Get the object definition
-            if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
-                objects_text.push_str(&format!("• {}\n", object.long_description));
+    let mut objects_text = String::new();
+    for instance in &object_instance_repo.get_in_room(room_channel_id).await? {
+        // Get the object definition
+        if let Some(object) = object_repo.get_for_instance(instance).await? {
+            if affect::is_invisible_object(&object) && !sees_invisible {
+                continue;
             }
+            objects_text.push_str(&format!("• {}\n", object.long_description));
         }
-        blocks.push(Block::section(&objects_text));
+    }
+    if !objects_text.is_empty() {
+        blocks.push(Block::section(&format!("*Items here:*\n{}", objects_text)));
     }
 
     let dm_text = format!("You look around #{}", room_name);
@@ -308,6 +415,7 @@ async fn handle_look_at_object(
     // Get player
     let real_name = state.slack_client.get_user_real_name(user_id).await?;
     let player = player_repo.get_or_create(user_id.to_string(), real_name).await?;
+    let sees_invisible = affect::detects_invisible(&state, &player).await?;
 
     // Check if player has a current room
     let room_id = match player.current_channel_id {
@@ -324,10 +432,30 @@ async fn handle_look_at_object(
     // Search for object in player's inventory first
     let inventory_instances = object_instance_repo.get_in_player_inventory(&player.slack_user_id).await?;
     for instance in &inventory_instances {
-        if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+        if let Some(object) = object_repo.get_for_instance(instance).await? {
+            // Like ROM, an extra description matching the keyword wins over the object itself
+            if let Some(extra) = object.extra_description(object_name) {
+                state.slack_client.send_dm(user_id, &extra).await?;
+                return Ok(());
+            }
             if object.matches_keyword(object_name) {
                 // Found in inventory
-                send_object_description(&state, user_id, &object, "inventory").await?;
+                send_object_description(&state, user_id, instance, &object, "inventory").await?;
+                return Ok(());
+            }
+        }
+    }
+
+    // Then what they're wearing
+    let equipped_instances = object_instance_repo.get_equipped(&player.slack_user_id).await?;
+    for instance in &equipped_instances {
+        if let Some(object) = object_repo.get_for_instance(instance).await? {
+            if let Some(extra) = object.extra_description(object_name) {
+                state.slack_client.send_dm(user_id, &extra).await?;
+                return Ok(());
+            }
+            if object.matches_keyword(object_name) {
+                send_object_description(&state, user_id, instance, &object, "equipped").await?;
                 return Ok(());
             }
         }
@@ -336,10 +464,17 @@ async fn handle_look_at_object(
     // Search for object in current room
     let room_instances = object_instance_repo.get_in_room(&room_id).await?;
     for instance in &room_instances {
-        if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+        if let Some(object) = object_repo.get_for_instance(instance).await? {
+            if affect::is_invisible_object(&object) && !sees_invisible {
+                continue;
+            }
+            if let Some(extra) = object.extra_description(object_name) {
+                state.slack_client.send_dm(user_id, &extra).await?;
+                return Ok(());
+            }
             if object.matches_keyword(object_name) {
                 // Found in room
-                send_object_description(&state, user_id, &object, "room").await?;
+                send_object_description(&state, user_id, instance, &object, "room").await?;
                 return Ok(());
             }
         }
@@ -354,15 +489,35 @@ async fn handle_look_at_object(
     Ok(())
 }
 
+/// Show a room extra description matching the keyword, returning false if there isn't one
+async fn look_at_room_extra(state: &Arc<AppState>, user_id: &str, keyword: &str) -> Result<bool> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let extra_repo = RoomExtraDescriptionRepository::new(state.db_pool.clone());
+
+    let Some(room_id) = player_repo.get_by_slack_id(user_id).await?.and_then(|p| p.current_channel_id) else {
+        return Ok(false);
+    };
+
+    match extra_repo.find(&room_id, keyword).await? {
+        Some(extra) => {
+            state.slack_client.send_dm(user_id, &extra.description).await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
 /// Send detailed description of an object to the player
 async fn send_object_description(
     state: &Arc<AppState>,
     user_id: &str,
+    instance: &crate::models::ObjectInstance,
     object: &crate::models::Object,
     location: &str,
 ) -> Result<()> {
     let location_text = match location {
         "inventory" => "You are carrying:",
+        "equipped" => "You are using:",
         "room" => "You examine:",
         _ => "You see:",
     };
@@ -373,6 +528,10 @@ async fn send_object_description(
     description.push_str(&format!("*Item Type:* {}\n", object.item_type));
     description.push_str(&format!("*Material:* {}\n", object.material));
     description.push_str(&format!("*Weight:* {} lbs\n", object.weight));
+    description.push_str(&format!(
+        "*Condition:* {}\n",
+        crate::game::object::condition_name(instance.current_condition)
+    ));
 
     // Show equipment stats
     match object.item_type.to_lowercase().as_str() {
@@ -488,8 +647,12 @@ async fn handle_look_at_player(
         target.level
     ));
 
-    // Health status (could be enhanced with actual health tracking)
-    description.push_str(&format!("{} is in excellent condition.\n\n", target.name));
+    // Health status
+    description.push_str(&format!(
+        "{} {}\n\n",
+        target.name,
+        crate::game::combat::condition(target.hit, target.max_hit)
+    ));
 
     // Get all equipped items
     let equipped_instances = object_instance_repo.get_equipped(&target.slack_user_id).await?;
@@ -507,7 +670,7 @@ async fn handle_look_at_player(
             if let Some(instance) = equipped_instances.iter().find(|i| {
                 i.equipped_slot.as_ref().map(|s| s.as_str()) == Some(slot_str)
             }) {
-                if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+                if let Some(object) = object_repo.get_for_instance(instance).await? {
                     description.push_str(&format!(
                         "{:<20} {}\n",
                         slot.display_label(),
@@ -528,7 +691,7 @@ async fn handle_look_at_player(
     if !inventory_instances.is_empty() {
         description.push_str(&format!("*{} is carrying:*\n", target.name));
         for instance in &inventory_instances {
-            if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+            if let Some(object) = object_repo.get_for_instance(instance).await? {
                 description.push_str(&format!("• {}\n", object.short_description));
             }
         }
@@ -604,7 +767,11 @@ async fn handle_look_at_self(
     ));
 
     // Health status
-    description.push_str(&format!("{} is in excellent condition.\n\n", player.name));
+    description.push_str(&format!(
+        "{} {}\n\n",
+        player.name,
+        crate::game::combat::condition(player.hit, player.max_hit)
+    ));
 
     // Get all equipped items
     let equipped_instances = object_instance_repo.get_equipped(&player.slack_user_id).await?;
@@ -622,7 +789,7 @@ async fn handle_look_at_self(
             if let Some(instance) = equipped_instances.iter().find(|i| {
                 i.equipped_slot.as_ref().map(|s| s.as_str()) == Some(slot_str)
             }) {
-                if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+                if let Some(object) = object_repo.get_for_instance(instance).await? {
                     description.push_str(&format!(
                         "{:<20} {}\n",
                         slot.display_label(),
@@ -643,7 +810,7 @@ async fn handle_look_at_self(
     if !inventory_instances.is_empty() {
         description.push_str(&format!("*{} is carrying:*\n", player.name));
         for instance in &inventory_instances {
-            if let Some(object) = object_repo.get_by_vnum(instance.object_vnum).await? {
+            if let Some(object) = object_repo.get_for_instance(instance).await? {
                 description.push_str(&format!("• {}\n", object.short_description));
             }
         }
@@ -0,0 +1,433 @@
+use crate::AppState;
+use crate::slack::SlashCommand;
+use crate::db::player::PlayerRepository;
+use crate::db::mobile::MobileInstanceRepository;
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::game::affect;
+use crate::game::combat::{self, Combatant, Fighter};
+use crate::game::magic;
+use crate::game::skills::{self, Skill, Target};
+use crate::models::{EquipmentSlot, Object, ObjectInstance, Player};
+use std::sync::Arc;
+use anyhow::Result;
+
+/// Handle quaff command - drink a potion
+pub async fn handle_quaff(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    quaff(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle quaff command from DM
+pub async fn handle_quaff_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    quaff(state, &user_id, player, args.trim()).await
+}
+
+/// Handle recite command - read a scroll aloud, optionally at someone
+pub async fn handle_recite(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    recite(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle recite command from DM
+pub async fn handle_recite_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    recite(state, &user_id, player, args.trim()).await
+}
+
+/// Handle zap command - point a held wand at someone
+pub async fn handle_zap(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    zap(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle zap command from DM
+pub async fn handle_zap_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    zap(state, &user_id, player, args.trim()).await
+}
+
+/// Handle brandish command - wave a held staff over the whole room
+pub async fn handle_brandish(state: Arc<AppState>, command: SlashCommand) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    brandish(state, &command.user_id, player).await
+}
+
+/// Handle brandish command from DM
+pub async fn handle_brandish_dm(state: Arc<AppState>, user_id: String, user_name: String) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    brandish(state, &user_id, player).await
+}
+
+/// Work one of an item's spells at the item's level (ROM's obj_cast_spell)
+///
+/// Items can't be used to attack players, nor turned on their user.
+async fn cast_spell(
+    state: &Arc<AppState>,
+    user_id: &str,
+    room_id: &str,
+    skill: &Skill,
+    level: i32,
+    caster: &Combatant,
+    victim: &Combatant,
+) -> Result<()> {
+    if skill.target == Target::CharOffensive {
+        if victim == caster {
+            return Ok(());
+        }
+        if matches!(victim, Combatant::Player(_)) {
+            state.slack_client.send_dm(user_id, "You can't attack other players.").await?;
+            return Ok(());
+        }
+        if combat::is_safe_room(state, room_id).await? {
+            state.slack_client.send_dm(user_id, "Not in this room.").await?;
+            return Ok(());
+        }
+    }
+
+    let (Some(caster), Some(victim)) = (Fighter::load(state, caster).await?, Fighter::load(state, victim).await?) else {
+        return Ok(());
+    };
+    magic::cast(state, skill, &caster, level, &victim).await
+}
+
+/// Work every spell an item holds at its value0 level, on `target` or wherever each spell aims by default
+pub(super) async fn cast_spells(
+    state: &Arc<AppState>,
+    user_id: &str,
+    room_id: &str,
+    object: &Object,
+    caster: &Combatant,
+    target: Option<&Combatant>,
+) -> Result<()> {
+    for name in object.spells() {
+        let Some(skill) = skills::lookup(&name) else {
+            tracing::debug!("{} casts unknown spell '{}'", object.short_description, name);
+            continue;
+        };
+        let victim = match skill.target {
+            Target::CharOffensive => target.cloned().or_else(|| combat::fighting(state, caster)),
+            Target::CharSelf => Some(caster.clone()),
+            Target::CharDefensive => Some(target.unwrap_or(caster).clone()),
+            Target::Room => {
+                if let Some(caster) = Fighter::load(state, caster).await? {
+                    magic::cast_on_room(state, skill, &caster, object.value0).await?;
+                }
+                continue;
+            }
+            // Items are only ever aimed at someone, so they have nothing to work an object spell on
+            Target::ObjectInventory => {
+                state.slack_client.send_dm(user_id, "Nothing happens.").await?;
+                continue;
+            }
+        };
+        match victim {
+            Some(victim) => cast_spell(state, user_id, room_id, skill, object.value0, caster, &victim).await?,
+            None => state.slack_client.send_dm(user_id, "You can't find anyone to aim it at.").await?,
+        }
+    }
+    Ok(())
+}
+
+/// Whether any of an item's spells is one we know how to cast
+///
+/// An item holding nothing castable does nothing, and isn't used up for it.
+pub(super) fn has_castable_spell(object: &Object) -> bool {
+    object.spells().iter().any(|name| skills::lookup(name).is_some())
+}
+
+/// The wand or staff a player is holding, if it is one
+async fn held_item(state: &Arc<AppState>, user_id: &str, item_type: &str) -> Result<Option<(ObjectInstance, Object)>> {
+    let hold_slot = EquipmentSlot::Hold.to_db_string();
+    let Some(instance) = ObjectInstanceRepository::new(state.db_pool.clone()).get_item_in_slot(user_id, hold_slot).await? else {
+        return Ok(None);
+    };
+    let object = ObjectRepository::new(state.db_pool.clone()).get_for_instance(&instance).await?;
+    Ok(object.filter(|object| object.item_type == item_type).map(|object| (instance, object)))
+}
+
+/// Use up a charge of a held wand or staff, destroying it with `last_gasp` when the last one goes
+async fn use_charge(
+    state: &Arc<AppState>,
+    player: &Player,
+    room_id: &str,
+    instance: &ObjectInstance,
+    object: &Object,
+    last_gasp: &str,
+) -> Result<()> {
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+    let charges = magic::charges_left(object, instance) - 1;
+    if charges > 0 {
+        object_instance_repo.update_charges(instance.id, charges).await?;
+        return Ok(());
+    }
+
+    object_instance_repo.delete(instance.id).await?;
+    affect::refresh_gear_points(state, &player.slack_user_id).await?;
+    super::broadcast_room_action(
+        state,
+        room_id,
+        &format!("_{}'s {} {}._", player.name, object.short_description, last_gasp),
+        Some(&player.slack_user_id),
+        Some(&format!("_Your {} {}._", object.short_description, last_gasp)),
+    ).await
+}
+
+async fn quaff(state: Arc<AppState>, user_id: &str, player: Player, args: &str) -> Result<()> {
+    if args.is_empty() {
+        state.slack_client.send_dm(user_id, "Quaff what?").await?;
+        return Ok(());
+    }
+
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let Some((instance, object)) = super::consume::find_carried(&state, user_id, args).await? else {
+        state.slack_client.send_dm(user_id, "You do not have that potion.").await?;
+        return Ok(());
+    };
+
+    if object.item_type != "potion" {
+        state.slack_client.send_dm(user_id, "You can quaff only potions.").await?;
+        return Ok(());
+    }
+    if object.level > player.level {
+        state.slack_client.send_dm(user_id, "This liquid is too powerful for you to drink.").await?;
+        return Ok(());
+    }
+    if !has_castable_spell(&object) {
+        state.slack_client.send_dm(user_id, "Nothing happens.").await?;
+        return Ok(());
+    }
+
+    // Used up before it works, so a potion can't be quaffed twice at once
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+    if !object_instance_repo.delete_if_at(instance.id, &instance.location_type, &instance.location_id).await? {
+        state.slack_client.send_dm(user_id, "You do not have that potion.").await?;
+        return Ok(());
+    }
+
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &format!("_{} quaffs {}._", player.name, object.short_description),
+        Some(user_id),
+        Some(&format!("_You quaff {}._", object.short_description)),
+    ).await?;
+
+    let me = Combatant::Player(player.slack_user_id.clone());
+    cast_spells(&state, user_id, &room_id, &object, &me, Some(&me)).await
+}
+
+async fn recite(state: Arc<AppState>, user_id: &str, player: Player, args: &str) -> Result<()> {
+    let (scroll_name, target_name) = match args.split_once(char::is_whitespace) {
+        Some((scroll, target)) => (scroll, target.trim()),
+        None => (args, ""),
+    };
+    if scroll_name.is_empty() {
+        state.slack_client.send_dm(user_id, "Recite what?").await?;
+        return Ok(());
+    }
+
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let Some((instance, object)) = super::consume::find_carried(&state, user_id, scroll_name).await? else {
+        state.slack_client.send_dm(user_id, "You do not have that scroll.").await?;
+        return Ok(());
+    };
+
+    if object.item_type != "scroll" {
+        state.slack_client.send_dm(user_id, "You can recite only scrolls.").await?;
+        return Ok(());
+    }
+    if object.level > player.level {
+        state.slack_client.send_dm(user_id, "This scroll is too complex for you to comprehend.").await?;
+        return Ok(());
+    }
+    if !has_castable_spell(&object) {
+        state.slack_client.send_dm(user_id, "Nothing happens.").await?;
+        return Ok(());
+    }
+
+    let target = if target_name.is_empty() || target_name == "self" {
+        None
+    } else {
+        match super::skill::find_in_room(&state, &player, &room_id, target_name).await? {
+            Some(target) => Some(target),
+            None => {
+                state.slack_client.send_dm(user_id, "You can't find it.").await?;
+                return Ok(());
+            }
+        }
+    };
+
+    // Used up before it works, so a scroll can't be recited twice at once
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+    if !object_instance_repo.delete_if_at(instance.id, &instance.location_type, &instance.location_id).await? {
+        state.slack_client.send_dm(user_id, "You do not have that scroll.").await?;
+        return Ok(());
+    }
+
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &format!("_{} recites {}._", player.name, object.short_description),
+        Some(user_id),
+        Some(&format!("_You recite {}._", object.short_description)),
+    ).await?;
+
+    let me = Combatant::Player(player.slack_user_id.clone());
+    cast_spells(&state, user_id, &room_id, &object, &me, target.as_ref()).await
+}
+
+async fn zap(state: Arc<AppState>, user_id: &str, player: Player, args: &str) -> Result<()> {
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let Some((instance, object)) = held_item(&state, user_id, "wand").await? else {
+        state.slack_client.send_dm(user_id, "You can zap only with a wand you're holding.").await?;
+        return Ok(());
+    };
+    if !has_castable_spell(&object) {
+        state.slack_client.send_dm(user_id, "Nothing happens.").await?;
+        return Ok(());
+    }
+
+    let me = Combatant::Player(player.slack_user_id.clone());
+    let target = if args.is_empty() {
+        combat::fighting(&state, &me)
+    } else {
+        super::skill::find_in_room(&state, &player, &room_id, args).await?
+    };
+    let Some(target) = target else {
+        let message = if args.is_empty() { "Zap whom or what?" } else { "You can't find it." };
+        state.slack_client.send_dm(user_id, message).await?;
+        return Ok(());
+    };
+    let Some(victim) = Fighter::load(&state, &target).await? else {
+        return Ok(());
+    };
+
+    if magic::charges_left(&object, &instance) > 0 {
+        super::broadcast_room_action(
+            &state,
+            &room_id,
+            &format!("_{} zaps {} with {}._", player.name, victim.short, object.short_description),
+            Some(user_id),
+            Some(&format!("_You zap {} with {}._", victim.short, object.short_description)),
+        ).await?;
+        cast_spells(&state, user_id, &room_id, &object, &me, Some(&target)).await?;
+    }
+    use_charge(&state, &player, &room_id, &instance, &object, "explodes into fragments").await
+}
+
+async fn brandish(state: Arc<AppState>, user_id: &str, player: Player) -> Result<()> {
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let Some((instance, object)) = held_item(&state, user_id, "staff").await? else {
+        state.slack_client.send_dm(user_id, "You can brandish only with a staff you're holding.").await?;
+        return Ok(());
+    };
+    if !has_castable_spell(&object) {
+        state.slack_client.send_dm(user_id, "Nothing happens.").await?;
+        return Ok(());
+    }
+
+    if magic::charges_left(&object, &instance) > 0 {
+        super::broadcast_room_action(
+            &state,
+            &room_id,
+            &format!("_{} brandishes {}._", player.name, object.short_description),
+            Some(user_id),
+            Some(&format!("_You brandish {}._", object.short_description)),
+        ).await?;
+
+        let me = Combatant::Player(player.slack_user_id.clone());
+        let mut present: Vec<Combatant> = PlayerRepository::new(state.db_pool.clone())
+            .get_players_in_room(&room_id)
+            .await?
+            .into_iter()
+            .map(|p| Combatant::Player(p.slack_user_id))
+            .collect();
+        present.extend(
+            MobileInstanceRepository::new(state.db_pool.clone())
+                .get_in_room(&room_id)
+                .await?
+                .into_iter()
+                .map(|m| Combatant::Mobile(m.id)),
+        );
+
+        // Harmful spells strike the mobiles present, helpful ones the players, as in ROM
+        for name in object.spells() {
+            let Some(skill) = skills::lookup(&name) else { continue };
+            for victim in &present {
+                let aimed = match skill.target {
+                    Target::CharOffensive => matches!(victim, Combatant::Mobile(_)),
+                    Target::CharDefensive => matches!(victim, Combatant::Player(_)),
+                    _ => *victim == me,
+                };
+                if aimed {
+                    cast_spell(&state, user_id, &room_id, skill, object.value0, &me, victim).await?;
+                }
+            }
+        }
+    }
+    use_charge(&state, &player, &room_id, &instance, &object, "blazes bright and is gone").await
+}
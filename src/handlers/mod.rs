@@ -2,6 +2,7 @@ mod look;
 mod character;
 mod events;
 mod dig;
+mod extra;
 mod r#move;
 mod attach;
 mod import;
@@ -11,12 +12,23 @@ mod equipment;
 mod social;
 mod char_creation;
 mod communication;
+mod shop;
+mod score;
+mod combat;
+mod position;
+mod skill;
+mod door;
+mod container;
+mod consume;
+mod magic_item;
 
 pub use events::handle_events;
 
 use crate::AppState;
 use crate::slack::SlashCommand;
 use crate::db::player::PlayerRepository;
+use crate::models::Position;
+use crate::game::door::DoorAction;
 use axum::{
     extract::State,
     response::{IntoResponse, Response},
@@ -36,6 +48,24 @@ pub async fn broadcast_room_action(
     message: &str,
     actor_user_id: Option<&str>,
     actor_message: Option<&str>,
+) -> Result<()> {
+    let personal: Vec<(&str, &str)> = actor_user_id
+        .map(|actor_id| (actor_id, actor_message.unwrap_or(message)))
+        .into_iter()
+        .collect();
+
+    broadcast_room_message(state, room_channel_id, message, &personal).await
+}
+
+/// Broadcast a public action to a room, giving some players their own version of it
+///
+/// `personal` pairs a player's Slack user ID with the message they should see instead
+/// of `message` (e.g. the attacker and the victim of a blow each get a "you" version).
+pub async fn broadcast_room_message(
+    state: &Arc<AppState>,
+    room_channel_id: &str,
+    message: &str,
+    personal: &[(&str, &str)],
 ) -> Result<()> {
     use crate::db::room::RoomRepository;
 
@@ -78,19 +108,11 @@ pub async fn broadcast_room_action(
     let players_in_room = player_repo.get_players_in_room(room_channel_id).await?;
 
     for player in players_in_room {
-        // Determine which message to send to this player
-        let player_message = if let Some(actor_id) = actor_user_id {
-            if player.slack_user_id == actor_id {
-                // This is the actor - send the first-person message
-                actor_message.unwrap_or(message)
-            } else {
-                // This is another player - send the third-person message
-                message
-            }
-        } else {
-            // No actor specified - send the same message to everyone
-            message
-        };
+        // Players with a personal version get that, everyone else the third-person message
+        let player_message = personal.iter()
+            .find(|(user_id, _)| *user_id == player.slack_user_id)
+            .map(|(_, personal_message)| *personal_message)
+            .unwrap_or(message);
 
         // Send the action as a DM so it appears in their SlackMUD conversation
         let _ = state.slack_client.send_dm(&player.slack_user_id, player_message).await;
@@ -148,7 +170,9 @@ pub async fn handle_slash_command(
         "look" | "l" => look::handle_look(state, command).await,
         "exits" => handle_exits(state, command).await,
         "character" | "char" => character::handle_character(state, command).await,
+        "score" | "sc" => score::handle_score(state, command).await,
         "dig" => dig::handle_dig(state, command.clone(), args).await,
+        "extra" => extra::handle_extra(state, command.clone(), args).await,
         "attach" => attach::handle_attach(state, command.clone(), args).await,
         "detach" => attach::handle_detach(state, command.clone()).await,
         "import-area" => import::handle_import_area(state, command.clone(), args).await,
@@ -163,17 +187,53 @@ pub async fn handle_slash_command(
         "west" | "w" => r#move::handle_move(state, command.clone(), "west").await,
         "up" | "u" => r#move::handle_move(state, command.clone(), "up").await,
         "down" | "d" => r#move::handle_move(state, command.clone(), "down").await,
+        "recall" => r#move::handle_recall(state, command).await,
+        // Door commands
+        "open" => door::handle_door(state, command.clone(), args, DoorAction::Open).await,
+        "close" => door::handle_door(state, command.clone(), args, DoorAction::Close).await,
+        "lock" => door::handle_door(state, command.clone(), args, DoorAction::Lock).await,
+        "unlock" => door::handle_door(state, command.clone(), args, DoorAction::Unlock).await,
+        "pick" => door::handle_door(state, command.clone(), args, DoorAction::Pick).await,
         // Item commands
         "get" | "take" => item::handle_get(state, command.clone(), args).await,
         "drop" => item::handle_drop(state, command.clone(), args).await,
+        "put" => container::handle_put(state, command.clone(), args).await,
         "give" => item::handle_give(state, command.clone(), args).await,
         "inventory" | "inv" | "i" => item::handle_inventory(state, command).await,
         "manifest" => item::handle_manifest(state, command.clone(), args).await,
+        // Item use commands
+        "eat" => consume::handle_eat(state, command.clone(), args).await,
+        "drink" => consume::handle_drink(state, command.clone(), args).await,
+        "fill" => consume::handle_fill(state, command.clone(), args).await,
+        "pour" => consume::handle_pour(state, command.clone(), args).await,
+        "quaff" => magic_item::handle_quaff(state, command.clone(), args).await,
+        "recite" => magic_item::handle_recite(state, command.clone(), args).await,
+        "zap" => magic_item::handle_zap(state, command.clone(), args).await,
+        "brandish" => magic_item::handle_brandish(state, command).await,
         // Equipment commands
         "wear" => equipment::handle_wear(state, command.clone(), args).await,
         "wield" => equipment::handle_wield(state, command.clone(), args).await,
         "remove" | "rem" => equipment::handle_remove(state, command.clone(), args).await,
         "equipment" | "eq" => equipment::handle_equipment(state, command).await,
+        // Shop commands
+        "list" => shop::handle_list(state, command).await,
+        "buy" => shop::handle_buy(state, command.clone(), args).await,
+        "sell" => shop::handle_sell(state, command.clone(), args).await,
+        "value" => shop::handle_value(state, command.clone(), args).await,
+        "repair" => shop::handle_repair(state, command.clone(), args).await,
+        // Combat commands
+        "kill" | "k" => combat::handle_kill(state, command.clone(), args).await,
+        "flee" => combat::handle_flee(state, command).await,
+        "consider" | "con" => combat::handle_consider(state, command.clone(), args).await,
+        // Position commands
+        "rest" => position::handle_position(state, command, Position::Resting).await,
+        "sit" => position::handle_position(state, command, Position::Sitting).await,
+        "sleep" => position::handle_position(state, command, Position::Sleeping).await,
+        "stand" => position::handle_position(state, command, Position::Standing).await,
+        "wake" => position::handle_wake(state, command.clone(), args).await,
+        // Skill commands
+        "cast" => skill::handle_cast(state, command.clone(), args).await,
+        "practice" | "prac" => skill::handle_practice(state, command.clone(), args).await,
         // Communication commands
         "say" => communication::handle_say(state, command.clone(), args).await,
         "tell" => communication::handle_tell(state, command.clone(), args).await,
@@ -235,6 +295,11 @@ async fn handle_exits(state: Arc<AppState>, command: SlashCommand) -> anyhow::Re
     } else {
         let mut msg = format!("*Exits from #{}:*\n", room_name);
         for exit in &exits {
+            // A closed door hides where it leads
+            if exit.is_closed() {
+                msg.push_str(&format!("• *{}* - _the {} is closed_\n", exit.direction, exit.door_name()));
+                continue;
+            }
             let target_room_name = if let Some(room) = room_repo.get_by_channel_id(&exit.to_room_id).await? {
                 room.channel_name
             } else {
@@ -259,16 +324,46 @@ async fn handle_help(state: Arc<AppState>, command: SlashCommand) -> anyhow::Res
     let mut help_text = String::from("*SlackMUD Commands*\n\n");
     help_text.push_str("• `/mud look` or `/mud l` - Look around the current room\n");
     help_text.push_str("• `/mud look <item>` - Examine an item in detail\n");
+    help_text.push_str("• `/mud look in <container>` - See what's inside a container\n");
     help_text.push_str("• `/mud exits` - Show available exits\n");
     help_text.push_str("• `/mud n/s/e/w/u/d` or `/mud north/south/east/west/up/down` - Move in a direction\n");
+    help_text.push_str("• `/mud recall` - Pray to be taken back to the temple\n");
+    help_text.push_str("• `/mud open/close <door|direction|container>` - Open or close a door or container\n");
+    help_text.push_str("• `/mud lock/unlock <door|direction|container>` - Lock or unlock a door or container with its key\n");
+    help_text.push_str("• `/mud pick <door|direction|container>` - Try to pick a lock\n");
     help_text.push_str("• `/mud get <item>` or `/mud take <item>` - Pick up an item\n");
+    help_text.push_str("• `/mud get all` - Pick up everything you can\n");
+    help_text.push_str("• `/mud get <item|all> <container>` - Take things out of a container or corpse\n");
+    help_text.push_str("• `/mud put <item|all> <container>` - Put things in a container\n");
     help_text.push_str("• `/mud drop <item>` - Drop an item\n");
     help_text.push_str("• `/mud give <item> <player>` - Give an item to another player\n");
+    help_text.push_str("• `/mud give <n> gold|silver <player>` - Give coins to another player\n");
     help_text.push_str("• `/mud inventory` or `/mud i` - Show what you're carrying\n");
+    help_text.push_str("• `/mud eat <food|pill>` - Eat something you're carrying\n");
+    help_text.push_str("• `/mud drink [container]` - Drink from a container, or from a fountain here\n");
+    help_text.push_str("• `/mud fill <container>` - Fill a drink container at a fountain\n");
+    help_text.push_str("• `/mud pour <container> out|<container>` - Empty a drink container or pour it into another\n");
+    help_text.push_str("• `/mud quaff <potion>` - Drink a potion\n");
+    help_text.push_str("• `/mud recite <scroll> [target]` - Read a scroll aloud\n");
+    help_text.push_str("• `/mud zap [target]` - Use the wand you're holding\n");
+    help_text.push_str("• `/mud brandish` - Use the staff you're holding on everyone here\n");
     help_text.push_str("• `/mud wear <item>` - Wear armor or clothing\n");
     help_text.push_str("• `/mud wield <weapon>` - Wield a weapon\n");
     help_text.push_str("• `/mud remove <item>` - Remove equipped item\n");
     help_text.push_str("• `/mud equipment` or `/mud eq` - Show your equipment\n");
+    help_text.push_str("• `/mud list` - List what a shopkeeper has for sale\n");
+    help_text.push_str("• `/mud buy <item>` - Buy an item from a shopkeeper\n");
+    help_text.push_str("• `/mud sell <item>` - Sell an item to a shopkeeper\n");
+    help_text.push_str("• `/mud value <item>` - Ask a shopkeeper what an item is worth\n");
+    help_text.push_str("• `/mud repair <item>` - Have an armourer or weaponsmith mend worn gear\n");
+    help_text.push_str("• `/mud kill <target>` or `/mud k <target>` - Attack a creature\n");
+    help_text.push_str("• `/mud flee` - Try to escape from a fight\n");
+    help_text.push_str("• `/mud consider <target>` or `/mud con <target>` - Judge how tough a creature is\n");
+    help_text.push_str("• `/mud rest`, `/mud sit`, `/mud sleep`, `/mud stand` - Change position (resting and sleeping heal faster)\n");
+    help_text.push_str("• `/mud wake [target]` - Wake up, or wake someone else\n");
+    help_text.push_str("• `/mud cast '<spell>' [target]` - Cast a spell (quote spell names with spaces)\n");
+    help_text.push_str("• `/mud practice [skill]` - List your skills, or practice one at a guildmaster\n");
+    help_text.push_str("• `/mud score` - Show your stats and purse\n");
     help_text.push_str("• `/mud character` or `/mud char` - Customize your character (class, race, gender)\n");
     help_text.push_str("• `/mud say <message>` - Say something to everyone in the room\n");
     help_text.push_str("• `/mud tell <player> <message>` - Send a private message to another player\n");
@@ -279,6 +374,7 @@ async fn handle_help(state: Arc<AppState>, command: SlashCommand) -> anyhow::Res
     if is_wizard {
         help_text.push_str("\n*Wizard Commands:*\n");
         help_text.push_str("• `/mud dig <direction> #channel` - Create an exit to another room\n");
+        help_text.push_str("• `/mud extra [<keywords> = <description> | remove <keyword>]` - List or edit this room's extra descriptions\n");
         help_text.push_str("• `/mud attach #channel` - Attach current room to a Slack channel\n");
         help_text.push_str("• `/mud detach` - Detach current room from its Slack channel\n");
         help_text.push_str("• `/mud import-area <url>` - Import MUD area file (creates virtual rooms)\n");
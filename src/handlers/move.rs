@@ -3,10 +3,26 @@ use crate::slack::SlashCommand;
 use crate::db::player::PlayerRepository;
 use crate::db::room::RoomRepository;
 use crate::db::exit::ExitRepository;
+use crate::db::mobile::MobileInstanceRepository;
 use crate::models::exit::is_valid_direction;
+use crate::models::{Player, Room};
+use crate::area::types::{RoomFlags, SectorType};
+use crate::game::combat::{self, Combatant};
+use crate::game::experience;
+use rand::Rng;
 use std::sync::Arc;
 use anyhow::Result;
 
+const WIZARD_LEVEL: i32 = 50;
+/// Players above this level are too experienced for newbie-only rooms (ROM's level 5)
+const NEWBIE_LEVEL: i32 = 5;
+/// Where recall takes players (ROM's ROOM_VNUM_TEMPLE)
+const TEMPLE_ROOM: &str = "vnum_3001";
+/// Percent chance of recalling out of a fight
+const RECALL_FROM_COMBAT_CHANCE: i32 = 40;
+/// Experience lost by recalling out of a fight
+const RECALL_FROM_COMBAT_EXP: i32 = 25;
+
 pub async fn handle_move(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
     let player_repo = PlayerRepository::new(state.db_pool.clone());
     let room_repo = RoomRepository::new(state.db_pool.clone());
@@ -17,7 +33,7 @@ pub async fn handle_move(state: Arc<AppState>, command: SlashCommand, args: &str
     let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
 
     // Check if player has a current room
-    let current_room_id = match player.current_channel_id {
+    let current_room_id = match player.current_channel_id.clone() {
         Some(id) => id,
         None => {
             state.slack_client.send_dm(
@@ -28,6 +44,18 @@ pub async fn handle_move(state: Arc<AppState>, command: SlashCommand, args: &str
         }
     };
 
+    // Can't just walk out of a fight
+    if crate::game::combat::fighting(&state, &Combatant::Player(player.slack_user_id.clone())).is_some() {
+        state.slack_client.send_dm(&command.user_id, "No way!  You are still fighting!").await?;
+        return Ok(());
+    }
+
+    // Or get anywhere without getting up first
+    if let Some(excuse) = player.position().excuse() {
+        state.slack_client.send_dm(&command.user_id, excuse).await?;
+        return Ok(());
+    }
+
     // Parse direction from args
     let direction = args.trim().to_lowercase();
 
@@ -60,6 +88,11 @@ pub async fn handle_move(state: Arc<AppState>, command: SlashCommand, args: &str
         }
     };
 
+    if exit.is_closed() {
+        state.slack_client.send_dm(&command.user_id, &format!("The {} is closed.", exit.door_name())).await?;
+        return Ok(());
+    }
+
     // Get current and destination room info
     let current_room = room_repo.get_by_channel_id(&current_room_id).await?;
     let current_room_name = current_room.as_ref().map(|r| r.channel_name.as_str()).unwrap_or("unknown");
@@ -67,6 +100,21 @@ pub async fn handle_move(state: Arc<AppState>, command: SlashCommand, args: &str
     let destination_room = room_repo.get_by_channel_id(&exit.to_room_id).await?;
     let destination_room_name = destination_room.as_ref().map(|r| r.channel_name.as_str()).unwrap_or("unknown");
 
+    // Some rooms only let certain players in, or only a few at a time
+    if let Some(room) = &destination_room {
+        if let Some(refusal) = entry_refusal(&state, &player, room).await? {
+            state.slack_client.send_dm(&command.user_id, refusal).await?;
+            return Ok(());
+        }
+    }
+
+    // Every step costs movement, more over rough ground
+    let cost = movement_cost(current_room.as_ref(), destination_room.as_ref());
+    if !player_repo.spend_movement(&player.slack_user_id, cost).await? {
+        state.slack_client.send_dm(&command.user_id, "You are too exhausted.").await?;
+        return Ok(());
+    }
+
     // Post departure message in current room (broadcasts to channel and players in room via DM)
     let departure_third_person = format!("_{} heads {}._", player.name, direction);
     let departure_first_person = format!("_You head {}._", direction);
@@ -98,6 +146,12 @@ pub async fn handle_move(state: Arc<AppState>, command: SlashCommand, args: &str
         &format!("You travel {} from #{} to #{}.", direction, current_room_name, destination_room_name)
     ).await?;
 
+    // Somewhere new is worth a little experience
+    if player_repo.mark_visited(&player.slack_user_id, &exit.to_room_id).await? {
+        state.slack_client.send_dm(&command.user_id, &format!("You gain {} experience for exploring somewhere new.", experience::EXPLORE_EXP)).await?;
+        experience::gain_exp(&state, &player.slack_user_id, experience::EXPLORE_EXP).await?;
+    }
+
     // Automatically show the new room description
     super::look::handle_look(state, command).await?;
 
@@ -118,7 +172,7 @@ pub async fn handle_move_dm(
     let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
 
     // Check if player has a current room
-    let current_room_id = match player.current_channel_id {
+    let current_room_id = match player.current_channel_id.clone() {
         Some(id) => id,
         None => {
             state.slack_client.send_dm(
@@ -129,6 +183,18 @@ pub async fn handle_move_dm(
         }
     };
 
+    // Can't just walk out of a fight
+    if crate::game::combat::fighting(&state, &Combatant::Player(player.slack_user_id.clone())).is_some() {
+        state.slack_client.send_dm(&user_id, "No way!  You are still fighting!").await?;
+        return Ok(());
+    }
+
+    // Or get anywhere without getting up first
+    if let Some(excuse) = player.position().excuse() {
+        state.slack_client.send_dm(&user_id, excuse).await?;
+        return Ok(());
+    }
+
     // Parse direction from args
     let direction = args.trim().to_lowercase();
 
@@ -161,6 +227,11 @@ pub async fn handle_move_dm(
         }
     };
 
+    if exit.is_closed() {
+        state.slack_client.send_dm(&user_id, &format!("The {} is closed.", exit.door_name())).await?;
+        return Ok(());
+    }
+
     // Get current and destination room info
     let current_room = room_repo.get_by_channel_id(&current_room_id).await?;
     let current_room_name = current_room.as_ref().map(|r| r.channel_name.as_str()).unwrap_or("unknown");
@@ -168,6 +239,21 @@ pub async fn handle_move_dm(
     let destination_room = room_repo.get_by_channel_id(&exit.to_room_id).await?;
     let destination_room_name = destination_room.as_ref().map(|r| r.channel_name.as_str()).unwrap_or("unknown");
 
+    // Some rooms only let certain players in, or only a few at a time
+    if let Some(room) = &destination_room {
+        if let Some(refusal) = entry_refusal(&state, &player, room).await? {
+            state.slack_client.send_dm(&user_id, refusal).await?;
+            return Ok(());
+        }
+    }
+
+    // Every step costs movement, more over rough ground
+    let cost = movement_cost(current_room.as_ref(), destination_room.as_ref());
+    if !player_repo.spend_movement(&player.slack_user_id, cost).await? {
+        state.slack_client.send_dm(&user_id, "You are too exhausted.").await?;
+        return Ok(());
+    }
+
     // Post departure message in current room (broadcasts to channel and players in room via DM)
     let departure_third_person = format!("_{} heads {}._", player.name, direction);
     let departure_first_person = format!("_You head {}._", direction);
@@ -199,8 +285,129 @@ pub async fn handle_move_dm(
         &format!("You travel {} from #{} to #{}.", direction, current_room_name, destination_room_name)
     ).await?;
 
+    // Somewhere new is worth a little experience
+    if player_repo.mark_visited(&player.slack_user_id, &exit.to_room_id).await? {
+        state.slack_client.send_dm(&user_id, &format!("You gain {} experience for exploring somewhere new.", experience::EXPLORE_EXP)).await?;
+        experience::gain_exp(&state, &player.slack_user_id, experience::EXPLORE_EXP).await?;
+    }
+
     // Automatically show the new room description
     super::look::handle_look_dm(state, user_id, player.name, "").await?;
 
     Ok(())
 }
+
+/// Handle recall command - pray to be taken back to the temple
+pub async fn handle_recall(state: Arc<AppState>, command: SlashCommand) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    recall(state, &command.user_id, player).await
+}
+
+/// Handle recall command from DM
+pub async fn handle_recall_dm(state: Arc<AppState>, user_id: String, user_name: String) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    recall(state, &user_id, player).await
+}
+
+/// Movement points for a step between two rooms: the average of what each one's ground takes
+fn movement_cost(from: Option<&Room>, to: Option<&Room>) -> i32 {
+    let loss = |room: Option<&Room>| room.map_or(SectorType::Inside, Room::sector).movement_loss();
+    (loss(from) + loss(to)) / 2
+}
+
+/// Why a player can't go into a room, if they can't (ROM's can_see_room and room_is_private)
+pub(super) async fn entry_refusal(state: &Arc<AppState>, player: &Player, room: &Room) -> Result<Option<&'static str>> {
+    if player.level >= WIZARD_LEVEL {
+        return Ok(None);
+    }
+
+    let flags = room.flags();
+    if flags.intersects(RoomFlags::IMP_ONLY | RoomFlags::GODS_ONLY)
+        || (flags.contains(RoomFlags::HEROES_ONLY) && player.level < experience::HERO_LEVEL)
+        || (flags.contains(RoomFlags::NEWBIES_ONLY) && player.level > NEWBIE_LEVEL)
+    {
+        return Ok(Some("Alas, you cannot go that way."));
+    }
+
+    // Private rooms hold two people, solitary ones just one
+    let capacity = if flags.contains(RoomFlags::SOLITARY) {
+        1
+    } else if flags.contains(RoomFlags::PRIVATE) {
+        2
+    } else {
+        return Ok(None);
+    };
+    let players = PlayerRepository::new(state.db_pool.clone()).get_players_in_room(&room.channel_id).await?;
+    let mobiles = MobileInstanceRepository::new(state.db_pool.clone()).get_in_room(&room.channel_id).await?;
+    if players.len() + mobiles.len() >= capacity {
+        return Ok(Some("That room is private right now."));
+    }
+
+    Ok(None)
+}
+
+async fn recall(state: Arc<AppState>, user_id: &str, player: Player) -> Result<()> {
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let me = Combatant::Player(player.slack_user_id.clone());
+    if let Some(excuse) = combat::effective_position(&state, &me, player.position()).excuse() {
+        state.slack_client.send_dm(user_id, excuse).await?;
+        return Ok(());
+    }
+
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &format!("_{} prays for transportation!_", player.name),
+        Some(user_id),
+        Some("_You pray for transportation!_"),
+    ).await?;
+
+    let room_repo = RoomRepository::new(state.db_pool.clone());
+    if room_repo.get_by_channel_id(TEMPLE_ROOM).await?.is_none() {
+        state.slack_client.send_dm(user_id, "You are completely lost.").await?;
+        return Ok(());
+    }
+    if room_id == TEMPLE_ROOM {
+        return Ok(());
+    }
+
+    let here = room_repo.get_by_channel_id(&room_id).await?;
+    if here.is_some_and(|room| room.flags().contains(RoomFlags::NO_RECALL)) {
+        state.slack_client.send_dm(user_id, "Mota has forsaken you.").await?;
+        return Ok(());
+    }
+
+    if combat::fighting(&state, &me).is_some() {
+        if rand::thread_rng().gen_range(1..=100) > RECALL_FROM_COMBAT_CHANCE {
+            state.slack_client.send_dm(user_id, "You failed!").await?;
+            return Ok(());
+        }
+        // Like fleeing, this never costs a level, so the loss can come to less
+        let lost = -experience::gain_exp(&state, &player.slack_user_id, -RECALL_FROM_COMBAT_EXP).await?;
+        state.slack_client.send_dm(user_id, &format!("You recall from combat!  You lose {} exps.", lost)).await?;
+        combat::stop_fighting(&state, &me, true);
+    }
+
+    // The trip takes half the player's remaining movement
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    player_repo.halve_movement(&player.slack_user_id).await?;
+
+    // Announced in each room while the player isn't in it, so they only see where they end up
+    super::broadcast_room_action(&state, TEMPLE_ROOM, &format!("_{} appears in the room._", player.name), None, None).await?;
+    player_repo.update_current_channel(&player.slack_user_id, TEMPLE_ROOM).await?;
+    super::broadcast_room_action(&state, &room_id, &format!("_{} disappears._", player.name), None, None).await?;
+
+    super::look::handle_look_dm(state, user_id.to_string(), player.name, "").await
+}
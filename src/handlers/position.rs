@@ -0,0 +1,223 @@
+use crate::AppState;
+use crate::slack::SlashCommand;
+use crate::db::player::PlayerRepository;
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::game::combat::{self, Combatant};
+use crate::models::{Player, Position};
+use std::sync::Arc;
+use anyhow::Result;
+
+/// What happens when someone tries to change position
+#[derive(Debug, PartialEq, Eq)]
+enum Change {
+    /// They can't; tell them why
+    Refuse(&'static str),
+    /// They do, telling them and (optionally) the room what they did
+    Move {
+        to_char: &'static str,
+        to_room: Option<&'static str>,
+    },
+}
+
+/// Work out a position change, with ROM's do_rest/do_sit/do_sleep/do_stand messages
+fn change(from: Position, to: Position) -> Change {
+    use Change::{Move, Refuse};
+    use Position::*;
+
+    if from < Sleeping {
+        return Refuse(from.excuse().unwrap_or("You can't do that right now."));
+    }
+
+    match (to, from) {
+        (Resting, Fighting) | (Sleeping, Fighting) | (Standing, Fighting) => Refuse("You are already fighting!"),
+        (Sitting, Fighting) => Refuse("Maybe you should finish this fight first?"),
+
+        (Resting, Resting) => Refuse("You are already resting."),
+        (Resting, Sleeping) => Move { to_char: "You wake up and start resting.", to_room: Some("wakes up and starts resting.") },
+        (Resting, Sitting) => Move { to_char: "You rest.", to_room: Some("rests.") },
+        (Resting, _) => Move { to_char: "You rest.", to_room: Some("sits down and rests.") },
+
+        (Sitting, Sitting) => Refuse("You are already sitting down."),
+        (Sitting, Sleeping) => Move { to_char: "You wake and sit up.", to_room: Some("wakes and sits up.") },
+        (Sitting, Resting) => Move { to_char: "You stop resting.", to_room: None },
+        (Sitting, _) => Move { to_char: "You sit down.", to_room: Some("sits down on the ground.") },
+
+        (Sleeping, Sleeping) => Refuse("You are already sleeping."),
+        (Sleeping, _) => Move { to_char: "You go to sleep.", to_room: Some("goes to sleep.") },
+
+        (Standing, Standing) => Refuse("You are already standing."),
+        (Standing, Sleeping) => Move { to_char: "You wake and stand up.", to_room: Some("wakes and stands up.") },
+        (Standing, _) => Move { to_char: "You stand up.", to_room: Some("stands up.") },
+
+        _ => Refuse("You can't do that."),
+    }
+}
+
+/// Handle rest, sit, sleep and stand - `to` is the position the command asks for
+pub async fn handle_position(state: Arc<AppState>, command: SlashCommand, to: Position) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    change_position(state, &command.user_id, player, to).await
+}
+
+/// Handle rest, sit, sleep and stand from DM
+pub async fn handle_position_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    to: Position,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    change_position(state, &user_id, player, to).await
+}
+
+/// Handle wake command - stand up, or wake someone else in the room
+pub async fn handle_wake(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    wake(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle wake command from DM
+pub async fn handle_wake_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    wake(state, &user_id, player, args.trim()).await
+}
+
+async fn change_position(state: Arc<AppState>, user_id: &str, player: Player, to: Position) -> Result<()> {
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let me = Combatant::Player(player.slack_user_id.clone());
+    let from = combat::effective_position(&state, &me, player.position());
+
+    match change(from, to) {
+        Change::Refuse(message) => {
+            state.slack_client.send_dm(user_id, message).await?;
+        }
+        Change::Move { to_char, to_room } => {
+            combat::set_position(&state, &me, to).await?;
+            match to_room {
+                Some(to_room) => {
+                    super::broadcast_room_action(
+                        &state,
+                        &room_id,
+                        &format!("_{} {}_", player.name, to_room),
+                        Some(user_id),
+                        Some(&format!("_{}_", to_char)),
+                    ).await?;
+                }
+                None => state.slack_client.send_dm(user_id, &format!("_{}_", to_char)).await?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn wake(state: Arc<AppState>, user_id: &str, player: Player, target: &str) -> Result<()> {
+    if target.is_empty() {
+        return change_position(state, user_id, player, Position::Standing).await;
+    }
+
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    if player.position() <= Position::Sleeping {
+        state.slack_client.send_dm(user_id, "You are asleep yourself!").await?;
+        return Ok(());
+    }
+
+    // Find the sleeper: another player first, then a mobile
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let sleeper = player_repo.get_players_in_room(&room_id).await?
+        .into_iter()
+        .find(|p| p.name.eq_ignore_ascii_case(target))
+        .map(|p| (Combatant::Player(p.slack_user_id.clone()), p.name.clone(), p.name.clone(), p.position()));
+
+    let sleeper = match sleeper {
+        Some(sleeper) => Some(sleeper),
+        None => {
+            let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+            let mobile_repo = MobileRepository::new(state.db_pool.clone());
+            let mut found = None;
+            for instance in mobile_instance_repo.get_in_room(&room_id).await? {
+                if let Some(mobile) = mobile_repo.get_by_vnum(instance.mobile_vnum).await? {
+                    if mobile.matches_keyword(target) {
+                        found = Some((
+                            Combatant::Mobile(instance.id),
+                            mobile.short_description.clone(),
+                            mobile.display_name(),
+                            instance.position(),
+                        ));
+                        break;
+                    }
+                }
+            }
+            found
+        }
+    };
+
+    let Some((who, name, display_name, position)) = sleeper else {
+        state.slack_client.send_dm(user_id, "They aren't here.").await?;
+        return Ok(());
+    };
+
+    if position > Position::Sleeping {
+        state.slack_client.send_dm(user_id, &format!("{} is already awake.", display_name)).await?;
+        return Ok(());
+    }
+
+    combat::set_position(&state, &who, Position::Standing).await?;
+
+    let mut personal = vec![(user_id, format!("_You wake {}._", name))];
+    if let Combatant::Player(id) = &who {
+        personal.push((id.as_str(), format!("_{} wakes you._", player.name)));
+    }
+    let personal: Vec<(&str, &str)> = personal.iter().map(|(id, m)| (*id, m.as_str())).collect();
+    super::broadcast_room_message(
+        &state,
+        &room_id,
+        &format!("_{} wakes {}._", player.name, name),
+        &personal,
+    ).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_changes() {
+        assert_eq!(change(Position::Standing, Position::Standing), Change::Refuse("You are already standing."));
+        assert_eq!(change(Position::Fighting, Position::Sleeping), Change::Refuse("You are already fighting!"));
+        assert_eq!(
+            change(Position::Sleeping, Position::Standing),
+            Change::Move { to_char: "You wake and stand up.", to_room: Some("wakes and stands up.") }
+        );
+        assert_eq!(change(Position::Resting, Position::Sitting), Change::Move { to_char: "You stop resting.", to_room: None });
+    }
+}
@@ -0,0 +1,97 @@
+use crate::AppState;
+use crate::slack::SlashCommand;
+use crate::db::player::PlayerRepository;
+use crate::db::race::RaceRepository;
+use crate::game::affect;
+use crate::game::combat::{self, Combatant};
+use crate::game::condition::{self, Conditions, MAX_CONDITION};
+use crate::game::experience;
+use crate::models::Player;
+use std::sync::Arc;
+use anyhow::Result;
+
+/// Handle score command - show the player's current stats and purse
+pub async fn handle_score(state: Arc<AppState>, command: SlashCommand) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    send_score(state, &command.user_id, &player).await
+}
+
+/// Handle score command from DM
+pub async fn handle_score_dm(state: Arc<AppState>, user_id: String, user_name: String) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    send_score(state, &user_id, &player).await
+}
+
+async fn send_score(state: Arc<AppState>, user_id: &str, player: &Player) -> Result<()> {
+    let race_repo = RaceRepository::new(state.db_pool.clone());
+
+    let class = experience::player_class(&state, player).await?;
+    let class_name = class.as_ref().map(|c| c.name.clone());
+    let race_name = match player.race_id {
+        Some(id) => race_repo.get_by_id(id).await?.map(|r| r.name),
+        None => None,
+    };
+
+    let mut message = format!("*Score for {}*\n", player.name);
+    message.push_str(&format!(
+        "Level {} {} {} ({})\n",
+        player.level,
+        race_name.as_deref().unwrap_or("unknown race"),
+        class_name.as_deref().unwrap_or("adventurer"),
+        player.gender.as_deref().unwrap_or("unknown"),
+    ));
+    message.push_str(&format!(
+        "You have {}/{} hit, {}/{} mana, {}/{} movement.\n",
+        player.hit, player.max_hit, player.mana, player.max_mana, player.movement, player.max_movement,
+    ));
+    let exp_per_level = class.as_ref().map_or(experience::DEFAULT_EXP_PER_LEVEL, |class| class.exp_per_level);
+    if player.level < experience::HERO_LEVEL {
+        message.push_str(&format!(
+            "*Experience:* {} ({} to next level)\n",
+            player.experience_points,
+            experience::exp_for_level(player.level + 1, exp_per_level) - player.experience_points,
+        ));
+    } else {
+        message.push_str(&format!("*Experience:* {}\n", player.experience_points));
+    }
+    let me = Combatant::Player(player.slack_user_id.clone());
+    let position = combat::effective_position(&state, &me, player.position());
+    message.push_str(&format!("You are {}.\n", position.as_str()));
+    message.push_str(&format!("You have {} practice sessions.\n", player.practices));
+    message.push_str(&format!("*Purse:* {} gold, {} silver\n", player.gold, player.silver));
+
+    if condition::is_immune(player.level) {
+        message.push_str("You never hunger or thirst.\n");
+    } else {
+        let conditions = Conditions::of(player);
+        message.push_str(&format!(
+            "*Food:* {}/{}  *Drink:* {}/{}  *Drunk:* {}/{}\n",
+            conditions.hunger, MAX_CONDITION, conditions.thirst, MAX_CONDITION, conditions.drunk, MAX_CONDITION,
+        ));
+        if conditions.is_drunk() {
+            message.push_str("You are drunk.\n");
+        }
+        if conditions.is_parched() {
+            message.push_str("You are thirsty.\n");
+        }
+        if conditions.is_starving() {
+            message.push_str("You are hungry.\n");
+        }
+    }
+
+    let affects = affect::affects_on(&state, &me).await?;
+    if !affects.is_empty() {
+        message.push_str("*You are affected by:*\n");
+        for affect in &affects {
+            message.push_str(&format!("• {}\n", affect::describe(affect)));
+        }
+    }
+
+    state.slack_client.send_dm(user_id, &message).await?;
+    Ok(())
+}
@@ -0,0 +1,575 @@
+use crate::AppState;
+use crate::slack::SlashCommand;
+use crate::db::player::PlayerRepository;
+use crate::db::object::{ObjectRepository, ObjectInstanceRepository};
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::db::shop::ShopRepository;
+use crate::game::carry;
+use crate::models::{Mobile, MobileInstance, Object, ObjectInstance, Player, Shop};
+use std::sync::Arc;
+use anyhow::Result;
+
+/// A shopkeeper standing in the player's room
+struct Keeper {
+    instance: MobileInstance,
+    mobile: Mobile,
+    shop: Shop,
+}
+
+impl Keeper {
+    /// Format something the keeper says to the player
+    fn tells(&self, message: &str) -> String {
+        format!("{} tells you '{}'", self.mobile.display_name(), message)
+    }
+}
+
+/// An item the keeper has for sale
+struct StockItem {
+    instance: ObjectInstance,
+    object: Object,
+    /// Permanent shop stock never runs out; items sold by players do
+    unlimited: bool,
+}
+
+/// Handle list command - show what the shopkeeper sells
+pub async fn handle_list(state: Arc<AppState>, command: SlashCommand) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    list_wares(state, &command.user_id, player).await
+}
+
+/// Handle list command from DM
+pub async fn handle_list_dm(state: Arc<AppState>, user_id: String, user_name: String) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    list_wares(state, &user_id, player).await
+}
+
+/// Handle buy command - buy an item from the shopkeeper
+pub async fn handle_buy(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    let item_name = args.trim();
+    if item_name.is_empty() {
+        state.slack_client.send_dm(
+            &command.user_id,
+            "Usage: `/mud buy <item>`\nExample: `/mud buy bread`"
+        ).await?;
+        return Ok(());
+    }
+
+    buy_item(state, &command.user_id, player, item_name).await
+}
+
+/// Handle buy command from DM
+pub async fn handle_buy_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    let item_name = args.trim();
+    if item_name.is_empty() {
+        state.slack_client.send_dm(
+            &user_id,
+            "Usage: `buy <item>`\nExample: `buy bread`"
+        ).await?;
+        return Ok(());
+    }
+
+    buy_item(state, &user_id, player, item_name).await
+}
+
+/// Handle sell command - sell an item from inventory to the shopkeeper
+pub async fn handle_sell(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    let item_name = args.trim();
+    if item_name.is_empty() {
+        state.slack_client.send_dm(
+            &command.user_id,
+            "Usage: `/mud sell <item>`\nExample: `/mud sell dagger`"
+        ).await?;
+        return Ok(());
+    }
+
+    sell_item(state, &command.user_id, player, item_name, false).await
+}
+
+/// Handle sell command from DM
+pub async fn handle_sell_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    let item_name = args.trim();
+    if item_name.is_empty() {
+        state.slack_client.send_dm(
+            &user_id,
+            "Usage: `sell <item>`\nExample: `sell dagger`"
+        ).await?;
+        return Ok(());
+    }
+
+    sell_item(state, &user_id, player, item_name, false).await
+}
+
+/// Handle value command - ask what the shopkeeper would pay for an item
+pub async fn handle_value(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    let item_name = args.trim();
+    if item_name.is_empty() {
+        state.slack_client.send_dm(
+            &command.user_id,
+            "Usage: `/mud value <item>`\nExample: `/mud value dagger`"
+        ).await?;
+        return Ok(());
+    }
+
+    sell_item(state, &command.user_id, player, item_name, true).await
+}
+
+/// Handle value command from DM
+pub async fn handle_value_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    let item_name = args.trim();
+    if item_name.is_empty() {
+        state.slack_client.send_dm(
+            &user_id,
+            "Usage: `value <item>`\nExample: `value dagger`"
+        ).await?;
+        return Ok(());
+    }
+
+    sell_item(state, &user_id, player, item_name, true).await
+}
+
+/// Handle repair command - have an armourer or weaponsmith mend worn gear
+pub async fn handle_repair(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    let item_name = args.trim();
+    if item_name.is_empty() {
+        state.slack_client.send_dm(
+            &command.user_id,
+            "Usage: `/mud repair <item>`\nExample: `/mud repair shield`"
+        ).await?;
+        return Ok(());
+    }
+
+    repair_item(state, &command.user_id, player, item_name).await
+}
+
+/// Handle repair command from DM
+pub async fn handle_repair_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    let item_name = args.trim();
+    if item_name.is_empty() {
+        state.slack_client.send_dm(
+            &user_id,
+            "Usage: `repair <item>`\nExample: `repair shield`"
+        ).await?;
+        return Ok(());
+    }
+
+    repair_item(state, &user_id, player, item_name).await
+}
+
+/// Find an open shop in the player's room, telling the player if there isn't one
+async fn find_open_keeper(state: &Arc<AppState>, user_id: &str, player: &Player) -> Result<Option<Keeper>> {
+    let room_id = match player.current_channel_id {
+        Some(ref id) => id,
+        None => {
+            state.slack_client.send_dm(
+                user_id,
+                "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+            ).await?;
+            return Ok(None);
+        }
+    };
+
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+    let mobile_repo = MobileRepository::new(state.db_pool.clone());
+    let shop_repo = ShopRepository::new(state.db_pool.clone());
+
+    let mut keeper = None;
+    for instance in mobile_instance_repo.get_in_room(room_id).await? {
+        if let Some(shop) = shop_repo.get_by_keeper(instance.mobile_vnum).await? {
+            if let Some(mobile) = mobile_repo.get_by_vnum(instance.mobile_vnum).await? {
+                keeper = Some(Keeper { instance, mobile, shop });
+                break;
+            }
+        }
+    }
+
+    let Some(keeper) = keeper else {
+        state.slack_client.send_dm(user_id, "You can't do that here.").await?;
+        return Ok(None);
+    };
+
+    if let Some(excuse) = keeper.shop.closed_message(crate::game::time::current_hour()) {
+        state.slack_client.send_dm(user_id, &keeper.tells(excuse)).await?;
+        return Ok(None);
+    }
+
+    Ok(Some(keeper))
+}
+
+/// Everything the keeper has for sale: permanent stock first, then items bought from players
+async fn get_stock(state: &Arc<AppState>, keeper: &Keeper) -> Result<Vec<StockItem>> {
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let shop_stock = object_instance_repo.get_shop_stock(keeper.instance.id).await?;
+    let carried = object_instance_repo.get_carried_by_mobile(keeper.instance.id).await?;
+
+    let mut stock = Vec::new();
+    for (instance, unlimited) in shop_stock.into_iter().map(|i| (i, true))
+        .chain(carried.into_iter().map(|i| (i, false)))
+    {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
+            stock.push(StockItem { instance, object, unlimited });
+        }
+    }
+
+    Ok(stock)
+}
+
+/// Describe an amount of silver as "X gold and Y silver"
+fn format_coins(amount: i32) -> String {
+    match (amount / 100, amount % 100) {
+        (0, silver) => format!("{} silver", silver),
+        (gold, 0) => format!("{} gold", gold),
+        (gold, silver) => format!("{} gold and {} silver", gold, silver),
+    }
+}
+
+async fn list_wares(state: Arc<AppState>, user_id: &str, player: Player) -> Result<()> {
+    let Some(keeper) = find_open_keeper(&state, user_id, &player).await? else {
+        return Ok(());
+    };
+
+    let stock = get_stock(&state, &keeper).await?;
+    if stock.is_empty() {
+        state.slack_client.send_dm(user_id, &keeper.tells("You can't buy anything here.")).await?;
+        return Ok(());
+    }
+
+    // Group identical items, keeping the order they were first seen in
+    let mut rows: Vec<(&StockItem, usize)> = Vec::new();
+    for item in &stock {
+        match rows.iter_mut().find(|(row, _)| row.object.vnum == item.object.vnum && row.unlimited == item.unlimited) {
+            Some((_, count)) => *count += 1,
+            None => rows.push((item, 1)),
+        }
+    }
+
+    let mut message = format!("*{} has for sale:*\n```\n[Lv Price Qty] Item\n", keeper.mobile.display_name());
+    for (item, count) in rows {
+        let qty = if item.unlimited { "--".to_string() } else { format!("{:>2}", count) };
+        message.push_str(&format!(
+            "[{:>2} {:>5} {} ] {}\n",
+            item.object.level,
+            keeper.shop.buy_price(&item.object),
+            qty,
+            item.object.short_description
+        ));
+    }
+    message.push_str("```\n");
+    message.push_str(&format!("_You have {}._", format_coins(player.wealth())));
+
+    state.slack_client.send_dm(user_id, &message).await?;
+    Ok(())
+}
+
+async fn buy_item(state: Arc<AppState>, user_id: &str, player: Player, item_name: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let Some(keeper) = find_open_keeper(&state, user_id, &player).await? else {
+        return Ok(());
+    };
+
+    let stock = get_stock(&state, &keeper).await?;
+    let Some(item) = stock.into_iter().find(|i| i.object.matches_keyword(item_name)) else {
+        state.slack_client.send_dm(user_id, &keeper.tells("I don't sell that -- try 'list'.")).await?;
+        return Ok(());
+    };
+
+    let cost = keeper.shop.buy_price(&item.object);
+    if cost <= 0 {
+        state.slack_client.send_dm(user_id, &keeper.tells("I don't sell that -- try 'list'.")).await?;
+        return Ok(());
+    }
+
+    if player.wealth() < cost {
+        state.slack_client.send_dm(
+            user_id,
+            &keeper.tells(&format!("You can't afford to buy {}.", item.object.short_description))
+        ).await?;
+        return Ok(());
+    }
+
+    if item.object.level > player.level {
+        state.slack_client.send_dm(
+            user_id,
+            &keeper.tells(&format!("You can't use {} yet.", item.object.short_description))
+        ).await?;
+        return Ok(());
+    }
+
+    // It has to fit in what the player can carry, like anything else they pick up
+    let adding = if item.unlimited {
+        carry::new_load(&item.object)
+    } else {
+        carry::load_of(&state, item.instance.id).await?
+    };
+    let carried = carry::carried(&state, &player.slack_user_id).await?;
+    let limits = carry::player_limits(&state, &player).await?;
+    if let Some(overload) = carry::overload(carried, adding, limits) {
+        state.slack_client.send_dm(user_id, &carry::too_much(overload, &item.object.display_name())).await?;
+        return Ok(());
+    }
+
+    // Something the keeper bought from a player is taken off the shelf before paying,
+    // so two buyers can't both pay for it
+    let shelf = (item.instance.location_type.as_str(), item.instance.location_id.as_str());
+    if !item.unlimited
+        && !object_instance_repo.move_if_at(item.instance.id, shelf.0, shelf.1, "player", &player.slack_user_id).await?
+    {
+        state.slack_client.send_dm(user_id, &keeper.tells("I don't sell that -- try 'list'.")).await?;
+        return Ok(());
+    }
+
+    // Paid from the purse as it is now, which may have changed since it was read
+    let (gold, silver) = player.payment(cost);
+    if !player_repo.spend_money(&player.slack_user_id, gold, silver).await? {
+        if !item.unlimited {
+            object_instance_repo.update_location(item.instance.id, shelf.0, shelf.1).await?;
+        }
+        state.slack_client.send_dm(
+            user_id,
+            &keeper.tells(&format!("You can't afford to buy {}.", item.object.short_description))
+        ).await?;
+        return Ok(());
+    }
+
+    if item.unlimited {
+        // Permanent stock: hand over a fresh copy
+        let instance = crate::game::object::fresh(
+            ObjectInstance::new_in_player_inventory(item.object.vnum, player.slack_user_id.clone()),
+            &item.object,
+        );
+        object_instance_repo.create(&instance).await?;
+    }
+
+    let room_id = keeper.instance.room_id.clone();
+    let third_person = format!("_{} buys {}._", player.name, item.object.short_description);
+    let first_person = format!("_You buy {} for {}._", item.object.short_description, format_coins(cost));
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &third_person,
+        Some(user_id),
+        Some(&first_person),
+    ).await?;
+
+    Ok(())
+}
+
+/// Sell an item to the keeper, or just ask its value when `appraise_only` is set
+async fn sell_item(
+    state: Arc<AppState>,
+    user_id: &str,
+    player: Player,
+    item_name: &str,
+    appraise_only: bool,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let Some(keeper) = find_open_keeper(&state, user_id, &player).await? else {
+        return Ok(());
+    };
+
+    // Find the item in the player's inventory
+    let mut found = None;
+    for instance in object_instance_repo.get_in_player_inventory(&player.slack_user_id).await? {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
+            if object.matches_keyword(item_name) {
+                found = Some((instance, object));
+                break;
+            }
+        }
+    }
+
+    let Some((instance, object)) = found else {
+        state.slack_client.send_dm(user_id, &keeper.tells("You don't have that item.")).await?;
+        return Ok(());
+    };
+
+    // Keepers pay less for things they already have plenty of
+    let mut cost = keeper.shop.sell_price(&object);
+    for held in get_stock(&state, &keeper).await? {
+        if held.object.vnum == object.vnum {
+            cost = if held.unlimited { cost / 2 } else { cost * 3 / 4 };
+        }
+    }
+
+    if cost <= 0 {
+        state.slack_client.send_dm(
+            user_id,
+            &format!("{} looks uninterested in {}.", keeper.mobile.display_name(), object.short_description)
+        ).await?;
+        return Ok(());
+    }
+
+    if appraise_only {
+        state.slack_client.send_dm(
+            user_id,
+            &keeper.tells(&format!("I'll give you {} for {}.", format_coins(cost), object.short_description))
+        ).await?;
+        return Ok(());
+    }
+
+    // Paid only once the item has actually changed hands, so it can't be sold twice
+    let keeper_id = keeper.instance.id.to_string();
+    if !object_instance_repo.move_if_at(instance.id, "player", &player.slack_user_id, "mobile", &keeper_id).await? {
+        state.slack_client.send_dm(user_id, &keeper.tells("You don't have that item.")).await?;
+        return Ok(());
+    }
+    player_repo.add_money(&player.slack_user_id, cost / 100, cost % 100).await?;
+
+    let room_id = keeper.instance.room_id.clone();
+    let third_person = format!("_{} sells {}._", player.name, object.short_description);
+    let first_person = format!("_You sell {} for {}._", object.short_description, format_coins(cost));
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &third_person,
+        Some(user_id),
+        Some(&first_person),
+    ).await?;
+
+    Ok(())
+}
+
+/// Mend something the player carries or wears, for the share of its price it's worn down by
+///
+/// Keepers only repair the weapons and armor they deal in, so it's the armourer
+/// and the weaponsmith who do this.
+async fn repair_item(state: Arc<AppState>, user_id: &str, player: Player, item_name: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let object_repo = ObjectRepository::new(state.db_pool.clone());
+    let object_instance_repo = ObjectInstanceRepository::new(state.db_pool.clone());
+
+    let Some(keeper) = find_open_keeper(&state, user_id, &player).await? else {
+        return Ok(());
+    };
+
+    // Find the item in the player's inventory or equipment
+    let mut found = None;
+    let carried = object_instance_repo.get_in_player_inventory(&player.slack_user_id).await?;
+    let worn = object_instance_repo.get_equipped(&player.slack_user_id).await?;
+    for instance in carried.into_iter().chain(worn) {
+        if let Some(object) = object_repo.get_for_instance(&instance).await? {
+            if object.matches_keyword(item_name) {
+                found = Some((instance, object));
+                break;
+            }
+        }
+    }
+
+    let Some((instance, object)) = found else {
+        state.slack_client.send_dm(user_id, &keeper.tells("You don't have that item.")).await?;
+        return Ok(());
+    };
+
+    if !matches!(object.item_type.as_str(), "weapon" | "armor") || !keeper.shop.will_buy(&object) {
+        state.slack_client.send_dm(
+            user_id,
+            &keeper.tells(&format!("I can't repair {}.", object.short_description))
+        ).await?;
+        return Ok(());
+    }
+
+    if instance.current_condition >= 100 {
+        state.slack_client.send_dm(
+            user_id,
+            &keeper.tells(&format!("{} doesn't need repairing.", object.display_name()))
+        ).await?;
+        return Ok(());
+    }
+
+    let cost = crate::game::object::repair_cost(keeper.shop.buy_price(&object), instance.current_condition);
+    let (gold, silver) = player.payment(cost);
+    if player.wealth() < cost || !player_repo.spend_money(&player.slack_user_id, gold, silver).await? {
+        state.slack_client.send_dm(
+            user_id,
+            &keeper.tells(&format!(
+                "It'll cost you {} to repair {}, which you can't afford.",
+                format_coins(cost),
+                object.short_description
+            ))
+        ).await?;
+        return Ok(());
+    }
+    object_instance_repo.update_condition(instance.id, 100).await?;
+
+    let room_id = keeper.instance.room_id.clone();
+    let third_person = format!(
+        "_{} repairs {} for {}._",
+        keeper.mobile.display_name(),
+        object.short_description,
+        player.name
+    );
+    let first_person = format!(
+        "_{} repairs {} for {}._",
+        keeper.mobile.display_name(),
+        object.short_description,
+        format_coins(cost)
+    );
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &third_person,
+        Some(user_id),
+        Some(&first_person),
+    ).await?;
+
+    Ok(())
+}
@@ -0,0 +1,361 @@
+use crate::AppState;
+use crate::slack::SlashCommand;
+use crate::area::types::has_flag;
+use crate::db::player::PlayerRepository;
+use crate::db::mobile::{MobileRepository, MobileInstanceRepository};
+use crate::db::skill::SkillRepository;
+use crate::game::affect;
+use crate::game::combat::{self, Combatant, Fighter};
+use crate::game::experience;
+use crate::game::magic;
+use crate::game::skills::{self, Target, ADEPT};
+use crate::models::{Object, Player};
+use rand::Rng;
+use std::sync::Arc;
+use anyhow::Result;
+
+/// How much one practice session improves a skill
+const PRACTICE_GAIN: i32 = 15;
+
+/// Handle cast command - cast a spell, optionally at a target
+pub async fn handle_cast(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    cast(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle cast command from DM
+pub async fn handle_cast_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    cast(state, &user_id, player, args.trim()).await
+}
+
+/// Handle practice command - list skills, or practice one at a guildmaster
+pub async fn handle_practice(state: Arc<AppState>, command: SlashCommand, args: &str) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let real_name = state.slack_client.get_user_real_name(&command.user_id).await?;
+    let player = player_repo.get_or_create(command.user_id.clone(), real_name).await?;
+
+    practice(state, &command.user_id, player, args.trim()).await
+}
+
+/// Handle practice command from DM
+pub async fn handle_practice_dm(
+    state: Arc<AppState>,
+    user_id: String,
+    user_name: String,
+    args: &str,
+) -> Result<()> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let player = player_repo.get_or_create(user_id.clone(), user_name).await?;
+
+    practice(state, &user_id, player, args.trim()).await
+}
+
+/// Split off the spell name, which needs quotes if it's more than one word ("'cure light' bob")
+fn split_spell(args: &str) -> (&str, &str) {
+    for quote in ['\'', '"'] {
+        if let Some(rest) = args.strip_prefix(quote) {
+            return match rest.split_once(quote) {
+                Some((spell, target)) => (spell.trim(), target.trim()),
+                None => (rest.trim(), ""),
+            };
+        }
+    }
+    match args.split_once(char::is_whitespace) {
+        Some((spell, target)) => (spell, target.trim()),
+        None => (args, ""),
+    }
+}
+
+/// What a spell is cast at
+enum Aim {
+    Char(Combatant),
+    Object(Box<Object>),
+    Room,
+}
+
+/// Find someone in the room by name or keyword: players first, then mobiles the viewer can see
+pub(super) async fn find_in_room(
+    state: &Arc<AppState>,
+    viewer: &Player,
+    room_id: &str,
+    name: &str,
+) -> Result<Option<Combatant>> {
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    if let Some(player) = player_repo.get_players_in_room(room_id).await?
+        .into_iter()
+        .find(|p| p.name.eq_ignore_ascii_case(name))
+    {
+        return Ok(Some(Combatant::Player(player.slack_user_id)));
+    }
+
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+    let mobile_repo = MobileRepository::new(state.db_pool.clone());
+    let sees_invisible = affect::detects_invisible(state, viewer).await?;
+    for instance in mobile_instance_repo.get_in_room(room_id).await? {
+        if let Some(mobile) = mobile_repo.get_by_vnum(instance.mobile_vnum).await? {
+            if mobile.matches_keyword(name) && (sees_invisible || !affect::is_invisible_mobile(&mobile)) {
+                return Ok(Some(Combatant::Mobile(instance.id)));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+async fn cast(state: Arc<AppState>, user_id: &str, player: Player, args: &str) -> Result<()> {
+    let (spell_name, target_name) = split_spell(args);
+    if spell_name.is_empty() {
+        state.slack_client.send_dm(user_id, "Cast which what where?").await?;
+        return Ok(());
+    }
+
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let me = Combatant::Player(player.slack_user_id.clone());
+    let position = combat::effective_position(&state, &me, player.position());
+    if let Some(excuse) = position.excuse() {
+        state.slack_client.send_dm(user_id, excuse).await?;
+        return Ok(());
+    }
+
+    let class_name = experience::player_class(&state, &player).await?.map(|class| class.name).unwrap_or_default();
+    let learned = SkillRepository::new(state.db_pool.clone()).get_learned(&player.slack_user_id).await?;
+    let known = |skill: &skills::Skill| {
+        skill.spell
+            && skill.level_for(&class_name).is_some_and(|level| level <= player.level)
+            && learned.get(skill.name).is_some_and(|&percent| percent > 0)
+    };
+    let Some(skill) = skills::find(spell_name, known).filter(|skill| known(skill)) else {
+        state.slack_client.send_dm(user_id, "You don't know any spells of that name.").await?;
+        return Ok(());
+    };
+
+    if position < skill.min_position {
+        state.slack_client.send_dm(user_id, "You can't concentrate enough.").await?;
+        return Ok(());
+    }
+
+    // Work out who or what it's aimed at
+    let aim = match skill.target {
+        Target::CharOffensive => {
+            let victim = if target_name.is_empty() {
+                combat::fighting(&state, &me)
+            } else {
+                find_in_room(&state, &player, &room_id, target_name).await?
+            };
+            match victim {
+                Some(Combatant::Player(id)) if id == player.slack_user_id => {
+                    state.slack_client.send_dm(user_id, "You can't cast that on yourself.").await?;
+                    return Ok(());
+                }
+                Some(Combatant::Player(_)) => {
+                    state.slack_client.send_dm(user_id, "You can't attack other players.").await?;
+                    return Ok(());
+                }
+                Some(_) if combat::is_safe_room(&state, &room_id).await? => {
+                    state.slack_client.send_dm(user_id, "Not in this room.").await?;
+                    return Ok(());
+                }
+                Some(victim) => Aim::Char(victim),
+                None if target_name.is_empty() => {
+                    state.slack_client.send_dm(user_id, "Cast the spell on whom?").await?;
+                    return Ok(());
+                }
+                None => {
+                    state.slack_client.send_dm(user_id, "They aren't here.").await?;
+                    return Ok(());
+                }
+            }
+        }
+        Target::CharSelf => {
+            if !target_name.is_empty() && !target_name.eq_ignore_ascii_case(&player.name) && target_name != "self" {
+                state.slack_client.send_dm(user_id, "You cannot cast this spell on another.").await?;
+                return Ok(());
+            }
+            Aim::Char(me.clone())
+        }
+        Target::CharDefensive => {
+            if target_name.is_empty() || target_name == "self" {
+                Aim::Char(me.clone())
+            } else {
+                match find_in_room(&state, &player, &room_id, target_name).await? {
+                    Some(victim) => Aim::Char(victim),
+                    None => {
+                        state.slack_client.send_dm(user_id, "They aren't here.").await?;
+                        return Ok(());
+                    }
+                }
+            }
+        }
+        Target::ObjectInventory => {
+            if target_name.is_empty() {
+                state.slack_client.send_dm(user_id, "What should the spell be cast upon?").await?;
+                return Ok(());
+            }
+            match super::consume::find_carried(&state, user_id, target_name).await? {
+                Some((_, object)) => Aim::Object(Box::new(object)),
+                None => {
+                    state.slack_client.send_dm(user_id, "You are not carrying that.").await?;
+                    return Ok(());
+                }
+            }
+        }
+        Target::Room => Aim::Room,
+    };
+
+    let skill_level = skill.level_for(&class_name).unwrap_or(player.level);
+    let mana = skill.mana_cost(player.level, skill_level);
+    if player.mana < mana {
+        state.slack_client.send_dm(user_id, "You don't have enough mana.").await?;
+        return Ok(());
+    }
+
+    let player_repo = PlayerRepository::new(state.db_pool.clone());
+    let percent = learned.get(skill.name).copied().unwrap_or(0);
+    let fumbled = rand::thread_rng().gen_range(1..=100) > percent;
+    // A fumbled spell still costs half the mana
+    let cost = if fumbled { mana / 2 } else { mana };
+    if !player_repo.spend_mana(&player.slack_user_id, cost).await? {
+        state.slack_client.send_dm(user_id, "You don't have enough mana.").await?;
+        return Ok(());
+    }
+    if fumbled {
+        state.slack_client.send_dm(user_id, "You lost your concentration.").await?;
+        return Ok(());
+    }
+
+    super::broadcast_room_action(
+        &state,
+        &room_id,
+        &format!("_{} utters the words, '{}'._", player.name, skill.name),
+        Some(user_id),
+        Some(&format!("_You utter the words, '{}'._", skill.name)),
+    ).await?;
+
+    let Some(caster) = Fighter::load(&state, &me).await? else {
+        return Ok(());
+    };
+    match aim {
+        Aim::Char(victim) => {
+            let Some(victim) = Fighter::load(&state, &victim).await? else {
+                return Ok(());
+            };
+            magic::cast(&state, skill, &caster, player.level, &victim).await
+        }
+        Aim::Object(object) => magic::cast_on_object(&state, skill, &caster, &object).await,
+        Aim::Room => magic::cast_on_room(&state, skill, &caster, player.level).await,
+    }
+}
+
+async fn practice(state: Arc<AppState>, user_id: &str, player: Player, args: &str) -> Result<()> {
+    let class_name = experience::player_class(&state, &player).await?.map(|class| class.name).unwrap_or_default();
+    let skill_repo = SkillRepository::new(state.db_pool.clone());
+    let learned = skill_repo.get_learned(&player.slack_user_id).await?;
+    let available = |skill: &skills::Skill| skill.level_for(&class_name).is_some_and(|level| level <= player.level);
+
+    if args.is_empty() {
+        let mut message = String::from("*Your skills and spells:*\n");
+        let mut any = false;
+        for skill in skills::SKILLS.iter().filter(|skill| available(skill)) {
+            message.push_str(&format!("• {} - {}%\n", skill.name, learned.get(skill.name).copied().unwrap_or(0)));
+            any = true;
+        }
+        if !any {
+            message.push_str("_None yet._\n");
+        }
+        message.push_str(&format!("You have {} practice sessions left.", player.practices));
+        state.slack_client.send_dm(user_id, &message).await?;
+        return Ok(());
+    }
+
+    let Some(room_id) = player.current_channel_id.clone() else {
+        state.slack_client.send_dm(
+            user_id,
+            "You need to be in a room first! Use `/mud look` in a channel to enter a room."
+        ).await?;
+        return Ok(());
+    };
+
+    let me = Combatant::Player(player.slack_user_id.clone());
+    if let Some(excuse) = combat::effective_position(&state, &me, player.position()).excuse() {
+        state.slack_client.send_dm(user_id, excuse).await?;
+        return Ok(());
+    }
+
+    // Practicing needs a guildmaster (ROM act flag 'K', ACT_PRACTICE)
+    let mobile_instance_repo = MobileInstanceRepository::new(state.db_pool.clone());
+    let mobile_repo = MobileRepository::new(state.db_pool.clone());
+    let mut has_trainer = false;
+    for instance in mobile_instance_repo.get_in_room(&room_id).await? {
+        if let Some(mobile) = mobile_repo.get_by_vnum(instance.mobile_vnum).await? {
+            if has_flag(&mobile.act_flags, 'K') {
+                has_trainer = true;
+                break;
+            }
+        }
+    }
+    if !has_trainer {
+        state.slack_client.send_dm(user_id, "You can't do that here.").await?;
+        return Ok(());
+    }
+
+    if player.practices <= 0 {
+        state.slack_client.send_dm(user_id, "You have no practice sessions left.").await?;
+        return Ok(());
+    }
+
+    let Some(skill) = skills::find(args, available).filter(|skill| available(skill)) else {
+        state.slack_client.send_dm(user_id, "You can't practice that.").await?;
+        return Ok(());
+    };
+
+    let current = learned.get(skill.name).copied().unwrap_or(0);
+    if current >= ADEPT {
+        state.slack_client.send_dm(user_id, &format!("You are already learned at {}.", skill.name)).await?;
+        return Ok(());
+    }
+
+    if !PlayerRepository::new(state.db_pool.clone()).spend_practice(&player.slack_user_id).await? {
+        state.slack_client.send_dm(user_id, "You have no practice sessions left.").await?;
+        return Ok(());
+    }
+    let now = (current + PRACTICE_GAIN).min(ADEPT);
+    skill_repo.set_learned(&player.slack_user_id, skill.name, now).await?;
+
+    let (to_char, to_room) = if now < ADEPT {
+        (format!("_You practice {}._", skill.name), format!("_{} practices {}._", player.name, skill.name))
+    } else {
+        (format!("_You are now learned at {}._", skill.name), format!("_{} is now learned at {}._", player.name, skill.name))
+    };
+    super::broadcast_room_action(&state, &room_id, &to_room, Some(user_id), Some(&to_char)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_spell() {
+        assert_eq!(split_spell("'cure light' bob"), ("cure light", "bob"));
+        assert_eq!(split_spell("armor"), ("armor", ""));
+        assert_eq!(split_spell("magic fido"), ("magic", "fido"));
+        assert_eq!(split_spell("\"detect invis"), ("detect invis", ""));
+    }
+}
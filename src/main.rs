@@ -4,6 +4,7 @@ mod slack;
 mod handlers;
 mod area;
 mod social;
+mod game;
 
 use anyhow::{Context, Result};
 use axum::{
@@ -51,6 +52,10 @@ pub struct AppState {
     pub slack_client: slack::SlackClient,
     pub recent_event_ids: Mutex<VecDeque<String>>,
     pub char_creation_states: Mutex<HashMap<String, CharCreationState>>,
+    pub area_ages: Mutex<HashMap<String, game::reset::AreaAge>>,
+    pub mayor_walks: Mutex<HashMap<i32, game::special::MayorWalk>>,
+    /// Who each combatant is fighting (ROM's `ch->fighting`)
+    pub fights: Mutex<HashMap<game::combat::Combatant, game::combat::Combatant>>,
 }
 
 #[tokio::main]
@@ -115,8 +120,14 @@ async fn main() -> Result<()> {
         slack_client,
         recent_event_ids: Mutex::new(VecDeque::with_capacity(1000)),
         char_creation_states: Mutex::new(HashMap::new()),
+        area_ages: Mutex::new(HashMap::new()),
+        mayor_walks: Mutex::new(HashMap::new()),
+        fights: Mutex::new(HashMap::new()),
     });
 
+    // Start the game heartbeat (area resets, mobile special procedures, ...)
+    tokio::spawn(game::tick::game_scheduler().run(state.clone()));
+
     // Build router
     let app = Router::new()
         .route("/health", get(health_check))
@@ -207,19 +218,25 @@ async fn promote_to_wizard(player_repo: &db::player::PlayerRepository, slack_use
 
 /// Load default area files (like Midgaard) on startup
 async fn load_default_areas(pool: &sqlx::PgPool) -> Result<()> {
-    use db::area::AreaRepository;
-    use db::room::RoomRepository;
+    use db::area::{AreaRepository, AreaResetRepository};
+    use db::room::{RoomRepository, RoomExtraDescriptionRepository};
     use db::exit::ExitRepository;
-    use db::object::{ObjectRepository, ObjectInstanceRepository};
+    use db::object::ObjectRepository;
+    use db::mobile::{MobileRepository, MobileInstanceRepository};
+    use db::shop::ShopRepository;
     use area::parser::parse_area_file;
-    use area::types::Reset;
-    use models::{Room, Exit, Area, Object, ObjectInstance};
+    use area::types::ExitFlags;
+    use models::{Room, RoomExtraDescription, Exit, Area, Object, Mobile, Shop};
 
     let area_repo = AreaRepository::new(pool.clone());
+    let reset_repo = AreaResetRepository::new(pool.clone());
     let room_repo = RoomRepository::new(pool.clone());
+    let extra_repo = RoomExtraDescriptionRepository::new(pool.clone());
     let exit_repo = ExitRepository::new(pool.clone());
     let object_repo = ObjectRepository::new(pool.clone());
-    let object_instance_repo = ObjectInstanceRepository::new(pool.clone());
+    let mobile_repo = MobileRepository::new(pool.clone());
+    let mobile_instance_repo = MobileInstanceRepository::new(pool.clone());
+    let shop_repo = ShopRepository::new(pool.clone());
 
     // Embed the midgaard.are file directly in the binary
     const MIDGAARD_CONTENT: &str = include_str!("../data/areas/midgaard.are");
@@ -242,7 +259,10 @@ async fn load_default_areas(pool: &sqlx::PgPool) -> Result<()> {
 
         // Delete existing area and all associated data
         if area_repo.exists(area_name).await? {
-            // Delete object instances first
+            // Delete mobile instances (and anything they carry) first
+            mobile_instance_repo.delete_by_area(area_name).await?;
+
+            // Delete object instances
             sqlx::query(
                 "DELETE FROM object_instances WHERE object_vnum IN
                  (SELECT vnum FROM objects WHERE area_name = $1)"
@@ -257,41 +277,64 @@ async fn load_default_areas(pool: &sqlx::PgPool) -> Result<()> {
                 .execute(pool)
                 .await?;
 
+            // Delete shops and mobile definitions
+            shop_repo.delete_by_area(area_name).await?;
+            mobile_repo.delete_by_area(area_name).await?;
+
             // Delete area (cascades to rooms and exits)
             area_repo.delete_by_name(area_name).await?;
 
             tracing::info!("Deleted existing area '{}' and all associated data", area_name);
         }
-    } else {
-        // Normal production behavior - skip if already imported
-        if area_repo.exists(area_name).await? {
-            tracing::info!("Area '{}' already imported, skipping", area_name);
-            return Ok(());
-        }
     }
 
-    tracing::info!("Importing area '{}' ({} rooms, {} objects, {} resets)...",
-        area_name, area_file.rooms.len(), area_file.objects.len(), area_file.resets.len());
+    // An area imported by an older version is brought up to date rather than skipped:
+    // templates are upserted so they pick up everything parsed since, while rooms and
+    // exits keep any changes made in the game. Only a new area is populated here;
+    // existing ones repopulate on their next reset.
+    let already_imported = area_repo.exists(area_name).await?;
+    if already_imported {
+        tracing::info!("Area '{}' already imported, updating it", area_name);
+    } else {
+        tracing::info!("Importing area '{}' ({} rooms, {} objects, {} mobiles, {} resets)...",
+            area_name, area_file.rooms.len(), area_file.objects.len(), area_file.mobiles.len(), area_file.resets.len());
+    }
 
     let mut rooms_created = 0;
     let mut exits_created = 0;
     let mut objects_created = 0;
-    let mut instances_spawned = 0;
+    let mut mobiles_created = 0;
+    let mut shops_created = 0;
 
     // First pass: Create all rooms
     for area_room in &area_file.rooms {
         let room_id = format!("vnum_{}", area_room.vnum);
 
+        // Keep a room's name, description and channel if it's already in the world
+        let existing = room_repo.get_by_channel_id(&room_id).await?;
         let room = Room {
             channel_id: room_id.clone(),
-            channel_name: area_room.name.clone(),
-            description: area_room.description.clone(),
-            attached_channel_id: None, // Virtual room
+            channel_name: existing.as_ref().map_or_else(|| area_room.name.clone(), |r| r.channel_name.clone()),
+            description: existing.as_ref().map_or_else(|| area_room.description.clone(), |r| r.description.clone()),
+            attached_channel_id: existing.as_ref().and_then(|r| r.attached_channel_id.clone()), // Virtual room
+            heal_rate: area_room.heal_rate,
+            mana_rate: area_room.mana_rate,
+            room_flags: area_room.room_flags.to_letters(),
+            sector_type: area_room.sector_type as i32,
             created_at: chrono::Utc::now().timestamp(),
             updated_at: chrono::Utc::now().timestamp(),
         };
 
         room_repo.create(&room).await?;
+
+        // Rooms whose extra descriptions wizards have already edited keep them
+        if existing.is_none() || extra_repo.get_for_room(&room_id).await?.is_empty() {
+            extra_repo.delete_for_room(&room_id).await?;
+            for extra in &area_room.extra_descs {
+                let extra = RoomExtraDescription::new(room_id.clone(), extra.keywords.join(" "), extra.description.clone());
+                extra_repo.create(&extra).await?;
+            }
+        }
         rooms_created += 1;
     }
 
@@ -308,12 +351,25 @@ async fn load_default_areas(pool: &sqlx::PgPool) -> Result<()> {
                 continue;
             }
 
-            let exit = Exit::new(
+            // Leave alone an exit that's been dug somewhere else since
+            if let Some(existing) = exit_repo.get_exit_in_direction(&room_id, area_exit.direction.as_str()).await? {
+                if existing.to_room_id != to_room_id {
+                    continue;
+                }
+            }
+
+            let mut exit = Exit::new(
                 room_id.clone(),
                 area_exit.direction.as_str().to_string(),
                 to_room_id,
                 None, // System-created exit
             );
+            if let Some(keyword) = &area_exit.keyword {
+                exit.keyword = keyword.clone();
+            }
+            exit.description = area_exit.description.clone();
+            exit.exit_flags = ExitFlags::from_lock_code(area_exit.door_flags).to_letters();
+            exit.key_vnum = area_exit.key_vnum;
 
             exit_repo.create(&exit).await?;
             exits_created += 1;
@@ -322,55 +378,57 @@ async fn load_default_areas(pool: &sqlx::PgPool) -> Result<()> {
 
     // Third pass: Create all objects
     for area_object in &area_file.objects {
-        let object = Object::new(
-            area_object.vnum,
-            area_name.clone(),
-            area_object.keywords.clone(),
-            area_object.short_description.clone(),
-            area_object.long_description.clone(),
-            area_object.material.clone(),
-            area_object.item_type.clone(),
-            area_object.extra_flags.clone(),
-            area_object.wear_flags.clone(),
-            area_object.value0,
-            area_object.value1,
-            area_object.value2.clone(),
-            area_object.value3,
-            area_object.value4,
-            area_object.weight,
-            area_object.cost,
-            area_object.level,
-            area_object.condition.clone(),
-        );
+        let object = Object::from_area_object(area_name.clone(), area_object)?;
 
         object_repo.create(&object).await?;
         objects_created += 1;
     }
 
-    // Fourth pass: Process resets and spawn object instances
-    for reset in &area_file.resets {
-        match reset {
-            Reset::ObjectInRoom { obj_vnum, room_vnum, .. } => {
-                // Spawn object in room
-                let room_id = format!("vnum_{}", room_vnum);
-
-                // Skip if room doesn't exist (outside area range)
-                if *room_vnum < area_file.header.min_vnum || *room_vnum > area_file.header.max_vnum {
-                    continue;
-                }
+    // Fourth pass: Create all mobiles
+    for area_mobile in &area_file.mobiles {
+        let mut mobile = Mobile::from_area_mobile(area_name.clone(), area_mobile);
+        mobile.spec_fun = area_file.specials.iter()
+            .find(|s| s.mob_vnum == area_mobile.vnum)
+            .map(|s| s.spec_fun.clone());
+        mobile_repo.create(&mobile).await?;
+        mobiles_created += 1;
+    }
 
-                // Create object instance
-                let instance = ObjectInstance::new_in_room(*obj_vnum, room_id);
-                object_instance_repo.create(&instance).await?;
-                instances_spawned += 1;
-            }
-            _ => {
-                // Skip other reset types for now (mobs, give, equip, etc.)
-                // We'll implement these when we have mobs
-            }
+    // Create shops for shopkeepers defined in this area
+    for area_shop in &area_file.shops {
+        if !area_file.mobiles.iter().any(|m| m.vnum == area_shop.keeper_vnum) {
+            tracing::debug!("Skipping shop for unknown keeper {}", area_shop.keeper_vnum);
+            continue;
         }
+        let shop = Shop::from_area_shop(area_name.clone(), area_shop);
+        shop_repo.create(&shop).await?;
+        shops_created += 1;
     }
 
+    // Store the resets so the area can be repopulated later
+    reset_repo.replace_for_area(area_name, &area_file.resets).await?;
+
+    if already_imported {
+        tracing::info!(
+            "Updated area '{}': {} rooms, {} exits, {} objects, {} mobiles, {} shops",
+            area_name,
+            rooms_created,
+            exits_created,
+            objects_created,
+            mobiles_created,
+            shops_created
+        );
+        return Ok(());
+    }
+
+    // Fifth pass: Process resets and spawn object and mobile instances
+    let spawned = game::reset::apply_resets(
+        pool,
+        &area_file.resets,
+        area_file.header.min_vnum,
+        area_file.header.max_vnum,
+    ).await?;
+
     // Record the area in the database
     let area = Area::new(
         area_file.header.name.clone(),
@@ -383,12 +441,15 @@ async fn load_default_areas(pool: &sqlx::PgPool) -> Result<()> {
     area_repo.create(&area).await?;
 
     tracing::info!(
-        "Successfully imported area '{}': {} rooms, {} exits, {} objects, {} instances spawned",
+        "Successfully imported area '{}': {} rooms, {} exits, {} objects, {} mobiles, {} shops, {} object instances and {} mobile instances spawned",
         area_name,
         rooms_created,
         exits_created,
         objects_created,
-        instances_spawned
+        mobiles_created,
+        shops_created,
+        spawned.objects_spawned,
+        spawned.mobiles_spawned
     );
 
     Ok(())
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// A timed spell effect on a player or mobile instance (ROM's AFFECT_DATA)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Affect {
+    pub id: i32,
+    pub target_type: String, // 'player' or 'mobile'
+    pub target_id: String,   // player slack_user_id or mobile instance id
+    pub skill: String,
+    pub level: i32,
+    pub duration: i32, // ticks remaining; -1 is permanent
+    pub location: String, // 'none', 'ac', 'hitroll', 'saving_spell', ...
+    pub modifier: i32,
+    pub bitvector: String, // ROM affect flag letters
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Affect {
+    /// An affect with no modifier or flags; add those with `modifying` and `granting`
+    pub fn new(target_type: &str, target_id: String, skill: &str, level: i32, duration: i32) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: 0, // Will be set by database
+            target_type: target_type.to_string(),
+            target_id,
+            skill: skill.to_string(),
+            level,
+            duration,
+            location: "none".to_string(),
+            modifier: 0,
+            bitvector: String::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Modify a stat, like ROM's APPLY_* locations
+    pub fn modifying(mut self, location: &str, modifier: i32) -> Self {
+        self.location = location.to_string();
+        self.modifier = modifier;
+        self
+    }
+
+    /// Grant ROM affect flags (letters, e.g. "D" for detect invis)
+    pub fn granting(mut self, bitvector: &str) -> Self {
+        self.bitvector = bitvector.to_string();
+        self
+    }
+}
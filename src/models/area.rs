@@ -1,6 +1,10 @@
+use crate::area::types::Reset;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+/// Message announced when an area resets, unless the area configures its own
+pub const DEFAULT_RESET_MESSAGE: &str = "You hear the patter of little feet.";
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Area {
     pub id: i32,
@@ -10,6 +14,7 @@ pub struct Area {
     pub max_vnum: i32,
     pub rooms_count: i32,
     pub exits_count: i32,
+    pub reset_message: String,
     pub imported_at: i64,
     pub updated_at: i64,
 }
@@ -32,8 +37,64 @@ impl Area {
             max_vnum,
             rooms_count,
             exits_count,
+            reset_message: DEFAULT_RESET_MESSAGE.to_string(),
             imported_at: now,
             updated_at: now,
         }
     }
 }
+
+/// A single stored reset command from an area's #RESETS section
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AreaReset {
+    pub id: i32,
+    pub area_name: String,
+    pub seq: i32,
+    pub command: String,
+    pub if_flag: i32,
+    pub arg1: i32,
+    pub arg2: i32,
+    pub arg3: i32,
+    pub arg4: i32,
+}
+
+impl AreaReset {
+    pub fn from_reset(area_name: String, seq: i32, reset: &Reset) -> Self {
+        let (command, if_flag, arg1, arg2, arg3, arg4) = match *reset {
+            Reset::ObjectInRoom { if_flag, obj_vnum, limit, room_vnum } => ("O", if_flag, obj_vnum, limit, room_vnum, 0),
+            Reset::Mobile { if_flag, mob_vnum, limit, room_vnum, max_in_room } => ("M", if_flag, mob_vnum, limit, room_vnum, max_in_room),
+            Reset::GiveObject { if_flag, obj_vnum, limit } => ("G", if_flag, obj_vnum, limit, 0, 0),
+            Reset::EquipObject { if_flag, obj_vnum, limit, wear_location } => ("E", if_flag, obj_vnum, limit, wear_location, 0),
+            Reset::PutInContainer { if_flag, obj_vnum, limit, container_vnum } => ("P", if_flag, obj_vnum, limit, container_vnum, 0),
+            Reset::Door { room_vnum, direction, state } => ("D", 0, room_vnum, direction, state, 0),
+            Reset::RandomizeExits { room_vnum, num_exits } => ("R", 0, room_vnum, num_exits, 0, 0),
+        };
+
+        Self {
+            id: 0, // Will be set by database
+            area_name,
+            seq,
+            command: command.to_string(),
+            if_flag,
+            arg1,
+            arg2,
+            arg3,
+            arg4,
+        }
+    }
+
+    /// Convert back into a parsed reset (None for unknown commands)
+    pub fn to_reset(&self) -> Option<Reset> {
+        let if_flag = self.if_flag;
+        match self.command.as_str() {
+            "O" => Some(Reset::ObjectInRoom { if_flag, obj_vnum: self.arg1, limit: self.arg2, room_vnum: self.arg3 }),
+            "M" => Some(Reset::Mobile { if_flag, mob_vnum: self.arg1, limit: self.arg2, room_vnum: self.arg3, max_in_room: self.arg4 }),
+            "G" => Some(Reset::GiveObject { if_flag, obj_vnum: self.arg1, limit: self.arg2 }),
+            "E" => Some(Reset::EquipObject { if_flag, obj_vnum: self.arg1, limit: self.arg2, wear_location: self.arg3 }),
+            "P" => Some(Reset::PutInContainer { if_flag, obj_vnum: self.arg1, limit: self.arg2, container_vnum: self.arg3 }),
+            "D" => Some(Reset::Door { room_vnum: self.arg1, direction: self.arg2, state: self.arg3 }),
+            "R" => Some(Reset::RandomizeExits { room_vnum: self.arg1, num_exits: self.arg2 }),
+            _ => None,
+        }
+    }
+}
@@ -6,4 +6,11 @@ pub struct Class {
     pub id: i32,
     pub name: String,
     pub description: String,
+    /// Experience needed for each level
+    pub exp_per_level: i32,
+    /// Range of hit points gained on each level
+    pub hp_min: i32,
+    pub hp_max: i32,
+    /// Casters gain mana at full speed; everyone else at half
+    pub gains_mana: bool,
 }
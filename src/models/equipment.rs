@@ -1,3 +1,4 @@
+use crate::area::types::has_flag;
 use std::fmt;
 
 /// Equipment slot where an item can be worn/wielded
@@ -126,11 +127,21 @@ impl EquipmentSlot {
         }
     }
 
+    /// Get the slot for a ROM wear location number (as used by `E` resets)
+    pub fn from_wear_location(location: i32) -> Option<Self> {
+        let index = usize::try_from(location).ok()?;
+        Self::all_slots_in_order().into_iter().nth(index)
+    }
+
     /// Get valid slots for a wear flag string from ROM area files
     /// Returns all possible slots for an item based on its wear_flags
     pub fn from_wear_flags(wear_flags: &str) -> Vec<EquipmentSlot> {
         let mut slots = Vec::new();
-        let flags_lower = wear_flags.to_lowercase();
+        let flags_lower = if wear_flags.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) {
+            rom_wear_flag_names(wear_flags)
+        } else {
+            wear_flags.to_lowercase()
+        };
 
         // Check for each wear flag
         if flags_lower.contains("take") {
@@ -189,8 +200,39 @@ impl EquipmentSlot {
     }
 }
 
+/// Spell out ROM wear flag letters (e.g. "AN") as the names `from_wear_flags` matches on
+fn rom_wear_flag_names(wear_flags: &str) -> String {
+    const NAMES: [(char, &str); 16] = [
+        ('A', "take"), ('B', "finger"), ('C', "neck"), ('D', "body"),
+        ('E', "head"), ('F', "legs"), ('G', "feet"), ('H', "hands"),
+        ('I', "arms"), ('J', "shield"), ('K', "about"), ('L', "waist"),
+        ('M', "wrist"), ('N', "wield"), ('O', "hold"), ('Q', "float"),
+    ];
+
+    NAMES.iter()
+        .filter(|(letter, _)| has_flag(wear_flags, *letter))
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 impl fmt::Display for EquipmentSlot {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.to_db_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_wear_flags_rom_letters() {
+        assert_eq!(EquipmentSlot::from_wear_flags("AN"), vec![EquipmentSlot::Wield]);
+        assert_eq!(EquipmentSlot::from_wear_flags("AD"), vec![EquipmentSlot::Body]);
+        // 1 + 8192: take + wield as a bit number
+        assert_eq!(EquipmentSlot::from_wear_flags("8193"), vec![EquipmentSlot::Wield]);
+        assert!(EquipmentSlot::from_wear_flags("A").is_empty());
+        assert_eq!(EquipmentSlot::from_wear_flags("take wield"), vec![EquipmentSlot::Wield]);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::area::types::ExitFlags;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
@@ -9,6 +10,13 @@ pub struct Exit {
     pub to_room_id: String,
     pub created_at: i64,
     pub created_by: Option<String>,
+    /// Name(s) the door answers to, like "gate"; empty for most exits
+    pub keyword: String,
+    pub description: String,
+    /// ROM exit flag letters; see `flags()`
+    pub exit_flags: String,
+    /// Vnum of the key that locks and unlocks the door; 0 or less if it has no lock
+    pub key_vnum: i32,
 }
 
 impl Exit {
@@ -21,8 +29,36 @@ impl Exit {
             to_room_id,
             created_at: now,
             created_by,
+            keyword: String::new(),
+            description: String::new(),
+            exit_flags: String::new(),
+            key_vnum: 0,
         }
     }
+
+    pub fn flags(&self) -> ExitFlags {
+        ExitFlags::from_str(&self.exit_flags)
+    }
+
+    pub fn is_door(&self) -> bool {
+        self.flags().contains(ExitFlags::IS_DOOR)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.flags().contains(ExitFlags::CLOSED)
+    }
+
+    /// What to call the door in messages: its first keyword, or just "door"
+    pub fn door_name(&self) -> &str {
+        self.keyword.split_whitespace().next().unwrap_or("door")
+    }
+
+    /// Check if the door answers to a keyword
+    pub fn matches_keyword(&self, keyword: &str) -> bool {
+        self.keyword
+            .split_whitespace()
+            .any(|k| k.eq_ignore_ascii_case(keyword))
+    }
 }
 
 /// Valid directions for exits
@@ -31,3 +67,25 @@ pub const VALID_DIRECTIONS: &[&str] = &["north", "south", "east", "west", "up",
 pub fn is_valid_direction(direction: &str) -> bool {
     VALID_DIRECTIONS.contains(&direction.to_lowercase().as_str())
 }
+
+/// Full direction name for a direction or its first letter ("n" or "north")
+pub fn expand_direction(direction: &str) -> Option<&'static str> {
+    let direction = direction.to_lowercase();
+    VALID_DIRECTIONS
+        .iter()
+        .copied()
+        .find(|d| *d == direction || (direction.len() == 1 && d.starts_with(&direction)))
+}
+
+/// The direction that leads back the way an exit came
+pub fn reverse_direction(direction: &str) -> Option<&'static str> {
+    match direction {
+        "north" => Some("south"),
+        "south" => Some("north"),
+        "east" => Some("west"),
+        "west" => Some("east"),
+        "up" => Some("down"),
+        "down" => Some("up"),
+        _ => None,
+    }
+}
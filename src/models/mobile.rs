@@ -0,0 +1,152 @@
+use crate::area::{AreaMobile, Dice};
+use super::Position;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Mobile {
+    pub id: i32,
+    pub vnum: i32,
+    pub area_name: String,
+    pub keywords: String,
+    pub short_description: String,
+    pub long_description: String,
+    pub description: String,
+    pub race: String,
+    pub act_flags: String,
+    pub affect_flags: String,
+    pub alignment: i32,
+    pub level: i32,
+    pub hitroll: i32,
+    pub hit_dice: String,
+    pub mana_dice: String,
+    pub damage_dice: String,
+    pub damage_type: String,
+    pub ac_pierce: i32,
+    pub ac_bash: i32,
+    pub ac_slash: i32,
+    pub ac_exotic: i32,
+    pub start_position: String,
+    pub default_position: String,
+    pub gender: String,
+    pub wealth: i32,
+    pub size: String,
+    pub spec_fun: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Mobile {
+    /// Short description with the first letter capitalised, for starting sentences
+    pub fn display_name(&self) -> String {
+        let mut chars = self.short_description.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+
+    /// Check if this mobile answers to a keyword
+    pub fn matches_keyword(&self, keyword: &str) -> bool {
+        self.keywords
+            .split_whitespace()
+            .any(|k| k.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Roll hit points for a new instance from the template's hit dice
+    pub fn roll_max_hit(&self) -> i32 {
+        Dice::parse(&self.hit_dice).map(|dice| dice.roll()).unwrap_or(1).max(1)
+    }
+
+    /// Roll mana for a new instance from the template's mana dice
+    pub fn roll_max_mana(&self) -> i32 {
+        Dice::parse(&self.mana_dice).map(|dice| dice.roll()).unwrap_or(0).max(0)
+    }
+
+    /// Build a mobile template from a parsed area file entry
+    ///
+    /// The special procedure comes from a separate section; callers set `spec_fun` afterwards.
+    pub fn from_area_mobile(area_name: String, mobile: &AreaMobile) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: 0, // Will be set by database
+            vnum: mobile.vnum,
+            area_name,
+            keywords: mobile.keywords.clone(),
+            short_description: mobile.short_description.clone(),
+            long_description: mobile.long_description.clone(),
+            description: mobile.description.clone(),
+            race: mobile.race.clone(),
+            act_flags: mobile.act_flags.clone(),
+            affect_flags: mobile.affect_flags.clone(),
+            alignment: mobile.alignment,
+            level: mobile.level,
+            hitroll: mobile.hitroll,
+            hit_dice: mobile.hit_dice.to_string(),
+            mana_dice: mobile.mana_dice.to_string(),
+            damage_dice: mobile.damage_dice.to_string(),
+            damage_type: mobile.damage_type.clone(),
+            ac_pierce: mobile.ac_pierce,
+            ac_bash: mobile.ac_bash,
+            ac_slash: mobile.ac_slash,
+            ac_exotic: mobile.ac_exotic,
+            start_position: mobile.start_position.clone(),
+            default_position: mobile.default_position.clone(),
+            gender: mobile.gender.clone(),
+            wealth: mobile.wealth,
+            size: mobile.size.clone(),
+            spec_fun: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MobileInstance {
+    pub id: i32,
+    pub mobile_vnum: i32,
+    pub room_id: String,
+    pub gold: i32,
+    pub silver: i32,
+    pub hit: i32,
+    pub max_hit: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+    pub movement: i32,
+    pub max_movement: i32,
+    pub position: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl MobileInstance {
+    /// Spawn a fresh instance of a template, rolling its hit points and mana
+    pub fn new_in_room(mobile: &Mobile, room_channel_id: String) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        let max_hit = mobile.roll_max_hit();
+        let max_mana = mobile.roll_max_mana();
+        let position = Position::parse_or_standing(&mobile.start_position);
+        Self {
+            id: 0, // Will be set by database
+            mobile_vnum: mobile.vnum,
+            room_id: room_channel_id,
+            gold: 0,
+            silver: 0,
+            hit: max_hit,
+            max_hit,
+            mana: max_mana,
+            max_mana,
+            movement: 100,
+            max_movement: 100,
+            position: position.as_str().to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// The instance's stored position (fighting is tracked separately, in the fight map)
+    pub fn position(&self) -> Position {
+        Position::parse_or_standing(&self.position)
+    }
+}
@@ -5,13 +5,21 @@ pub mod room;
 pub mod exit;
 pub mod area;
 pub mod object;
+pub mod mobile;
+pub mod shop;
 pub mod equipment;
+pub mod position;
+pub mod affect;
 
 pub use player::Player;
 pub use class::Class;
 pub use race::Race;
-pub use room::Room;
+pub use room::{Room, RoomExtraDescription};
 pub use exit::Exit;
-pub use area::Area;
+pub use area::{Area, AreaReset};
 pub use object::{Object, ObjectInstance};
+pub use mobile::{Mobile, MobileInstance};
+pub use shop::Shop;
 pub use equipment::EquipmentSlot;
+pub use position::Position;
+pub use affect::Affect;
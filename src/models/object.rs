@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use super::EquipmentSlot;
+use crate::area::AreaObject;
+use crate::area::types::{ExtraDescription, ObjectApply};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Object {
@@ -23,6 +26,8 @@ pub struct Object {
     pub level: i32,
     pub condition: String,
     pub extra_descriptions: serde_json::Value,
+    pub applies: serde_json::Value, // Vec<ObjectApply> as JSON
+    pub spells: serde_json::Value,  // Vec<String> as JSON
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -70,11 +75,86 @@ impl Object {
             level,
             condition,
             extra_descriptions: serde_json::json!([]),
+            applies: serde_json::json!([]),
+            spells: serde_json::json!([]),
             created_at: now,
             updated_at: now,
         }
     }
 
+    /// Build an object template from a parsed area file entry, extra descriptions, applies and spells included
+    pub fn from_area_object(area_name: String, object: &AreaObject) -> Result<Self, serde_json::Error> {
+        let mut template = Self::new(
+            object.vnum,
+            area_name,
+            object.keywords.clone(),
+            object.short_description.clone(),
+            object.long_description.clone(),
+            object.material.clone(),
+            object.item_type.clone(),
+            object.extra_flags.clone(),
+            object.wear_flags.clone(),
+            object.value0,
+            object.value1,
+            object.value2.clone(),
+            object.value3,
+            object.value4,
+            object.weight,
+            object.cost,
+            object.level,
+            object.condition.clone(),
+        );
+        template.extra_descriptions = serde_json::to_value(&object.extra_descriptions)?;
+        template.applies = serde_json::to_value(&object.applies)?;
+        template.spells = serde_json::to_value(&object.spells)?;
+        Ok(template)
+    }
+
+    /// Short description with the first letter capitalised, for starting sentences
+    pub fn display_name(&self) -> String {
+        let mut chars = self.short_description.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+
+    /// The extra description for a keyword, like the writing on a scroll or a sign
+    pub fn extra_description(&self, keyword: &str) -> Option<String> {
+        let extra_descriptions: Vec<ExtraDescription> =
+            serde_json::from_value(self.extra_descriptions.clone()).unwrap_or_default();
+        extra_descriptions
+            .into_iter()
+            .find(|ed| ed.keywords.iter().any(|k| k.eq_ignore_ascii_case(keyword)))
+            .map(|ed| ed.description)
+    }
+
+    /// Stat changes this object gives whoever wears it
+    pub fn applies(&self) -> Vec<ObjectApply> {
+        serde_json::from_value(self.applies.clone()).unwrap_or_default()
+    }
+
+    /// Names of the spells a potion, pill, scroll, wand or staff casts
+    pub fn spells(&self) -> Vec<String> {
+        serde_json::from_value(self.spells.clone()).unwrap_or_default()
+    }
+
+    /// How sound a freshly made copy is, out of 100, from the area file's condition letter
+    ///
+    /// ROM 2.4 areas write P(erfect), G(ood), A(verage), W(orn), D(amaged), B(roken) or R(uined).
+    pub fn base_condition(&self) -> i32 {
+        match self.condition.trim() {
+            "P" => 100,
+            "G" => 90,
+            "A" => 75,
+            "W" => 50,
+            "D" => 25,
+            "B" => 10,
+            "R" => 0,
+            other => other.parse().map_or(100, |condition: i32| condition.clamp(0, 100)),
+        }
+    }
+
     /// Get the first keyword (used for matching player commands)
     pub fn primary_keyword(&self) -> &str {
         self.keywords.split_whitespace().next().unwrap_or(&self.keywords)
@@ -87,6 +167,11 @@ impl Object {
             .any(|k| k.eq_ignore_ascii_case(keyword))
     }
 
+    /// Whether the object can be picked up at all (ROM's ITEM_TAKE wear flag)
+    pub fn can_take(&self) -> bool {
+        self.wear_flags.to_lowercase().contains("take") || crate::area::types::has_flag(&self.wear_flags, 'A')
+    }
+
     /// Get armor class (AC) bonus from armor
     /// Returns total AC (sum of pierce, bash, slash, magic)
     pub fn get_armor_class(&self) -> i32 {
@@ -195,12 +280,24 @@ impl Object {
 pub struct ObjectInstance {
     pub id: i32,
     pub object_vnum: i32,
-    pub location_type: String, // 'room', 'player', 'container', 'equipped'
+    pub location_type: String, // 'room', 'player', 'container', 'equipped', 'mobile', 'mobile_equipped', 'shop'
     pub location_id: String,
     pub wear_location: Option<String>,
     pub equipped_slot: Option<String>, // 'body', 'wield', 'finger_l', etc.
     pub current_condition: i32,
     pub timer: Option<i32>,
+    /// Overrides the template's short description (e.g. whose corpse this is)
+    pub short_description: Option<String>,
+    /// Overrides the template's long description
+    pub long_description: Option<String>,
+    /// Hours a light source has left once it has burned some; None means the template's value2
+    pub light_hours: Option<i32>,
+    /// A container's flags as letters once opened, closed or locked; None means the template's value1
+    pub container_flags: Option<String>,
+    /// Charges left in a wand or staff, or drinks left in a drink container; None means the template's
+    pub charges: Option<i32>,
+    /// What a drink container holds once filled or emptied; None means the template's value2
+    pub liquid: Option<String>,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -217,6 +314,12 @@ impl ObjectInstance {
             equipped_slot: None,
             current_condition: 100,
             timer: None,
+            short_description: None,
+            long_description: None,
+            light_hours: None,
+            container_flags: None,
+            charges: None,
+            liquid: None,
             created_at: now,
             updated_at: now,
         }
@@ -233,8 +336,194 @@ impl ObjectInstance {
             equipped_slot: None,
             current_condition: 100,
             timer: None,
+            short_description: None,
+            long_description: None,
+            light_hours: None,
+            container_flags: None,
+            charges: None,
+            liquid: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn new_in_shop(object_vnum: i32, keeper_instance_id: i32) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: 0, // Will be set by database
+            object_vnum,
+            location_type: "shop".to_string(),
+            location_id: keeper_instance_id.to_string(),
+            wear_location: None,
+            equipped_slot: None,
+            current_condition: 100,
+            timer: None,
+            short_description: None,
+            long_description: None,
+            light_hours: None,
+            container_flags: None,
+            charges: None,
+            liquid: None,
             created_at: now,
             updated_at: now,
         }
     }
+
+    pub fn new_on_mobile(object_vnum: i32, mobile_instance_id: i32) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: 0, // Will be set by database
+            object_vnum,
+            location_type: "mobile".to_string(),
+            location_id: mobile_instance_id.to_string(),
+            wear_location: None,
+            equipped_slot: None,
+            current_condition: 100,
+            timer: None,
+            short_description: None,
+            long_description: None,
+            light_hours: None,
+            container_flags: None,
+            charges: None,
+            liquid: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn new_equipped_on_mobile(object_vnum: i32, mobile_instance_id: i32, slot: &EquipmentSlot) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: 0, // Will be set by database
+            object_vnum,
+            location_type: "mobile_equipped".to_string(),
+            location_id: mobile_instance_id.to_string(),
+            wear_location: None,
+            equipped_slot: Some(slot.to_db_string().to_string()),
+            current_condition: 100,
+            timer: None,
+            short_description: None,
+            long_description: None,
+            light_hours: None,
+            container_flags: None,
+            charges: None,
+            liquid: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn new_in_container(object_vnum: i32, container_instance_id: i32) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: 0, // Will be set by database
+            object_vnum,
+            location_type: "container".to_string(),
+            location_id: container_instance_id.to_string(),
+            wear_location: None,
+            equipped_slot: None,
+            current_condition: 100,
+            timer: None,
+            short_description: None,
+            long_description: None,
+            light_hours: None,
+            container_flags: None,
+            charges: None,
+            liquid: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Create a corpse in a room from a corpse template, named after who died
+    ///
+    /// The template's descriptions contain `%s` where the name goes, as in ROM.
+    pub fn new_corpse(template: &Object, name: &str, room_channel_id: String, timer: i32) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: 0, // Will be set by database
+            object_vnum: template.vnum,
+            location_type: "room".to_string(),
+            location_id: room_channel_id,
+            wear_location: None,
+            equipped_slot: None,
+            current_condition: 100,
+            timer: Some(timer),
+            short_description: Some(template.short_description.replace("%s", name)),
+            long_description: Some(template.long_description.replace("%s", name)),
+            light_hours: None,
+            container_flags: None,
+            charges: None,
+            liquid: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Give a fresh instance a decay timer, in ticks
+    pub fn with_timer(mut self, timer: Option<i32>) -> Self {
+        self.timer = timer;
+        self
+    }
+
+    /// Give a fresh instance its template's condition
+    pub fn with_condition(mut self, condition: i32) -> Self {
+        self.current_condition = condition;
+        self
+    }
+
+    /// Whether this instance has worn down to nothing and can't be used until repaired
+    pub fn is_broken(&self) -> bool {
+        self.current_condition <= 0
+    }
+
+    /// Apply this instance's own descriptions, if any, over its template's
+    pub fn restring(&self, mut object: Object) -> Object {
+        if let Some(short) = &self.short_description {
+            object.short_description = short.clone();
+        }
+        if let Some(long) = &self.long_description {
+            object.long_description = long.clone();
+        }
+        object
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corpse_is_named_after_the_dead() {
+        let template = Object::new(
+            10, "limbo".to_string(), "corpse".to_string(),
+            "the corpse of %s".to_string(), "The corpse of %s is lying here.".to_string(),
+            "flesh".to_string(), "npc_corpse".to_string(), String::new(), "A".to_string(),
+            0, 0, String::new(), 0, 0, 100, 0, 0, "P".to_string(),
+        );
+        let corpse = ObjectInstance::new_corpse(&template, "the cityguard", "vnum_3001".to_string(), 5);
+        assert_eq!(corpse.timer, Some(5));
+
+        let object = corpse.restring(template);
+        assert_eq!(object.short_description, "the corpse of the cityguard");
+        assert_eq!(object.long_description, "The corpse of the cityguard is lying here.");
+        assert_eq!(object.display_name(), "The corpse of the cityguard");
+    }
+
+    #[test]
+    fn test_extra_description_by_keyword() {
+        let mut scroll = Object::new(
+            3042, "midgaard".to_string(), "scroll recall".to_string(),
+            "a scroll of recall".to_string(), "A scroll has been left here.".to_string(),
+            "paper".to_string(), "scroll".to_string(), String::new(), "AO".to_string(),
+            0, 0, String::new(), 0, 0, 1, 100, 0, "P".to_string(),
+        );
+        assert_eq!(scroll.extra_description("writing"), None);
+
+        scroll.extra_descriptions = serde_json::json!([
+            { "keywords": ["writing", "words"], "description": "It reads 'recall'." }
+        ]);
+        assert_eq!(scroll.extra_description("Words").as_deref(), Some("It reads 'recall'."));
+        assert_eq!(scroll.extra_description("scroll"), None);
+    }
 }
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use super::Position;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Player {
@@ -11,6 +12,21 @@ pub struct Player {
     pub race_id: Option<i32>,
     pub gender: Option<String>,
     pub current_channel_id: Option<String>,
+    pub gold: i32,
+    pub silver: i32,
+    pub hit: i32,
+    pub max_hit: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+    pub movement: i32,
+    pub max_movement: i32,
+    pub position: String,
+    pub practices: i32,
+    /// Conditions from 0 to 48: how drunk, how full, how watered and how fed
+    pub drunk: i32,
+    pub fullness: i32,
+    pub thirst: i32,
+    pub hunger: i32,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -27,12 +43,47 @@ impl Player {
             race_id: None,
             gender: None,
             current_channel_id: Some("vnum_3014".to_string()), // Start in Market Square (Midgaard)
+            gold: 0,
+            silver: 0,
+            hit: 20,
+            max_hit: 20,
+            mana: 100,
+            max_mana: 100,
+            movement: 100,
+            max_movement: 100,
+            position: Position::Standing.as_str().to_string(),
+            practices: 5,
+            drunk: 0,
+            fullness: 48,
+            thirst: 48,
+            hunger: 48,
             created_at: now,
             updated_at: now,
         }
     }
 
+    /// The player's stored position (fighting is tracked separately, in the fight map)
+    pub fn position(&self) -> Position {
+        Position::parse_or_standing(&self.position)
+    }
+
     pub fn is_character_complete(&self) -> bool {
         self.class_id.is_some() && self.race_id.is_some() && self.gender.is_some()
     }
+
+    /// Total purse value in silver (1 gold = 100 silver)
+    pub fn wealth(&self) -> i32 {
+        self.silver + self.gold * 100
+    }
+
+    /// The gold and silver to pay a cost in silver with, spending silver first and
+    /// breaking gold when that's not enough (negative silver is the change)
+    pub fn payment(&self, cost: i32) -> (i32, i32) {
+        if self.silver < cost {
+            let gold = (cost - self.silver + 99) / 100;
+            (gold, cost - 100 * gold)
+        } else {
+            (0, cost)
+        }
+    }
 }
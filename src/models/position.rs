@@ -0,0 +1,78 @@
+/// What a character is doing, from ROM's POS_* values (ordered from worst to best)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Position {
+    Dead,
+    Mortal,
+    Incapacitated,
+    Stunned,
+    Sleeping,
+    Resting,
+    Sitting,
+    Fighting,
+    Standing,
+}
+
+impl Position {
+    /// Parse a position from the database or an area file ("sleeping" or ROM's "sleep")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "dead" => Some(Position::Dead),
+            "mort" | "mortal" => Some(Position::Mortal),
+            "incap" | "incapacitated" => Some(Position::Incapacitated),
+            "stun" | "stunned" => Some(Position::Stunned),
+            "sleep" | "sleeping" => Some(Position::Sleeping),
+            "rest" | "resting" => Some(Position::Resting),
+            "sit" | "sitting" => Some(Position::Sitting),
+            "fight" | "fighting" => Some(Position::Fighting),
+            "stand" | "standing" => Some(Position::Standing),
+            _ => None,
+        }
+    }
+
+    /// Parse a stored position, treating anything unrecognised as standing
+    pub fn parse_or_standing(s: &str) -> Self {
+        Self::parse(s).unwrap_or(Position::Standing)
+    }
+
+    /// Convert to database string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Position::Dead => "dead",
+            Position::Mortal => "mortal",
+            Position::Incapacitated => "incapacitated",
+            Position::Stunned => "stunned",
+            Position::Sleeping => "sleeping",
+            Position::Resting => "resting",
+            Position::Sitting => "sitting",
+            Position::Fighting => "fighting",
+            Position::Standing => "standing",
+        }
+    }
+
+    /// Why someone in this position can't do something that needs them on their feet
+    pub fn excuse(&self) -> Option<&'static str> {
+        match self {
+            Position::Dead => Some("Lie still; you are DEAD."),
+            Position::Mortal | Position::Incapacitated => Some("You are hurt far too bad for that."),
+            Position::Stunned => Some("You are too stunned to do that."),
+            Position::Sleeping => Some("In your dreams, or what?"),
+            Position::Resting => Some("Nah... You feel too relaxed..."),
+            Position::Sitting => Some("Better stand up first."),
+            Position::Fighting | Position::Standing => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rom_and_stored_names() {
+        assert_eq!(Position::parse("sleep"), Some(Position::Sleeping));
+        assert_eq!(Position::parse("resting"), Some(Position::Resting));
+        assert_eq!(Position::parse_or_standing("nonsense"), Position::Standing);
+        assert!(Position::Sleeping < Position::Resting);
+        assert!(Position::Fighting.excuse().is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::area::types::{RoomFlags, SectorType};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
@@ -7,6 +8,14 @@ pub struct Room {
     pub channel_name: String,
     pub description: String,
     pub attached_channel_id: Option<String>,
+    /// Hit point and movement regeneration rate in percent
+    pub heal_rate: i32,
+    /// Mana regeneration rate in percent
+    pub mana_rate: i32,
+    /// ROM room flags as area file letters; see `flags()`
+    pub room_flags: String,
+    /// ROM sector type code; see `sector()`
+    pub sector_type: i32,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -19,8 +28,52 @@ impl Room {
             channel_name,
             description: "A mysterious room in the Slack workspace.".to_string(),
             attached_channel_id: Some(channel_id), // Auto-attach to the channel by default
+            heal_rate: 100,
+            mana_rate: 100,
+            room_flags: String::new(),
+            sector_type: SectorType::Inside as i32,
             created_at: now,
             updated_at: now,
         }
     }
+
+    pub fn flags(&self) -> RoomFlags {
+        RoomFlags::from_str(&self.room_flags)
+    }
+
+    pub fn sector(&self) -> SectorType {
+        SectorType::from_code(self.sector_type).unwrap_or(SectorType::Inside)
+    }
+}
+
+/// Something in a room that can be looked at by keyword, like a fountain or a sign
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RoomExtraDescription {
+    pub id: i32,
+    pub room_id: String,
+    pub keywords: String,
+    pub description: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl RoomExtraDescription {
+    pub fn new(room_id: String, keywords: String, description: String) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: 0, // Will be set by database
+            room_id,
+            keywords,
+            description,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Check if this extra description matches a keyword
+    pub fn matches_keyword(&self, keyword: &str) -> bool {
+        self.keywords
+            .split_whitespace()
+            .any(|k| k.eq_ignore_ascii_case(keyword))
+    }
 }
@@ -0,0 +1,68 @@
+use crate::area::types::{item_type_name, AreaShop};
+use crate::models::Object;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Shop {
+    pub id: i32,
+    pub keeper_vnum: i32,
+    pub area_name: String,
+    pub buy_types: Vec<String>,
+    pub profit_buy: i32,
+    pub profit_sell: i32,
+    pub open_hour: i32,
+    pub close_hour: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Shop {
+    pub fn from_area_shop(area_name: String, shop: &AreaShop) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: 0, // Will be set by database
+            keeper_vnum: shop.keeper_vnum,
+            area_name,
+            buy_types: shop.buy_types.iter()
+                .filter_map(|&code| item_type_name(code))
+                .map(String::from)
+                .collect(),
+            profit_buy: shop.profit_buy,
+            profit_sell: shop.profit_sell,
+            open_hour: shop.open_hour,
+            close_hour: shop.close_hour,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Check whether the keeper deals in this kind of item
+    pub fn will_buy(&self, object: &Object) -> bool {
+        self.buy_types.iter().any(|t| t.eq_ignore_ascii_case(&object.item_type))
+    }
+
+    /// Price (in silver) a player pays for an object
+    pub fn buy_price(&self, object: &Object) -> i32 {
+        object.cost * self.profit_buy / 100
+    }
+
+    /// Price (in silver) the keeper pays for an object, before stock adjustments
+    pub fn sell_price(&self, object: &Object) -> i32 {
+        if !self.will_buy(object) {
+            return 0;
+        }
+        object.cost * self.profit_sell / 100
+    }
+
+    /// The keeper's excuse if the shop is closed at the given game hour
+    pub fn closed_message(&self, hour: i32) -> Option<&'static str> {
+        if hour < self.open_hour {
+            Some("Sorry, I am closed. Come back later.")
+        } else if hour > self.close_hour {
+            Some("Sorry, I am closed. Come back tomorrow.")
+        } else {
+            None
+        }
+    }
+}